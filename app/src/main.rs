@@ -8,7 +8,7 @@ mod windows;
 mod rpc;
 
 use anyhow::Result;
-use clap::{Arg, ArgMatches};
+use clap::{Arg, ArgGroup, ArgMatches};
 use image::GenericImageView;
 use rust_embed::RustEmbed;
 use serde_json::json;
@@ -32,7 +32,51 @@ fn parse_args() -> ArgMatches {
         .version(env!("CARGO_PKG_VERSION"))
         .author("by Karlitos")
         .about("A save editor for Mass Effect Trilogy (and Legendary)")
-        .arg(Arg::new("SAVE").help("Mass Effect save file"));
+        .arg(Arg::new("SAVE").help("Mass Effect save file"))
+        .arg(
+            Arg::new("script")
+                .long("script")
+                .value_name("FILE")
+                .takes_value(true)
+                .requires("target")
+                .help("Rhai script to run on the save once opened, or on each save of the batch"),
+        )
+        .arg(
+            Arg::new("batch")
+                .long("batch")
+                .value_name("FILES")
+                .takes_value(true)
+                .multiple_values(true)
+                .requires("operation")
+                .help("Saves or glob patterns to edit and save without opening the window"),
+        )
+        .arg(
+            Arg::new("preset")
+                .long("preset")
+                .value_name("FILE")
+                .takes_value(true)
+                .requires("batch")
+                .help("Plot preset to apply on each save of the batch"),
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .value_name("FIELD=VALUE")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .requires("batch")
+                .help("Field to set on each save of the batch, like `player.credits=1000000` or `plot.bool[42]=true`"),
+        )
+        .arg(
+            Arg::new("report")
+                .long("report")
+                .value_name("FILE")
+                .takes_value(true)
+                .requires("batch")
+                .help("File to write the batch report to, it is also printed"),
+        )
+        .group(ArgGroup::new("target").args(&["SAVE", "batch"]))
+        .group(ArgGroup::new("operation").args(&["script", "preset", "set"]));
 
     app.get_matches()
 }
@@ -54,6 +98,7 @@ async fn main() -> Result<()> {
     }
 
     let args = parse_args();
+    rpc::command_line_batch(&args)?;
 
     let event_loop = EventLoop::<rpc::Event>::with_user_event();
     let window = WindowBuilder::new()
@@ -1,16 +1,28 @@
 use std::{
-    fs,
+    cmp::Reverse,
+    env, fs,
     path::{Path, PathBuf},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use anyhow::{Error, Result};
+use anyhow::{bail, Context, Error, Result};
+use clap::ArgMatches;
 use serde::{Deserialize, Serialize};
 
-use super::{dialog, Event, RpcUtils};
+use super::{
+    dialog,
+    file_watcher::FILE_WATCHER,
+    settings::{BackupSettings, RecentFile, Settings},
+    Event, RpcUtils,
+};
 
 // Commands
 pub fn init(utils: &RpcUtils) {
-    utils.window.set_visible(true);
+    // Command line batches run without window
+    if !utils.args.is_present("batch") {
+        utils.window.set_visible(true);
+    }
 }
 
 pub fn minimize(utils: &RpcUtils) {
@@ -69,11 +81,26 @@ pub fn open_external_link(_: &RpcUtils, link: PathBuf) -> Result<()> {
 }
 
 pub fn save_file(_: &RpcUtils, rpc_file: RpcFile) -> Result<()> {
-    write_file(rpc_file)
+    if FILE_WATCHER.is_outdated(&rpc_file.path) {
+        bail!("The save has been modified by the game since it was opened, reload or merge it before saving");
+    }
+
+    let path = rpc_file.path.clone();
+    write_file(rpc_file)?;
+    FILE_WATCHER.acknowledge(&path);
+    Ok(())
 }
 
-pub fn open_save(utils: &RpcUtils, last_dir: bool) -> Result<Option<RpcFile>> {
-    match dialog::open_save(utils.window, last_dir) {
+/// Also used to keep the editor version of a file modified by the game
+pub fn watch_file(utils: &RpcUtils, path: PathBuf) -> Result<()> {
+    FILE_WATCHER.watch(&path, utils.event_proxy.clone())
+}
+
+/// Opens the dialog in the last directory of `game`, or in the one of the last save opened
+pub fn open_save(utils: &RpcUtils, game: Option<String>) -> Result<Option<RpcFile>> {
+    // The settings errors are reported when they are loaded
+    let directory = Settings::load().ok().and_then(|settings| settings.last_dir(game.as_deref()));
+    match dialog::open_save(utils.window, directory) {
         Some(path) => open_file(path).map(Some),
         None => Ok(None),
     }
@@ -100,6 +127,243 @@ pub fn export_head_morph_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
     Ok(result)
 }
 
+pub fn import_plot_preset(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::import_plot_preset(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn export_plot_preset_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_plot_preset(utils.window);
+    Ok(result)
+}
+
+pub fn open_script(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::open_script(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn save_script_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::save_script(utils.window);
+    Ok(result)
+}
+
+pub fn open_command_line_script(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match utils.args.value_of("script") {
+        Some(path) => open_file(PathBuf::from(path)).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Prints what the command line script printed, on the terminal the editor has been started from
+pub fn print_script_output(_: &RpcUtils, lines: Vec<String>) -> Result<()> {
+    for line in lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+pub fn import_plot_labels(utils: &RpcUtils) -> Result<Option<RpcFile>> {
+    match dialog::import_plot_labels(utils.window) {
+        Some(path) => open_file(path).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn export_plot_labels_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    let result = dialog::export_plot_labels(utils.window);
+    Ok(result)
+}
+
+pub fn load_user_plot_labels(_: &RpcUtils, file_name: PathBuf) -> Result<Option<RpcFile>> {
+    let path = user_config_file(&file_name)?;
+    if path.is_file() {
+        open_file(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn save_user_plot_labels(_: &RpcUtils, rpc_file: RpcFile) -> Result<()> {
+    let RpcFile { path, file } = rpc_file;
+    let path = user_config_file(&path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_file(RpcFile { path, file })
+}
+
+pub fn list_saves(_: &RpcUtils) -> Result<Vec<LibrarySave>> {
+    const EXTENSIONS: [&str; 2] = ["pcsav", "MassEffectSave"];
+
+    let bioware_dir = match dialog::bioware_dir() {
+        Some(bioware_dir) => bioware_dir,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut saves = Vec::new();
+    for (save_dir, game) in SAVE_DIRS {
+        let careers = match fs::read_dir(bioware_dir.join(save_dir)) {
+            Ok(careers) => careers,
+            Err(_) => continue,
+        };
+
+        for career in careers.flatten().filter(|career| career.path().is_dir()) {
+            let career_name = career.file_name().to_string_lossy().into_owned();
+            // An unreadable career is skipped, the others are still listed
+            let files = match fs::read_dir(career.path()) {
+                Ok(files) => files,
+                Err(_) => continue,
+            };
+            for file in files.flatten() {
+                let path = file.path();
+                let is_save = path
+                    .extension()
+                    .map(|ext| EXTENSIONS.iter().any(|save_ext| ext.eq_ignore_ascii_case(save_ext)))
+                    .unwrap_or_default();
+                if !is_save {
+                    continue;
+                }
+
+                let modified = file
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or_default();
+                saves.push(LibrarySave {
+                    path,
+                    game: game.to_owned(),
+                    career: career_name.clone(),
+                    modified,
+                });
+            }
+        }
+    }
+    Ok(saves)
+}
+
+pub fn create_career(_: &RpcUtils, career: CareerFiles) -> Result<()> {
+    let CareerFiles { path, saves } = career;
+    check_career_dir(&path)?;
+    if path.exists() {
+        bail!("The career `{}` already exists", path.display());
+    }
+
+    fs::create_dir(&path)?;
+    for RpcFile { path: file_name, file } in saves {
+        // Only keep the file name, the saves must stay in the career directory
+        let file_name = file_name.file_name().context("invalid file name")?;
+        fs::write(path.join(file_name), file.decode()?)?;
+    }
+    Ok(())
+}
+
+/// Writes the renamed saves to the new career then moves the old one to the trash
+pub fn rename_career(utils: &RpcUtils, params: RenameCareerParams) -> Result<PathBuf> {
+    let RenameCareerParams { from, to } = params;
+    check_career_dir(&from)?;
+    create_career(utils, to)?;
+    move_to_trash(&from)
+}
+
+pub fn delete_career(_: &RpcUtils, path: PathBuf) -> Result<PathBuf> {
+    check_career_dir(&path)?;
+    move_to_trash(&path)
+}
+
+pub fn list_backups(_: &RpcUtils, path: PathBuf) -> Result<Vec<Backup>> {
+    read_backups(&path)
+}
+
+/// The current file is backed up too, so a restore can be undone
+pub fn restore_backup(_: &RpcUtils, params: RestoreBackupParams) -> Result<()> {
+    let RestoreBackupParams { backup, path } = params;
+    if !read_backups(&path)?.iter().any(|other| other.path == backup) {
+        bail!("`{}` is not a backup of `{}`", backup.display(), path.display());
+    }
+
+    let file = fs::read(&backup)?;
+    if path.exists() {
+        backup_file(&path)?;
+    }
+    fs::write(&path, file)?;
+    FILE_WATCHER.acknowledge(&path);
+    Ok(())
+}
+
+pub fn get_settings(_: &RpcUtils) -> Result<Settings> {
+    Settings::load()
+}
+
+pub fn set_settings(_: &RpcUtils, settings: Settings) -> Result<()> {
+    settings.save()
+}
+
+pub fn add_recent_file(_: &RpcUtils, recent_file: RecentFile) -> Result<Settings> {
+    let mut settings = Settings::load()?;
+    settings.add_recent_file(recent_file);
+    settings.save()?;
+    Ok(settings)
+}
+
+pub fn open_tlk_dialog(utils: &RpcUtils) -> Result<Option<PathBuf>> {
+    Ok(dialog::open_tlk(utils.window))
+}
+
+pub fn load_tlk(_: &RpcUtils, game: String) -> Result<Option<RpcFile>> {
+    match Settings::load()?.tlk_file(&game) {
+        Some(path) => open_file(path.to_owned()).map(Some),
+        None => Ok(None),
+    }
+}
+
+pub fn open_saves_dialog(utils: &RpcUtils) -> Result<Vec<PathBuf>> {
+    Ok(dialog::open_saves(utils.window))
+}
+
+pub fn expand_batch_paths(_: &RpcUtils, patterns: Vec<String>) -> Result<Vec<PathBuf>> {
+    expand_paths(patterns.iter().map(String::as_str))
+}
+
+pub fn open_command_line_batch(utils: &RpcUtils) -> Result<Option<CommandLineBatch>> {
+    command_line_batch(utils.args)
+}
+
+/// Prints the report of the command line batch and exits, with 1 if a save failed
+pub fn finish_batch(utils: &RpcUtils, report: BatchReport) -> Result<()> {
+    println!("{}", report.text);
+    if let Some(path) = utils.args.value_of("report") {
+        if let Err(err) = fs::write(path, &report.text) {
+            eprintln!("Failed to write the report: {}", err);
+        }
+    }
+    process::exit(if report.failed > 0 { 1 } else { 0 })
+}
+
+/// The batch given on the command line, also read before opening the window to fail early
+pub fn command_line_batch(args: &ArgMatches) -> Result<Option<CommandLineBatch>> {
+    let patterns = match args.values_of("batch") {
+        Some(patterns) => patterns,
+        None => return Ok(None),
+    };
+    let paths = expand_paths(patterns)?;
+
+    let operation = if let Some(path) = args.value_of("preset") {
+        CommandLineOperation::Preset(open_file(PathBuf::from(path))?)
+    } else if let Some(path) = args.value_of("script") {
+        CommandLineOperation::Script(open_file(PathBuf::from(path))?)
+    } else {
+        let assignments = args.values_of("set").into_iter().flatten().map(String::from).collect();
+        CommandLineOperation::Assignments(assignments)
+    };
+    Ok(Some(CommandLineBatch { paths, operation }))
+}
+
 pub fn load_database(_: &RpcUtils, path: PathBuf) -> Result<RpcFile> {
     #[cfg(not(debug_assertions))]
     let path = std::env::current_exe()?.parent().map(|parent| parent.join(&path)).unwrap_or(path);
@@ -108,6 +372,96 @@ pub fn load_database(_: &RpcUtils, path: PathBuf) -> Result<RpcFile> {
 }
 
 // Utils
+pub fn user_config_file(file_name: &Path) -> Result<PathBuf> {
+    // Only keep the file name, the frontend must not write outside of the config directory
+    let file_name = file_name.file_name().context("invalid file name")?;
+    let config_dir = dirs::config_dir().context("failed to find the user config directory")?;
+    Ok(config_dir.join("trilogy-save-editor").join(file_name))
+}
+
+// Save folders relative to the BioWare directory, each sub folder is a career
+const SAVE_DIRS: [(&str, &str); 6] = [
+    ("Mass Effect/Save", "Mass Effect"),
+    ("Mass Effect 2/Save", "Mass Effect 2"),
+    ("Mass Effect 3/Save", "Mass Effect 3"),
+    ("Mass Effect Legendary Edition/Save/ME1", "Mass Effect Legendary"),
+    ("Mass Effect Legendary Edition/Save/ME2", "Mass Effect 2 Legendary"),
+    ("Mass Effect Legendary Edition/Save/ME3", "Mass Effect 3 Legendary"),
+];
+
+/// Checks that `career` is a folder right below a game save directory, not the BioWare directory
+/// or a save directory itself, as careers are moved to the trash as a whole
+fn check_career_dir(career: &Path) -> Result<()> {
+    let bioware_dir = dialog::bioware_dir().context("failed to find the BioWare directory")?;
+
+    // An existing career is resolved so a link cannot point outside of the save directory
+    let career = if career.exists() { career.canonicalize()? } else { career.to_owned() };
+    let is_career = match (career.parent(), career.file_name()) {
+        (Some(parent), Some(_)) => {
+            let parent = parent.canonicalize()?;
+            SAVE_DIRS.iter().any(|(save_dir, _)| {
+                bioware_dir
+                    .join(save_dir)
+                    .canonicalize()
+                    .map(|dir| dir == parent)
+                    .unwrap_or_default()
+            })
+        }
+        _ => false,
+    };
+    if !is_career {
+        bail!("`{}` is not a career directory", career.display());
+    }
+    Ok(())
+}
+
+fn move_to_trash(path: &Path) -> Result<PathBuf> {
+    let name = path.file_name().context("invalid career directory")?.to_string_lossy();
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let trash = user_config_file(Path::new("trash"))?.join(format!("{}_{}", name, secs));
+    fs::create_dir_all(&trash)?;
+
+    // `rename` fails across drives, so the saves are moved one by one
+    for file in fs::read_dir(path)?.flatten() {
+        let to = trash.join(file.file_name());
+        if fs::rename(file.path(), &to).is_err() {
+            fs::copy(file.path(), &to)?;
+            fs::remove_file(file.path())?;
+        }
+    }
+    fs::remove_dir(path)?;
+    Ok(trash)
+}
+
+// Globs are expanded here as the Windows shell does not
+fn expand_paths<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Result<Vec<PathBuf>> {
+    let current_dir = env::current_dir()?;
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        let matches = if pattern.contains(['*', '?', '[']) {
+            glob::glob(pattern)?.collect::<Result<Vec<_>, _>>()?
+        } else {
+            vec![PathBuf::from(pattern)]
+        };
+
+        let matches: Vec<_> = matches
+            .into_iter()
+            .filter(|path| path.is_file())
+            .map(|path| current_dir.join(path))
+            .collect();
+        if matches.is_empty() {
+            bail!("`{}` matches no file", pattern);
+        }
+
+        for path in matches {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
+
 fn open_file(path: PathBuf) -> Result<RpcFile> {
     let file = fs::read(path.canonicalize()?)?;
     let unencoded_size = file.len();
@@ -120,24 +474,132 @@ fn write_file(rpc_file: RpcFile) -> Result<()> {
 
     // Backup if file exists
     if path.exists() {
-        if let Some(ext) = path.extension() {
-            let mut ext = ext.to_owned();
-            ext.push(".bak");
-            let to = Path::with_extension(&path, ext);
-            fs::copy(&path, to)?;
-        }
+        backup_file(&path)?;
     }
     fs::write(path, file.decode()?)?;
 
     Ok(())
 }
 
+// Backups
+// Each file has its own directory in the backup store, a backup is named `<millis>_<file name>`
+fn backup_dir(path: &Path) -> Result<PathBuf> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+    let key: String = path
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    Ok(user_config_file(Path::new("backups"))?.join(key))
+}
+
+fn backup_file(path: &Path) -> Result<()> {
+    let file_name = path.file_name().context("invalid file name")?.to_string_lossy();
+    let dir = backup_dir(path)?;
+    fs::create_dir_all(&dir)?;
+
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    fs::copy(path, dir.join(format!("{}_{}", millis, file_name)))?;
+
+    prune_backups(path)
+}
+
+/// Removes the oldest backups beyond the retention count or the size cap, the newest is always kept
+fn prune_backups(path: &Path) -> Result<()> {
+    // A save is not refused over the settings, their errors are reported when they are loaded
+    let BackupSettings { retention, max_size_mb } =
+        Settings::load().map(|settings| settings.backup).unwrap_or_default();
+    let max_size = max_size_mb.saturating_mul(1024 * 1024);
+
+    let mut total_size = 0;
+    for (idx, backup) in read_backups(path)?.into_iter().enumerate() {
+        total_size += backup.size;
+        if idx > 0 && (idx >= retention || total_size > max_size) {
+            fs::remove_file(backup.path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Newest first
+fn read_backups(path: &Path) -> Result<Vec<Backup>> {
+    let dir = backup_dir(path)?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups: Vec<Backup> = fs::read_dir(dir)?
+        .flatten()
+        .filter_map(|file| {
+            let name = file.file_name().to_string_lossy().into_owned();
+            let timestamp = name.split_once('_')?.0.parse::<u64>().ok()?;
+            let size = file.metadata().ok()?.len();
+            Some(Backup { path: file.path(), timestamp, size })
+        })
+        .collect();
+    backups.sort_by_key(|backup| Reverse(backup.timestamp));
+    Ok(backups)
+}
+
 #[derive(Deserialize, Default)]
 pub struct DialogParams {
     pub path: PathBuf,
     pub filters: Vec<(String, Vec<String>)>,
 }
 
+#[derive(Deserialize, Default)]
+pub struct CareerFiles {
+    path: PathBuf,
+    saves: Vec<RpcFile>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct RenameCareerParams {
+    from: PathBuf,
+    to: CareerFiles,
+}
+
+#[derive(Serialize)]
+pub struct Backup {
+    path: PathBuf,
+    /// Milliseconds since the Unix epoch
+    timestamp: u64,
+    size: u64,
+}
+
+#[derive(Deserialize, Default)]
+pub struct RestoreBackupParams {
+    backup: PathBuf,
+    path: PathBuf,
+}
+
+#[derive(Serialize)]
+pub struct CommandLineBatch {
+    paths: Vec<PathBuf>,
+    operation: CommandLineOperation,
+}
+
+#[derive(Serialize)]
+pub enum CommandLineOperation {
+    Preset(RpcFile),
+    Script(RpcFile),
+    Assignments(Vec<String>),
+}
+
+#[derive(Deserialize, Default)]
+pub struct BatchReport {
+    text: String,
+    failed: usize,
+}
+
+#[derive(Serialize)]
+pub struct LibrarySave {
+    path: PathBuf,
+    game: String,
+    career: String,
+    modified: u64,
+}
+
 #[derive(Deserialize, Serialize, Default)]
 pub struct RpcFile {
     pub path: PathBuf,
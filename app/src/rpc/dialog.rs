@@ -5,22 +5,34 @@ use wry::application::window::Window;
 
 use super::command::DialogParams;
 
-pub fn open_save(window: &Window, last_dir: bool) -> Option<PathBuf> {
+pub fn open_save(window: &Window, directory: Option<PathBuf>) -> Option<PathBuf> {
     let mut dialog = rfd::FileDialog::new()
         .add_filter("Mass Effect Trilogy Save", &["pcsav", "xbsav", "ps4sav", "MassEffectSave"])
         .add_filter("All Files", &["*"]);
 
     dialog = with_parent(dialog, window);
 
-    if !last_dir {
-        if let Some(bioware_dir) = bioware_dir() {
-            dialog = dialog.set_directory(bioware_dir);
-        }
+    if let Some(directory) = directory.or_else(bioware_dir) {
+        dialog = dialog.set_directory(directory);
     }
 
     dialog.pick_file()
 }
 
+pub fn open_saves(window: &Window) -> Vec<PathBuf> {
+    let mut dialog = rfd::FileDialog::new()
+        .add_filter("Mass Effect Trilogy Save", &["pcsav", "xbsav", "ps4sav", "MassEffectSave"])
+        .add_filter("All Files", &["*"]);
+
+    dialog = with_parent(dialog, window);
+
+    if let Some(bioware_dir) = bioware_dir() {
+        dialog = dialog.set_directory(bioware_dir);
+    }
+
+    dialog.pick_files().unwrap_or_default()
+}
+
 pub fn save_save(window: &Window, params: DialogParams) -> Option<PathBuf> {
     let DialogParams { path, filters } = params;
 
@@ -59,8 +71,52 @@ pub fn export_head_morph(window: &Window) -> Option<PathBuf> {
     with_parent(dialog, window).save_file()
 }
 
+pub fn import_plot_preset(window: &Window) -> Option<PathBuf> {
+    let dialog =
+        rfd::FileDialog::new().add_filter("Plot Preset", &["ron"]).add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
+pub fn export_plot_preset(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Plot Preset", &["ron"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn import_plot_labels(window: &Window) -> Option<PathBuf> {
+    let dialog =
+        rfd::FileDialog::new().add_filter("Plot Labels", &["ron"]).add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
+pub fn export_plot_labels(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Plot Labels", &["ron"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn open_script(window: &Window) -> Option<PathBuf> {
+    let dialog =
+        rfd::FileDialog::new().add_filter("Rhai Script", &["rhai"]).add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
+pub fn save_script(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new().add_filter("Rhai Script", &["rhai"]);
+    with_parent(dialog, window).save_file()
+}
+
+pub fn open_tlk(window: &Window) -> Option<PathBuf> {
+    let dialog = rfd::FileDialog::new()
+        .add_filter("Talk Table", &["tlk", "xml"])
+        .add_filter("All Files", &["*"]);
+
+    with_parent(dialog, window).pick_file()
+}
+
 #[cfg(target_os = "windows")]
-fn bioware_dir() -> Option<PathBuf> {
+pub fn bioware_dir() -> Option<PathBuf> {
     dirs::document_dir().and_then(|mut path| {
         path.push("BioWare\\");
         path.is_dir().then(|| path)
@@ -72,7 +128,7 @@ fn bioware_dir() -> Option<PathBuf> {
 // Mass Effect games installed in the default steam library, in
 // the user's home directory.
 #[cfg(target_os = "linux")]
-fn bioware_dir() -> Option<PathBuf> {
+pub fn bioware_dir() -> Option<PathBuf> {
     dirs::home_dir().and_then(|mut path| {
         path.push(".steam/root/steamapps/compatdata/1328670/pfx/drive_c/users/steamuser/My Documents/BioWare/");
         path.is_dir().then(|| path)
@@ -80,7 +136,7 @@ fn bioware_dir() -> Option<PathBuf> {
 }
 
 #[cfg(all(not(target_os = "linux"), not(target_os = "windows")))]
-fn bioware_dir() -> Option<PathBuf> {
+pub fn bioware_dir() -> Option<PathBuf> {
     None
 }
 
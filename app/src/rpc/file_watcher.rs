@@ -0,0 +1,114 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use serde_json::json;
+use wry::application::event_loop::EventLoopProxy;
+
+use super::Event;
+
+lazy_static! {
+    pub static ref FILE_WATCHER: FileWatcher = FileWatcher::new();
+}
+
+struct WatchedFile {
+    path: PathBuf,
+    // What the editor has loaded or written
+    modified: Option<SystemTime>,
+    // What the frontend has been notified of
+    notified: Option<SystemTime>,
+    _watcher: RecommendedWatcher,
+}
+
+/// Watches the open save, the games write a new file and rename it, so the directory is watched
+pub struct FileWatcher {
+    watched: Mutex<Option<WatchedFile>>,
+}
+
+impl FileWatcher {
+    fn new() -> Self {
+        FileWatcher { watched: Mutex::new(None) }
+    }
+
+    pub fn watch(&self, path: &Path, proxy: EventLoopProxy<Event>) -> Result<()> {
+        let path = path.canonicalize()?;
+        let dir = path.parent().context("invalid file path")?;
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::watcher(tx, Duration::from_millis(500))?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+        let modified = modified(&path);
+        // Dropping the previous watcher ends its thread
+        *self.watched.lock() =
+            Some(WatchedFile { path, modified, notified: modified, _watcher: watcher });
+
+        thread::spawn(move || {
+            for event in rx {
+                let path = match event {
+                    DebouncedEvent::Create(path)
+                    | DebouncedEvent::Write(path)
+                    | DebouncedEvent::Rename(_, path) => path,
+                    _ => continue,
+                };
+
+                if let Some(path) = FILE_WATCHER.changed_since_notified(&path) {
+                    let _ = proxy.send_event(Event::DispatchCustomEvent(
+                        "tse_file_changed",
+                        json!({ "path": path }),
+                    ));
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// The file has been modified by something else than the editor since it was loaded
+    pub fn is_outdated(&self, path: &Path) -> bool {
+        match *self.watched.lock() {
+            Some(ref watched) if is_same_file(&watched.path, path) => {
+                modified(path) != watched.modified
+            }
+            _ => false,
+        }
+    }
+
+    /// The editor wrote the file, or the user chose to keep its version
+    pub fn acknowledge(&self, path: &Path) {
+        if let Some(ref mut watched) = *self.watched.lock() {
+            if is_same_file(&watched.path, path) {
+                watched.modified = modified(path);
+                watched.notified = watched.modified;
+            }
+        }
+    }
+
+    fn changed_since_notified(&self, path: &Path) -> Option<PathBuf> {
+        match *self.watched.lock() {
+            Some(ref mut watched) if is_same_file(&watched.path, path) => {
+                let modified = modified(path);
+                (modified != watched.notified).then(|| {
+                    watched.notified = modified;
+                    watched.path.clone()
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn is_same_file(watched: &Path, path: &Path) -> bool {
+    path.canonicalize().map(|path| path == watched).unwrap_or_default()
+}
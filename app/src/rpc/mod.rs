@@ -1,5 +1,9 @@
 mod command;
 mod dialog;
+mod file_watcher;
+mod settings;
+
+pub use command::command_line_batch;
 
 use std::env;
 use std::path::PathBuf;
@@ -88,6 +92,18 @@ pub fn rpc_handler(mut req: RpcRequest, utils: RpcUtils) -> Option<RpcResponse>
             command::download_and_install_update,
             command::import_head_morph,
             command::export_head_morph_dialog,
+            command::import_plot_preset,
+            command::export_plot_preset_dialog,
+            command::import_plot_labels,
+            command::export_plot_labels_dialog,
+            command::open_script,
+            command::save_script_dialog,
+            command::open_command_line_script,
+            command::list_saves,
+            command::get_settings,
+            command::open_tlk_dialog,
+            command::open_saves_dialog,
+            command::open_command_line_batch,
         ]);
 
         call_commands_with_param!(req, utils => [
@@ -97,6 +113,20 @@ pub fn rpc_handler(mut req: RpcRequest, utils: RpcUtils) -> Option<RpcResponse>
             command::save_save_dialog,
             command::reload_save,
             command::load_database,
+            command::load_user_plot_labels,
+            command::save_user_plot_labels,
+            command::create_career,
+            command::rename_career,
+            command::delete_career,
+            command::list_backups,
+            command::restore_backup,
+            command::set_settings,
+            command::add_recent_file,
+            command::load_tlk,
+            command::watch_file,
+            command::expand_batch_paths,
+            command::finish_batch,
+            command::print_script_output,
         ]);
 
         bail!("Wrong RPC method, got: {}", req.method)
@@ -0,0 +1,118 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::command::user_config_file;
+
+const SETTINGS: &str = "settings.json";
+const MAX_RECENT_FILES: usize = 10;
+
+/// The preferences kept between runs, in the user config directory
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct Settings {
+    /// Newest first
+    recent_files: Vec<RecentFile>,
+    /// The directory of the last save opened, by game
+    last_dirs: BTreeMap<String, PathBuf>,
+    pub backup: BackupSettings,
+    language: Language,
+    default_platform: SavePlatform,
+    /// The TLK file picked for each game, to resolve the localized ids
+    tlk_files: BTreeMap<String, PathBuf>,
+}
+
+impl Settings {
+    /// The defaults if there is no settings file yet, an error if it cannot be read
+    pub fn load() -> Result<Self> {
+        let path = user_config_file(Path::new(SETTINGS))?;
+        let file = match fs::read(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Default::default()),
+            Err(err) => return Err(err.into()),
+        };
+        serde_json::from_slice(&file)
+            .with_context(|| format!("Invalid settings `{}`", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = user_config_file(Path::new(SETTINGS))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Moves the file on top of the recent files and remembers its directory for its game
+    pub fn add_recent_file(&mut self, recent_file: RecentFile) {
+        if let Some(parent) = recent_file.path.parent() {
+            self.last_dirs.insert(recent_file.game.clone(), parent.to_owned());
+        }
+
+        self.recent_files.retain(|file| file.path != recent_file.path);
+        self.recent_files.insert(0, recent_file);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    /// The last directory of `game`, or the one of the last save opened
+    pub fn last_dir(&self, game: Option<&str>) -> Option<PathBuf> {
+        game.and_then(|game| self.last_dirs.get(game).map(PathBuf::as_path))
+            .or_else(|| self.recent_files.first().and_then(|file| file.path.parent()))
+            .filter(|dir| dir.is_dir())
+            .map(ToOwned::to_owned)
+    }
+
+    pub fn tlk_file(&self, game: &str) -> Option<&Path> {
+        self.tlk_files.get(game).map(PathBuf::as_path)
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct RecentFile {
+    path: PathBuf,
+    game: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[serde(default)]
+pub struct BackupSettings {
+    pub retention: usize,
+    pub max_size_mb: u64,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        BackupSettings { retention: 10, max_size_mb: 100 }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub enum Language {
+    English,
+    French,
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::English
+    }
+}
+
+/// Offered first when saving a save that exists on several platforms
+#[derive(Deserialize, Serialize)]
+pub enum SavePlatform {
+    Pc,
+    Xbox360,
+}
+
+impl Default for SavePlatform {
+    fn default() -> Self {
+        SavePlatform::Pc
+    }
+}
@@ -1,3 +1,6 @@
+// `html!` binds the props of every component, so `()` for the ones without properties
+#![allow(clippy::let_unit_value)]
+
 use std::cell::Ref;
 use std::mem;
 
@@ -7,14 +10,23 @@ use yew::prelude::*;
 
 use crate::{
     gui::{
-        components::{AutoUpdate, NavBar, Tab, TabBar, Table},
+        components::{AutoUpdate, DocumentBar, NavBar, Tab, TabBar, Table},
         format_code,
-        mass_effect_1::{Me1General, Me1Plot, Me1RawData, Me1RawPlot},
-        mass_effect_1_le::{Me1LeGeneral, Me1LeInventory},
-        mass_effect_2::{Me2General, Me2Plot, Me2RawPlot, Me2Type},
-        mass_effect_3::{Me3General, Me3Plot, Me3RawPlot},
+        i18n::tr,
+        mass_effect_1::{
+            Me1General, Me1Inventory, Me1Plot, Me1PlotPresets, Me1RawData, Me1RawPlot, Me1World,
+        },
+        mass_effect_1_le::{Me1LeGeneral, Me1LeInventory, Me1LeMako, Me1LeTalents, Me1LeWorld},
+        mass_effect_2::{
+            Me2General, Me2Plot, Me2PlotChecker, Me2PlotPresets, Me2RawPlot, Me2SuicideMission,
+            Me2Type,
+        },
+        mass_effect_3::{Me3General, Me3Plot, Me3PlotChecker, Me3PlotPresets, Me3RawPlot},
         raw_ui::RawUi,
-        shared::HeadMorph,
+        shared::{
+            BatchEdit, GlobalSearch, HeadMorph, SaveBackups, SaveLibrary, SaveWatcher,
+            ScriptConsole, SettingsPanel,
+        },
         shared::{FloatPlotType, IntPlotType},
     },
     save_data::{
@@ -32,11 +44,23 @@ pub enum Msg {
     DismissNotification,
     Error(Error),
     DismissError,
+    OpenLibrary,
+    CloseLibrary,
+    OpenBackups,
+    CloseBackups,
+    OpenBatch,
+    CloseBatch,
+    OpenSettings,
+    CloseSettings,
 }
 
 pub struct App {
     notification: Option<&'static str>,
     error: Option<Error>,
+    library_opened: bool,
+    backups_opened: bool,
+    batch_opened: bool,
+    settings_opened: bool,
 }
 
 impl Component for App {
@@ -44,7 +68,14 @@ impl Component for App {
     type Properties = ();
 
     fn create(_ctx: &Context<Self>) -> Self {
-        App { notification: None, error: None }
+        App {
+            notification: None,
+            error: None,
+            library_opened: false,
+            backups_opened: false,
+            batch_opened: false,
+            settings_opened: false,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -69,6 +100,38 @@ impl Component for App {
                 self.error = None;
                 true
             }
+            Msg::OpenLibrary => {
+                self.library_opened = true;
+                true
+            }
+            Msg::CloseLibrary => {
+                self.library_opened = false;
+                true
+            }
+            Msg::OpenBackups => {
+                self.backups_opened = true;
+                true
+            }
+            Msg::CloseBackups => {
+                self.backups_opened = false;
+                true
+            }
+            Msg::OpenBatch => {
+                self.batch_opened = true;
+                true
+            }
+            Msg::CloseBatch => {
+                self.batch_opened = false;
+                true
+            }
+            Msg::OpenSettings => {
+                self.settings_opened = true;
+                true
+            }
+            Msg::CloseSettings => {
+                self.settings_opened = false;
+                true
+            }
         }
     }
 
@@ -84,10 +147,34 @@ impl Component for App {
                     onnotification={link.callback(Msg::Notification)}
                     onerror={link.callback(Msg::Error)}
                 >
-                    <NavBar>
+                    <NavBar
+                        onopenlibrary={link.callback(|_| Msg::OpenLibrary)}
+                        onopenbackups={link.callback(|_| Msg::OpenBackups)}
+                        onopenbatch={link.callback(|_| Msg::OpenBatch)}
+                        onopensettings={link.callback(|_| Msg::OpenSettings)}
+                    >
                         <AutoUpdate onerror={link.callback(Msg::Error)} />
                     </NavBar>
+                    <DocumentBar />
+                    <SaveLibrary
+                        opened={self.library_opened}
+                        onclose={link.callback(|_| Msg::CloseLibrary)}
+                    />
+                    <SaveBackups
+                        opened={self.backups_opened}
+                        onclose={link.callback(|_| Msg::CloseBackups)}
+                    />
+                    <BatchEdit
+                        opened={self.batch_opened}
+                        onclose={link.callback(|_| Msg::CloseBatch)}
+                    />
+                    <SettingsPanel
+                        opened={self.settings_opened}
+                        onclose={link.callback(|_| Msg::CloseSettings)}
+                    />
+                    <SaveWatcher onerror={link.callback(Msg::Error)} />
                     <DatabaseProvider onerror={link.callback(Msg::Error)}>
+                        <GlobalSearch />
                         <SaveContent/>
                     </DatabaseProvider>
                 </SaveHandlerProvider>
@@ -139,7 +226,7 @@ impl App {
         html! {
             <div class="absolute w-screen h-[calc(100vh-28px)] grid place-content-center bg-white/30 z-50">
                 <div class="border border-default-border bg-default-bg max-w-xl">
-                    <div class="px-1 bg-theme-tab select-none">{"Error"}</div>
+                    <div class="px-1 bg-theme-tab select-none">{ tr("Error") }</div>
                     <div class="p-1 pt-0.5">
                         { format_code(error.to_string()) }
                         { for chain }
@@ -147,7 +234,7 @@ impl App {
                         <button class="button w-12"
                             onclick={ctx.link().callback(|_| Msg::DismissError)}
                         >
-                            {"OK"}
+                            { tr("OK") }
                         </button>
                     </div>
                 </div>
@@ -185,6 +272,15 @@ fn save_content() -> Html {
 fn mass_effect_1(save_game: Ref<'_, Me1SaveGame>) -> Html {
     let state = save_game.state();
     let plot = state.plot();
+    // Character exports have no world state, and an unparsed one is only kept as is
+    let world = save_game.world_save_package().as_ref().and_then(|world_save_package| {
+        let world = world_save_package.borrow().parsed().cloned()?;
+        Some(html_nested! {
+            <Tab title="World">
+                <Me1World {world} />
+            </Tab>
+        })
+    });
 
     html! {
         <section class="flex-auto flex p-1">
@@ -201,6 +297,17 @@ fn mass_effect_1(save_game: Ref<'_, Me1SaveGame>) -> Html {
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                     />
                 </Tab>
+                <Tab title="Inventory">
+                    <Me1Inventory player={RcRef::clone(&save_game.player)} />
+                </Tab>
+                { for world }
+                <Tab title="Presets">
+                    <Me1PlotPresets
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
+                    />
+                </Tab>
                 <Tab title="Raw Data">
                     <Me1RawData player={RcRef::clone(&save_game.player)} />
                 </Tab>
@@ -211,6 +318,9 @@ fn mass_effect_1(save_game: Ref<'_, Me1SaveGame>) -> Html {
                         floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Script">
+                    <ScriptConsole />
+                </Tab>
             </TabBar>
         </section>
     }
@@ -220,6 +330,21 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
     let me1 = save_game.borrow();
     let plot = me1.plot();
     let head_morph = RcRef::clone(&me1.player().head_morph);
+    // Character exports have no world state
+    let world = me1.no_export().as_ref().map(|no_export| {
+        html_nested! {
+            <Tab title="World">
+                <Me1LeWorld no_export={RcRef::clone(no_export)} />
+            </Tab>
+        }
+    });
+    let mako = me1.no_export().as_ref().map(|no_export| {
+        html_nested! {
+            <Tab title="Mako">
+                <Me1LeMako player={RcRef::clone(&me1.player)} no_export={RcRef::clone(no_export)} />
+            </Tab>
+        }
+    });
 
     html! {
         <section class="flex-auto flex p-1">
@@ -227,18 +352,31 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
                 <Tab title="General">
                     <Me1LeGeneral save_game={RcRef::clone(&save_game)} />
                 </Tab>
+                <Tab title="Talents">
+                    <Me1LeTalents player={RcRef::clone(&me1.player)} squad={RcRef::clone(&me1.squad)} />
+                </Tab>
                 <Tab title="Plot">
                     <Me1Plot
                         booleans={RcRef::clone(&plot.booleans)}
                         integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
                     />
                 </Tab>
+                <Tab title="Presets">
+                    <Me1PlotPresets
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
+                    />
+                </Tab>
                 <Tab title="Inventory">
                     <Me1LeInventory
                         player={RcRef::clone(&me1.player)}
                         squad={RcRef::clone(&me1.squad)}
+                        no_export={me1.no_export().as_ref().map(RcRef::clone)}
                     />
                 </Tab>
+                { for world }
+                { for mako }
                 <Tab title="Head Morph">
                     <HeadMorph {head_morph} />
                 </Tab>
@@ -252,24 +390,31 @@ fn mass_effect_1_le(save_game: RcRef<Me1LeSaveData>) -> Html {
                         floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Script">
+                    <ScriptConsole />
+                </Tab>
             </TabBar>
         </section>
     }
 }
 
 fn mass_effect_2(save_game: Me2Type) -> Html {
-    let (raw_data, plot, me1_plot, head_morph) = match save_game {
+    let (raw_data, plot, me1_plot, head_morph, is_female, level) = match save_game {
         Me2Type::Vanilla(ref me2) => (
             me2.view_opened("Mass Effect 2", true),
             RcRef::clone(&me2.borrow().plot),
             RcRef::clone(&me2.borrow().me1_plot),
             RcRef::clone(&me2.borrow().player().appearance().head_morph),
+            me2.borrow().player().is_female(),
+            me2.borrow().player().level(),
         ),
         Me2Type::Legendary(ref me2) => (
             me2.view_opened("Mass Effect 2", true),
             RcRef::clone(&me2.borrow().plot),
             RcRef::clone(&me2.borrow().me1_plot),
             RcRef::clone(&me2.borrow().player().appearance().head_morph),
+            me2.borrow().player().is_female(),
+            me2.borrow().player().level(),
         ),
     };
     let (plot, me1_plot) = (plot.borrow(), me1_plot.borrow());
@@ -288,6 +433,29 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
                         me1_integers={IntPlotType::Vec(RcRef::clone(&me1_plot.integers))}
                     />
                 </Tab>
+                <Tab title="Presets">
+                    <Me2PlotPresets
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
+                    />
+                </Tab>
+                <Tab title="Checker">
+                    <Me2PlotChecker
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
+                        {is_female}
+                        {level}
+                    />
+                </Tab>
+                <Tab title="Suicide Mission">
+                    <Me2SuicideMission
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::Vec(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
+                    />
+                </Tab>
                 <Tab title="Head Morph">
                     <HeadMorph {head_morph} />
                 </Tab>
@@ -301,6 +469,9 @@ fn mass_effect_2(save_game: Me2Type) -> Html {
                         floats={FloatPlotType::Vec(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Script">
+                    <ScriptConsole />
+                </Tab>
             </TabBar>
         </section>
     }
@@ -324,6 +495,22 @@ fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
                         variables={RcRef::clone(&me3.player_variables)}
                     />
                 </Tab>
+                <Tab title="Presets">
+                    <Me3PlotPresets
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::IndexMap(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::IndexMap(RcRef::clone(&plot.floats))}
+                    />
+                </Tab>
+                <Tab title="Checker">
+                    <Me3PlotChecker
+                        booleans={RcRef::clone(&plot.booleans)}
+                        integers={IntPlotType::IndexMap(RcRef::clone(&plot.integers))}
+                        floats={FloatPlotType::IndexMap(RcRef::clone(&plot.floats))}
+                        is_female={me3.player().is_female()}
+                        level={me3.player().level()}
+                    />
+                </Tab>
                 <Tab title="Head Morph">
                     <HeadMorph {head_morph} />
                 </Tab>
@@ -337,6 +524,9 @@ fn mass_effect_3(save_game: RcRef<Me3SaveGame>) -> Html {
                         floats={FloatPlotType::IndexMap(RcRef::clone(&plot.floats))}
                     />
                 </Tab>
+                <Tab title="Script">
+                    <ScriptConsole />
+                </Tab>
             </TabBar>
         </section>
     }
@@ -1,6 +1,6 @@
 use yew::prelude::*;
 
-use crate::save_data::RcCell;
+use crate::{gui::i18n::tr, save_data::RcCell};
 
 pub enum Msg {
     Toggle,
@@ -43,7 +43,7 @@ impl Component for CheckBox {
         html! {
             <label class="flex items-center gap-1">
                 <input type="checkbox" class="checkbox" {checked} {onchange} />
-                { &ctx.props().label }
+                { tr(&ctx.props().label) }
             </label>
         }
     }
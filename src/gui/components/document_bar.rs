@@ -0,0 +1,163 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        format_code,
+        i18n::{tr, tr_format},
+    },
+    services::save_handler::{Action, SaveHandler},
+};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Select(usize),
+    Close(usize),
+    ConfirmClose,
+    CancelClose,
+}
+
+/// The open saves as tabs, with the content of the clipboard shared between them
+pub struct DocumentBar {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    // The edited save waiting for a confirmation to be closed
+    closing: Option<usize>,
+}
+
+impl Component for DocumentBar {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        DocumentBar { _save_handle, save_handler, closing: None }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                true
+            }
+            Msg::Select(idx) => {
+                self.save_handler.action(Action::SelectDocument(idx));
+                false
+            }
+            Msg::Close(idx) => {
+                let is_dirty = self
+                    .save_handler
+                    .documents
+                    .get(idx)
+                    .map(|document| document.is_dirty)
+                    .unwrap_or_default();
+                if is_dirty {
+                    self.closing = Some(idx);
+                    true
+                } else {
+                    self.save_handler.action(Action::CloseDocument(idx));
+                    false
+                }
+            }
+            Msg::ConfirmClose => {
+                if let Some(idx) = self.closing.take() {
+                    self.save_handler.action(Action::CloseDocument(idx));
+                }
+                true
+            }
+            Msg::CancelClose => {
+                self.closing = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let SaveHandler { ref save_game, ref documents, ref clipboard, .. } = self.save_handler;
+        if documents.is_empty() {
+            return Html::default();
+        }
+
+        let link = ctx.link();
+        let tabs = documents.iter().enumerate().map(|(idx, document)| {
+            let is_active = save_game
+                .as_ref()
+                .map(|save_game| Rc::ptr_eq(save_game, &document.save_game))
+                .unwrap_or_default();
+            let file_name = document
+                .save_game
+                .file_path()
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let title = if document.is_dirty { format!("{}*", file_name) } else { file_name };
+
+            html! {
+                <div class={classes![
+                    "flex",
+                    "items-center",
+                    "gap-1",
+                    "rounded-b-none",
+                    "rounded-t-[0.2rem]",
+                    "select-none",
+                    "leading-[19px]",
+                    "px-1",
+                    "bg-theme-tab",
+                    "hover:!bg-theme-hover",
+                    is_active.then(|| "!bg-theme-active"),
+                    document.save_game.theme(),
+                ]}
+                    title={document.save_game.file_path().display().to_string()}
+                >
+                    <a class="cursor-pointer" onclick={link.callback(move |_| Msg::Select(idx))}>
+                        { title }
+                    </a>
+                    <a class="cursor-pointer hover:text-white" onclick={link.callback(move |_| Msg::Close(idx))}>
+                        {"×"}
+                    </a>
+                </div>
+            }
+        });
+
+        let closing = self.closing.and_then(|idx| documents.get(idx)).map(|document| {
+            let message = tr_format(
+                "`{}` has unsaved changes, close it anyway?",
+                &[&document.save_game.file_path().display()],
+            );
+            html! {
+                <div class="flex items-center gap-2 px-1">
+                    <span>{ format_code(message) }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::ConfirmClose)}>
+                        { tr("Close") }
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::CancelClose)}>
+                        { tr("Cancel") }
+                    </button>
+                </div>
+            }
+        });
+
+        let clipboard = clipboard.as_ref().map(|clipboard| {
+            html! {
+                <span class="ml-auto px-1">
+                    { format_code(tr_format("Clipboard: `{}`", &[&clipboard.label])) }
+                </span>
+            }
+        });
+
+        html! {
+            <div class="flex flex-col">
+                <div class="flex flex-wrap items-center gap-1 border-b border-theme-active px-1 pt-0.5">
+                    { for tabs }
+                    { for clipboard }
+                </div>
+                { for closing }
+            </div>
+        }
+    }
+}
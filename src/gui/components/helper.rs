@@ -2,7 +2,7 @@ use gloo::utils;
 use web_sys::HtmlElement;
 use yew::prelude::*;
 
-use crate::gui::format_code;
+use crate::gui::{format_code, i18n::tr};
 
 pub enum Msg {
     Hover,
@@ -61,7 +61,8 @@ impl Component for Helper {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let text = ctx.props().text.split_terminator('\n').map(|text| {
+        let text = tr(ctx.props().text);
+        let text = text.split_terminator('\n').map(|text| {
             html! { <p>{ format_code(text) }</p> }
         });
         html! {
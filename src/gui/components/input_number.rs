@@ -1,7 +1,10 @@
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use crate::{gui::components::Helper, save_data::RcCell};
+use crate::{
+    gui::{components::Helper, i18n::tr},
+    save_data::RcCell,
+};
 
 use super::CallbackType;
 
@@ -112,7 +115,7 @@ impl Component for InputNumber {
                     {value}
                     onchange={ctx.link().callback(Msg::Change)}
                 />
-                { &ctx.props().label }
+                { tr(&ctx.props().label) }
                 { for helper }
             </label>
         }
@@ -2,7 +2,10 @@ use std::cell::{Ref, RefMut};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use crate::{gui::components::Helper, save_data::RcRef};
+use crate::{
+    gui::{components::Helper, i18n::tr},
+    save_data::RcRef,
+};
 
 use super::CallbackType;
 
@@ -64,7 +67,7 @@ impl Component for InputText {
         html! {
             <label class="flex-auto flex items-center gap-1">
                 <input type="text" class="input w-2/3" placeholder="<empty>" {value} {oninput} />
-                { &ctx.props().label }
+                { tr(&ctx.props().label) }
                 { for helper }
             </label>
         }
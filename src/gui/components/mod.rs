@@ -1,18 +1,20 @@
 mod auto_update;
 mod check_box;
 mod color_picker;
+mod document_bar;
 mod helper;
 mod input_number;
 mod input_text;
 mod nav_bar;
+mod navigation;
 pub mod raw_ui;
 mod select;
 mod tab_bar;
 mod table;
 
 pub use self::{
-    auto_update::*, check_box::*, color_picker::*, helper::*, input_number::*, input_text::*,
-    nav_bar::*, select::*, tab_bar::*, table::*,
+    auto_update::*, check_box::*, color_picker::*, document_bar::*, helper::*, input_number::*,
+    input_text::*, nav_bar::*, navigation::*, select::*, tab_bar::*, table::*,
 };
 
 pub enum CallbackType {
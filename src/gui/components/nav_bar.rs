@@ -1,9 +1,14 @@
+use std::path::PathBuf;
+
 use wasm_bindgen_futures as futures;
 use web_sys::HtmlElement;
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::components::{Tab, TabBar},
+    gui::{
+        components::{Tab, TabBar},
+        i18n::tr,
+    },
     services::{
         rpc,
         save_handler::{Action, SaveHandler},
@@ -19,6 +24,14 @@ pub enum Msg {
     OpenSave,
     SaveSave,
     ReloadSave,
+    OpenLibrary,
+    OpenBackups,
+    OpenBatch,
+    OpenSettings,
+    RecentOpen,
+    RecentClose,
+    RecentBlur,
+    OpenRecent(PathBuf),
     MenuOpen,
     MenuClose,
     MenuBlur,
@@ -28,12 +41,18 @@ pub enum Msg {
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
+    pub onopenlibrary: Callback<()>,
+    pub onopenbackups: Callback<()>,
+    pub onopenbatch: Callback<()>,
+    pub onopensettings: Callback<()>,
     pub children: Children,
 }
 
 pub struct NavBar {
     _db_handle: ContextHandle<SaveHandler>,
     save_handler: SaveHandler,
+    recent_ref: NodeRef,
+    recent_opened: bool,
     about_ref: NodeRef,
     about_opened: bool,
     licenses_opened: bool,
@@ -52,13 +71,15 @@ impl Component for NavBar {
         NavBar {
             _db_handle,
             save_handler,
+            recent_ref: Default::default(),
+            recent_opened: false,
             about_ref: Default::default(),
             about_opened: false,
             licenses_opened: false,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::SaveLoaded(save_handler) => {
                 self.save_handler = save_handler;
@@ -77,6 +98,41 @@ impl Component for NavBar {
                 self.save_handler.action(Action::ReloadSave);
                 false
             }
+            Msg::OpenLibrary => {
+                ctx.props().onopenlibrary.emit(());
+                false
+            }
+            Msg::OpenBackups => {
+                ctx.props().onopenbackups.emit(());
+                false
+            }
+            Msg::OpenBatch => {
+                ctx.props().onopenbatch.emit(());
+                false
+            }
+            Msg::OpenSettings => {
+                ctx.props().onopensettings.emit(());
+                false
+            }
+            Msg::RecentOpen => {
+                self.recent_opened = true;
+                true
+            }
+            Msg::RecentClose => {
+                self.recent_opened = false;
+                true
+            }
+            Msg::RecentBlur => {
+                if let Some(recent) = self.recent_ref.cast::<HtmlElement>() {
+                    let _ = recent.blur();
+                }
+                false
+            }
+            Msg::OpenRecent(path) => {
+                self.save_handler.action(Action::OpenSaveAt(path));
+                ctx.link().send_message(Msg::RecentBlur);
+                false
+            }
             // Menus
             Msg::MenuOpen => {
                 self.about_opened = true;
@@ -110,11 +166,15 @@ impl Component for NavBar {
         let loaded_buttons = self.save_handler.save_game.is_some().then(|| {
             html! { <>
                 <button class="button" onclick={ctx.link().callback(|_| Msg::SaveSave)}>
-                    {"Save"}
+                    { tr("Save") }
                 </button>
                 <span>{"-"}</span>
                 <button class="button" onclick={ctx.link().callback(|_| Msg::ReloadSave)}>
-                    {"Reload"}
+                    { tr("Reload") }
+                </button>
+                <span>{"-"}</span>
+                <button class="button" onclick={ctx.link().callback(|_| Msg::OpenBackups)}>
+                    { tr("Backups") }
                 </button>
             </> }
         });
@@ -123,9 +183,22 @@ impl Component for NavBar {
             <nav class="bg-menu-bar select-none flex">
                 <div class="flex items-center gap-2 px-1">
                     <button class="button" onclick={ctx.link().callback(|_| Msg::OpenSave)}>
-                        {"Open"}
+                        { tr("Open") }
+                    </button>
+                    { self.view_recent_menu(ctx) }
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::OpenLibrary)}>
+                        { tr("Library") }
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::OpenBatch)}>
+                        { tr("Batch") }
                     </button>
                     { for loaded_buttons }
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::OpenSettings)}>
+                        { tr("Settings") }
+                    </button>
                     { self.view_about_menu(ctx) }
                 </div>
                 { ctx.props().children.clone() }
@@ -135,6 +208,75 @@ impl Component for NavBar {
 }
 
 impl NavBar {
+    fn view_recent_menu(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let onclick = if !self.recent_opened {
+            link.callback(|_| Msg::RecentOpen)
+        } else {
+            link.callback(|_| Msg::RecentBlur)
+        };
+
+        let recent_files = self.save_handler.settings.recent_files.iter().map(|recent_file| {
+            let path = recent_file.path.clone();
+            let file_name = path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            html! {
+                <a class={classes![
+                        "px-1",
+                        "hover:bg-theme-hover",
+                        "active:bg-theme-active",
+                        "whitespace-nowrap",
+                        "cursor-pointer",
+                    ]}
+                    title={path.display().to_string()}
+                    onclick={link.callback(move |_| Msg::OpenRecent(path.clone()))}
+                >
+                    { format!("{} - {}", recent_file.game, file_name) }
+                </a>
+            }
+        });
+        let is_empty = self.save_handler.settings.recent_files.is_empty();
+        let empty = is_empty.then(|| html! { <span class="px-1">{ tr("No recent file") }</span> });
+
+        html! {
+            <div class="relative" tabindex="0"
+                onblur={self.recent_opened.then(|| link.callback(|_| Msg::RecentClose))}
+                ref={self.recent_ref.clone()}
+            >
+                <a
+                    class={classes![
+                        "hover:bg-theme-hover",
+                        "px-2",
+                        "py-px",
+                        "cursor-pointer",
+                        self.recent_opened.then(|| "bg-theme-hover" )
+                    ]}
+                    {onclick}
+                >
+                    { tr("Recent") }
+                </a>
+                <div class={classes![
+                    "absolute",
+                    "left-0",
+                    "flex",
+                    "flex-col",
+                    "gap-px",
+                    "bg-popup/90",
+                    "border",
+                    "border-default-border",
+                    "p-1",
+                    "z-40",
+                    (!self.recent_opened).then(|| "hidden" )
+                ]}>
+                    { for recent_files }
+                    { for empty }
+                </div>
+            </div>
+        }
+    }
+
     fn view_about_menu(&self, ctx: &Context<Self>) -> Html {
         let link = ctx.link();
         let onclick = if !self.about_opened {
@@ -160,7 +302,7 @@ impl NavBar {
                     ]}
                     {onclick}
                 >
-                    {"About"}
+                    { tr("About") }
                 </a>
                 <div class={classes![
                     "absolute",
@@ -219,7 +361,7 @@ impl NavBar {
                             title={DONATION_LINK}
                             onclick={link.callback(|_| Msg::OpenLink(DONATION_LINK))}
                         >
-                            {"Donate"}
+                            { tr("Donate") }
                         </a>
                         <hr class="border-default-border" />
                         <div class="relative flex">
@@ -234,7 +376,7 @@ impl NavBar {
                                 ]}
                                 onmouseover={link.callback(|_| Msg::LicensesHover)}
                             >
-                                {"License"}
+                                { tr("License") }
                             </a>
                         </div>
                 </div>
@@ -0,0 +1,36 @@
+use gloo::{events::EventListener, timers::future::TimeoutFuture, utils};
+use wasm_bindgen::JsCast;
+use web_sys::{PopStateEvent, PopStateEventInit};
+
+const NAVIGATE_EVENT: &str = "navigate";
+
+/// Opens every tab and table whose title is in `path`.
+/// One level at a time, so the components created by a level receive the next one.
+pub async fn navigate(path: &[String]) {
+    for depth in 1..=path.len() {
+        if let Ok(state) = serde_wasm_bindgen::to_value(&path[..depth]) {
+            let init = PopStateEventInit::new();
+            init.set_state(&state);
+            if let Ok(event) = PopStateEvent::new_with_event_init_dict(NAVIGATE_EVENT, &init) {
+                let _ = utils::window().dispatch_event(&event);
+            }
+        }
+        TimeoutFuture::new(0).await;
+    }
+}
+
+/// Whether `title` is the level being opened, the last of `path` as it is sent one level at a time.
+/// A title deeper or higher in the path is another component with the same title.
+pub fn is_navigation_target(path: &[String], title: &str) -> bool {
+    path.last().map(|last| last == title).unwrap_or_default()
+}
+
+pub fn navigation_listener(callback: impl Fn(Vec<String>) + 'static) -> EventListener {
+    EventListener::new(&utils::window(), NAVIGATE_EVENT, move |event| {
+        if let Some(event) = event.dyn_ref::<PopStateEvent>() {
+            if let Ok(path) = serde_wasm_bindgen::from_value(event.state()) {
+                callback(path);
+            }
+        }
+    })
+}
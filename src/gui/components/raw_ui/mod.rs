@@ -2,10 +2,11 @@ mod raw_ui_enum;
 mod raw_ui_guid;
 mod raw_ui_index_map;
 mod raw_ui_option;
+mod raw_ui_string_ref;
 mod raw_ui_struct;
 mod raw_ui_vec;
 
 pub use self::{
-    raw_ui_enum::*, raw_ui_guid::*, raw_ui_index_map::*, raw_ui_option::*, raw_ui_struct::*,
-    raw_ui_vec::*,
+    raw_ui_enum::*, raw_ui_guid::*, raw_ui_index_map::*, raw_ui_option::*, raw_ui_string_ref::*,
+    raw_ui_struct::*, raw_ui_vec::*,
 };
@@ -1,12 +1,14 @@
 use std::{any::Any, marker::PhantomData};
 
+use gloo::events::EventListener;
 use indexmap::IndexMap;
 use yew::prelude::*;
 
 use crate::{
     gui::{
         components::{
-            raw_ui::RawUiStruct, CallbackType, InputNumber, InputText, NumberType, Table,
+            is_navigation_target, navigation_listener, raw_ui::RawUiStruct, CallbackType,
+            InputNumber, InputText, NumberType, Table,
         },
         raw_ui::{RawUi, RawUiChildren},
     },
@@ -40,6 +42,7 @@ where
 
 pub enum Msg {
     Toggle,
+    Navigate(Vec<String>),
     Add,
     Remove(usize),
     EditKey(usize, CallbackType),
@@ -59,6 +62,7 @@ where
     T: RawUi + Default,
 {
     _marker: PhantomData<T>,
+    _navigation_listener: EventListener,
     opened: bool,
     new_item_idx: usize,
 }
@@ -70,8 +74,13 @@ where
     type Message = Msg;
     type Properties = Props<T>;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        RawUiIndexMap { _marker: PhantomData, opened: false, new_item_idx: 0 }
+    fn create(ctx: &Context<Self>) -> Self {
+        let _navigation_listener = {
+            let link = ctx.link().clone();
+            navigation_listener(move |path| link.send_message(Msg::Navigate(path)))
+        };
+
+        RawUiIndexMap { _marker: PhantomData, _navigation_listener, opened: false, new_item_idx: 0 }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -87,6 +96,13 @@ where
                 }
                 true
             }
+            Msg::Navigate(path) => {
+                if !self.opened && is_navigation_target(&path, &ctx.props().label) {
+                    self.update(ctx, Msg::Toggle)
+                } else {
+                    false
+                }
+            }
             Msg::Add => {
                 match ctx.props().index_map {
                     IndexMapKeyType::I32(ref index_map) => {
@@ -0,0 +1,147 @@
+use std::rc::Rc;
+
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::i18n::tr,
+    save_data::{shared::tlk::TlkFile, RcRef, StringRef},
+    services::database::Databases,
+};
+
+const MAX_RESULTS: usize = 20;
+
+pub enum Msg {
+    Databases(Databases),
+    Change(Event),
+    ToggleSearch,
+    Search(InputEvent),
+    Pick(StringRef),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub label: String,
+    pub string_ref: RcRef<StringRef>,
+}
+
+/// A localized id with its string from the TLK file of the game, and a search to pick another one
+pub struct RawUiStringRef {
+    _db_handle: ContextHandle<Databases>,
+    tlk: Option<Rc<TlkFile>>,
+    search: Option<String>,
+}
+
+impl Component for RawUiStringRef {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (dbs, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::Databases))
+            .expect("no database provider");
+
+        RawUiStringRef { _db_handle, tlk: dbs.get_tlk(), search: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Databases(dbs) => {
+                let tlk = dbs.get_tlk();
+                let changed = match (&tlk, &self.tlk) {
+                    (Some(tlk), Some(current)) => !Rc::ptr_eq(tlk, current),
+                    (None, None) => false,
+                    _ => true,
+                };
+                self.tlk = tlk;
+                changed
+            }
+            Msg::Change(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    let value = input.value_as_number();
+                    if !value.is_nan() {
+                        *ctx.props().string_ref.borrow_mut() = StringRef(value as i32);
+                    }
+                }
+                true
+            }
+            Msg::ToggleSearch => {
+                self.search = match self.search {
+                    Some(_) => None,
+                    None => Some(String::new()),
+                };
+                true
+            }
+            Msg::Search(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.search = Some(input.value());
+                }
+                true
+            }
+            Msg::Pick(string_ref) => {
+                *ctx.props().string_ref.borrow_mut() = string_ref;
+                self.search = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let string_ref = *ctx.props().string_ref.borrow();
+
+        let resolved = self.tlk.as_ref().map(|tlk| {
+            let string = tlk.get(string_ref).unwrap_or("<unknown>");
+            html! {
+                <>
+                    <span class="truncate text-white/50" title={string.to_owned()}>{ string }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::ToggleSearch)}>
+                        { tr("Pick") }
+                    </button>
+                </>
+            }
+        });
+
+        let search = self.tlk.as_ref().zip(self.search.as_ref()).map(|(tlk, search)| {
+            let results = (search.len() >= 3)
+                .then(|| tlk.search(search).take(MAX_RESULTS))
+                .into_iter()
+                .flatten()
+                .map(|(string_ref, string)| {
+                    html! {
+                        <a class="cursor-pointer truncate px-1 hover:bg-theme-hover"
+                            onclick={link.callback(move |_| Msg::Pick(string_ref))}
+                        >
+                            { format!("{} - {}", string_ref, string) }
+                        </a>
+                    }
+                });
+            html! {
+                <div class="flex flex-col gap-1 pl-[114px]">
+                    <input type="text" class="input w-2/3" placeholder={tr("Search a string")}
+                        value={search.clone()}
+                        oninput={link.callback(Msg::Search)}
+                    />
+                    <div class="flex flex-col w-2/3 max-h-60 overflow-y-auto">
+                        { for results }
+                    </div>
+                </div>
+            }
+        });
+
+        html! {
+            <div class="flex flex-col gap-1">
+                <label class="flex items-center gap-1">
+                    <input type="number" class="input w-[110px]" placeholder="<integer>"
+                        value={string_ref.to_string()}
+                        onchange={link.callback(Msg::Change)}
+                    />
+                    { tr(&ctx.props().label) }
+                    { for resolved }
+                </label>
+                { for search }
+            </div>
+        }
+    }
+}
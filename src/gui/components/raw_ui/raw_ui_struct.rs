@@ -1,9 +1,11 @@
+use gloo::events::EventListener;
 use yew::prelude::*;
 
-use crate::gui::components::Table;
+use crate::gui::components::{is_navigation_target, navigation_listener, Table};
 
 pub enum Msg {
     Toggle,
+    Navigate(Vec<String>),
 }
 
 #[derive(Properties, PartialEq)]
@@ -15,6 +17,7 @@ pub struct Props {
 }
 
 pub struct RawUiStruct {
+    _navigation_listener: EventListener,
     opened: bool,
 }
 
@@ -23,15 +26,28 @@ impl Component for RawUiStruct {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        RawUiStruct { opened: ctx.props().opened }
+        let _navigation_listener = {
+            let link = ctx.link().clone();
+            navigation_listener(move |path| link.send_message(Msg::Navigate(path)))
+        };
+
+        RawUiStruct { _navigation_listener, opened: ctx.props().opened }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Toggle => {
                 self.opened = !self.opened;
                 true
             }
+            Msg::Navigate(path) => {
+                if !self.opened && is_navigation_target(&path, &ctx.props().label) {
+                    self.opened = true;
+                    true
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -5,15 +5,20 @@ use std::{
     marker::PhantomData,
 };
 
+use gloo::events::EventListener;
 use yew::prelude::*;
 
 use crate::{
-    gui::{components::Table, raw_ui::RawUi},
+    gui::{
+        components::{is_navigation_target, navigation_listener, Table},
+        raw_ui::RawUi,
+    },
     save_data::RcRef,
 };
 
 pub enum Msg {
     Toggle,
+    Navigate(Vec<String>),
     Add,
     Remove(usize),
 }
@@ -47,6 +52,7 @@ where
     T: RawUi + Default + Display,
 {
     _marker: PhantomData<T>,
+    _navigation_listener: EventListener,
     opened: bool,
     new_item_idx: usize,
 }
@@ -58,8 +64,13 @@ where
     type Message = Msg;
     type Properties = Props<T>;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        RawUiVec { _marker: PhantomData, opened: false, new_item_idx: 0 }
+    fn create(ctx: &Context<Self>) -> Self {
+        let _navigation_listener = {
+            let link = ctx.link().clone();
+            navigation_listener(move |path| link.send_message(Msg::Navigate(path)))
+        };
+
+        RawUiVec { _marker: PhantomData, _navigation_listener, opened: false, new_item_idx: 0 }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -72,6 +83,13 @@ where
                 }
                 true
             }
+            Msg::Navigate(path) => {
+                if !self.opened && is_navigation_target(&path, &ctx.props().label) {
+                    self.update(ctx, Msg::Toggle)
+                } else {
+                    false
+                }
+            }
             Msg::Add => {
                 // Open added item
                 self.new_item_idx = ctx.props().vec().len();
@@ -2,6 +2,8 @@ use gloo::utils;
 use web_sys::HtmlElement;
 use yew::prelude::*;
 
+use crate::gui::i18n::tr;
+
 pub enum Msg {
     Open,
     Close,
@@ -110,7 +112,7 @@ impl Component for Select {
                         ]}
                         onclick={ctx.link().callback(move |_| Msg::Select(idx))}
                     >
-                        { option }
+                        { tr(option) }
                     </a>
                 }
             });
@@ -147,7 +149,7 @@ impl Component for Select {
                     ]}
                     {onclick}
                 >
-                    { ctx.props().options[self.current_idx] }
+                    { tr(ctx.props().options[self.current_idx]) }
                 </a>
                 <div
                     class={classes![
@@ -3,13 +3,18 @@ use wasm_bindgen::JsCast;
 use web_sys::PopStateEvent;
 use yew::{html::Scope, prelude::*};
 
-use crate::gui::Theme;
+use crate::gui::{
+    components::{is_navigation_target, navigation_listener},
+    i18n::tr,
+    Theme,
+};
 
 const MAIN_BUTTON: i16 = 0;
 
 pub enum Msg {
     TabClicked(MouseEvent, String),
     MainTabChanged(String),
+    Navigate(Vec<String>),
 }
 
 #[derive(Properties, PartialEq)]
@@ -21,6 +26,7 @@ pub struct Props {
 
 pub struct TabBar {
     main_tab_listener: Option<EventListener>,
+    _navigation_listener: EventListener,
     current_tab: String,
 }
 
@@ -35,9 +41,14 @@ impl Component for TabBar {
             Self::event_listener(link)
         });
 
+        let _navigation_listener = {
+            let link = ctx.link().clone();
+            navigation_listener(move |path| link.send_message(Msg::Navigate(path)))
+        };
+
         // TODO: Tab history
 
-        TabBar { current_tab, main_tab_listener }
+        TabBar { current_tab, main_tab_listener, _navigation_listener }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -59,6 +70,22 @@ impl Component for TabBar {
                 }
                 true
             }
+            Msg::Navigate(path) => {
+                // The path goes from the main tab to the target, one level at a time
+                let children = &ctx.props().children;
+                let tab = children
+                    .iter()
+                    .find(|child| is_navigation_target(&path, &child.props.title))
+                    .map(|child| child.props.title.clone());
+
+                match tab {
+                    Some(tab) if tab != self.current_tab => {
+                        self.current_tab = tab;
+                        true
+                    }
+                    _ => false,
+                }
+            }
         }
     }
 
@@ -103,7 +130,7 @@ impl Component for TabBar {
                     ]}
                     {onmousedown}
                 >
-                    { title }
+                    { tr(&title) }
                 </a>
             }
         });
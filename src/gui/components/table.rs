@@ -1,10 +1,14 @@
-use gloo::timers::future::TimeoutFuture;
+use gloo::{events::EventListener, timers::future::TimeoutFuture};
 use yew::prelude::*;
 
-use crate::gui::components::Helper;
+use crate::gui::{
+    components::{is_navigation_target, navigation_listener, Helper},
+    i18n::tr,
+};
 
 pub enum Msg {
     Toggle,
+    Navigate(Vec<String>),
 }
 
 #[derive(Properties, PartialEq)]
@@ -17,6 +21,7 @@ pub struct Props {
 }
 
 pub struct Table {
+    _navigation_listener: EventListener,
     opened: bool,
 }
 
@@ -25,15 +30,34 @@ impl Component for Table {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        Table { opened: ctx.props().opened }
+        let _navigation_listener = {
+            let link = ctx.link().clone();
+            navigation_listener(move |path| link.send_message(Msg::Navigate(path)))
+        };
+
+        Table { _navigation_listener, opened: ctx.props().opened }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Toggle => {
                 self.opened = !self.opened;
                 true
             }
+            Msg::Navigate(path) => {
+                let is_target = ctx
+                    .props()
+                    .title
+                    .as_ref()
+                    .map(|title| is_navigation_target(&path, title))
+                    .unwrap_or_default();
+                if !self.opened && is_target {
+                    self.opened = true;
+                    true
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -72,7 +96,7 @@ impl Component for Table {
                         ]}
                         onclick={ctx.link().callback(|_| Msg::Toggle)}
                     >
-                        { title }
+                        { tr(title) }
                         { for helper }
                     </button>
                 </div>
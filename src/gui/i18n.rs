@@ -0,0 +1,99 @@
+use std::{cell::RefCell, collections::HashMap, fmt::Display};
+
+use crate::services::rpc::Language;
+
+// The UI only runs on the main thread, so the catalog doesn't need to go through the contexts
+thread_local! {
+    static CATALOG: RefCell<Catalog> = RefCell::new(Catalog::default());
+}
+
+/// The translations of a language, keyed by their English text
+#[derive(Default)]
+struct Catalog {
+    language: Language,
+    messages: HashMap<String, String>,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::French];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+        }
+    }
+
+    /// English is the source language, it has no catalog
+    pub fn catalog_path(self) -> Option<String> {
+        (self != Language::English).then(|| format!("databases/i18n/{}.ron", self.code()))
+    }
+
+    /// `databases/me2_plot_db.ron` => `databases/me2_plot_db.fr.ron`
+    pub fn localized_path(self, path: &str) -> Option<String> {
+        let stem = path.strip_suffix(".ron")?;
+        (self != Language::English).then(|| format!("{}.{}.ron", stem, self.code()))
+    }
+}
+
+pub fn language() -> Language {
+    CATALOG.with(|catalog| catalog.borrow().language)
+}
+
+pub fn set_language(language: Language, messages: HashMap<String, String>) {
+    CATALOG.with(|catalog| *catalog.borrow_mut() = Catalog { language, messages });
+}
+
+/// Translates `text` into the current language, untranslated texts stay in English
+pub fn tr(text: &str) -> String {
+    CATALOG.with(|catalog| {
+        catalog.borrow().messages.get(text).cloned().unwrap_or_else(|| text.to_owned())
+    })
+}
+
+/// Translates `template` then replaces each `{}` with the next argument
+pub fn tr_format(template: &str, args: &[&dyn Display]) -> String {
+    let translated = tr(template);
+    let mut args = args.iter();
+    let mut output = String::with_capacity(translated.len());
+    let mut parts = translated.split("{}");
+    if let Some(first) = parts.next() {
+        output.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            output.push_str(&arg.to_string());
+        }
+        output.push_str(part);
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn translate() -> Result<()> {
+        let input = fs::read_to_string("databases/i18n/fr.ron")?;
+        let messages: HashMap<String, String> = ron::from_str(&input)?;
+        set_language(Language::French, messages);
+
+        assert_eq!(language(), Language::French);
+        assert_eq!(tr("Open"), "Ouvrir");
+        assert_eq!(tr("Not in the catalog"), "Not in the catalog");
+        assert_eq!(tr_format("{} recent file(s)", &[&3]), "3 fichier(s) récent(s)");
+
+        assert_eq!(
+            Language::French.localized_path("databases/me2_plot_db.ron").as_deref(),
+            Some("databases/me2_plot_db.fr.ron")
+        );
+        assert_eq!(Language::English.localized_path("databases/me2_plot_db.ron"), None);
+        assert_eq!(Language::English.catalog_path(), None);
+        Ok(())
+    }
+}
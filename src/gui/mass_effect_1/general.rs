@@ -1,26 +1,33 @@
-use std::cell::Ref;
+use std::{cell::Ref, rc::Rc};
 
-use yew::prelude::*;
+use yew::{context::ContextHandle, prelude::*};
 
 use crate::save_data::{
     mass_effect_1::{
         data::{Data, Property as DataProperty},
         player::Player,
+        player_class_db::{Me1PlayerClass, Me1PlayerClassDb},
     },
     shared::plot::PlotTable,
     List, RcCell,
 };
 use crate::{
     gui::{
-        components::{Select, Table},
+        components::{Helper, Select, Table},
+        format_code,
         mass_effect_1::property::Property,
+        mass_effect_1_le::specializations,
         raw_ui::RawUi,
     },
-    save_data::{mass_effect_1::data::StructType, RcRef},
+    save_data::{mass_effect_1::data::StructType, mass_effect_1_le::player::Me1LeClass, RcRef},
+    services::database::Databases,
 };
 
 pub enum Msg {
+    DatabaseLoaded(Databases),
     Difficulty(usize),
+    PlayerClass(usize),
+    PlayerSpecialization(usize),
 }
 
 #[derive(Properties, PartialEq)]
@@ -39,18 +46,53 @@ impl Props {
     }
 }
 
-pub struct Me1General;
+pub struct Me1General {
+    _db_handle: ContextHandle<Databases>,
+    player_class_db: Option<Rc<Me1PlayerClassDb>>,
+    status: Option<String>,
+}
 
 impl Component for Me1General {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Me1General
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1General { _db_handle, player_class_db: databases.get_me1_player_classes(), status: None }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.player_class_db = dbs.get_me1_player_classes();
+                true
+            }
+            Msg::PlayerClass(class_idx) => {
+                if let Some(ref player_class_db) = self.player_class_db {
+                    if let Some(new_class) = player_class_db.class(&Me1LeClass::from(class_idx)) {
+                        let mut player = ctx.props().player.borrow_mut();
+                        self.status =
+                            player.change_class(new_class).err().map(|err| err.to_string());
+                    }
+                }
+                true
+            }
+            Msg::PlayerSpecialization(spec_idx) => {
+                let mut player = ctx.props().player.borrow_mut();
+                let player_class = self.player_class(&player);
+                if let Some(player_class) = player_class {
+                    let (_, spec_ids) = specializations(&player_class.player_class);
+                    self.status = player
+                        .set_specialization(player_class, spec_ids, spec_idx)
+                        .err()
+                        .map(|err| err.to_string());
+                }
+                true
+            }
             Msg::Difficulty(new_difficulty_idx) => {
                 let player = ctx.props().player();
 
@@ -100,12 +142,19 @@ impl Component for Me1General {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        html! { for Self::try_view(ctx) }
+        html! { for self.try_view(ctx) }
     }
 }
 
 impl Me1General {
-    fn try_view(ctx: &Context<Self>) -> Option<Html> {
+    /// The class of Shepard, from its `m_ClassBase`
+    fn player_class(&self, player: &Player) -> Option<&Me1PlayerClass> {
+        let class_base = player.class_base()?;
+        let player_class_db = self.player_class_db.as_ref()?;
+        player_class_db.iter().find(|player_class| player_class.class_base == class_base)
+    }
+
+    fn try_view(&self, ctx: &Context<Self>) -> Option<Html> {
         let player = ctx.props().player();
 
         let current_game = player.objects.iter().enumerate().find_map(|(i, object)| {
@@ -132,7 +181,7 @@ impl Me1General {
             <div class="flex divide-solid divide-x divide-default-border">
                 <div class="flex-1 pr-1 flex flex-col gap-1">
                     { Self::role_play(ctx, m_player) }
-                    { Self::gameplay(ctx, m_player, m_squad) }
+                    { self.gameplay(ctx, &player, m_player, m_squad) }
                     { Self::morality(ctx) }
                 </div>
                 <div class="flex-1 pl-1 flex flex-col gap-1">
@@ -163,9 +212,53 @@ impl Me1General {
         }
     }
 
-    fn gameplay(ctx: &Context<Self>, m_player: &Data, m_squad: &Data) -> Html {
-        let class = Self::find_property(ctx, &m_player.properties, "m_ClassBase")
-            .map(|p| Self::view_property(ctx, p, "Class"));
+    fn gameplay(
+        &self, ctx: &Context<Self>, player: &Player, m_player: &Data, m_squad: &Data,
+    ) -> Html {
+        let class = match self.player_class(player) {
+            Some(player_class) => {
+                let current_spec_id = player.specialization_bonus_id().unwrap_or(-1);
+                let (spec_variants, spec_ids) = specializations(&player_class.player_class);
+                let current_spec_idx = spec_ids
+                    .iter()
+                    .position(|&spec_id| spec_id == current_spec_id)
+                    .unwrap_or_default();
+                let status =
+                    self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+
+                Some(html! {
+                    <>
+                        <div class="flex items-center gap-1 cursor-default">
+                            <Select
+                                options={Me1LeClass::variants()}
+                                current_idx={player_class.player_class.clone() as usize}
+                                onselect={ctx.link().callback(Msg::PlayerClass)}
+                            />
+                            { "Class" }
+                            <Helper text=
+                                "If you change your class: \n\
+                                • Your talent points will be reset\n\
+                                • Your specialization will be set to `None`\n\
+                                • Your armor, omni-tool and bio-amp will be moved to the inventory\n\
+                                • The default gear of the new class will be equipped"
+                            />
+                        </div>
+                        <div class="flex items-center gap-1 cursor-default">
+                            <Select
+                                options={spec_variants}
+                                current_idx={current_spec_idx}
+                                onselect={ctx.link().callback(Msg::PlayerSpecialization)}
+                            />
+                            { "Specialization" }
+                        </div>
+                        { for status }
+                    </>
+                })
+            }
+            // The database is loading or the class is unknown
+            None => Self::find_property(ctx, &m_player.properties, "m_ClassBase")
+                .map(|p| Self::view_property(ctx, p, "Class")),
+        };
         let level = Self::find_property(ctx, &m_player.properties, "m_XPLevel")
             .map(|p| Self::view_property(ctx, p, "Level"));
         let curent_xp = Self::find_property(ctx, &m_squad.properties, "m_nSquadExperience")
@@ -263,32 +356,12 @@ impl Me1General {
     fn find_property<'a>(
         ctx: &Context<Self>, properties: &'a List<RcRef<DataProperty>>, property_name: &str,
     ) -> Option<&'a RcRef<DataProperty>> {
-        let player = ctx.props().player();
-        properties.iter().find_map(|property| match *property.borrow() {
-            DataProperty::Array { name_id, .. }
-            | DataProperty::Bool { name_id, .. }
-            | DataProperty::Byte { name_id, .. }
-            | DataProperty::Float { name_id, .. }
-            | DataProperty::Int { name_id, .. }
-            | DataProperty::Name { name_id, .. }
-            | DataProperty::Object { name_id, .. }
-            | DataProperty::Str { name_id, .. }
-            | DataProperty::StringRef { name_id, .. }
-            | DataProperty::Struct { name_id, .. }
-            | DataProperty::None { name_id, .. } => {
-                (player.get_name(name_id) == property_name).then(|| property)
-            }
-        })
+        ctx.props().player().find_property(properties, property_name)
     }
 
     fn find_object_id(
         ctx: &Context<Self>, properties: &List<RcRef<DataProperty>>, property_name: &str,
     ) -> Option<i32> {
-        Self::find_property(ctx, properties, property_name).and_then(|property| {
-            match *property.borrow() {
-                DataProperty::Object { object_id, .. } => Some(object_id),
-                _ => None,
-            }
-        })
+        ctx.props().player().find_object_id(properties, property_name)
     }
 }
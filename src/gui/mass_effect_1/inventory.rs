@@ -0,0 +1,241 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{Select, Table},
+        format_code,
+        i18n::tr,
+        mass_effect_1_le::{remove_link, ItemSelect},
+    },
+    save_data::{
+        mass_effect_1::{
+            inventory::{Character, Item, ItemArray},
+            player::Player,
+        },
+        mass_effect_1_le::{
+            item_db::{DbItem, Me1ItemDb},
+            player::ItemLevel,
+        },
+        RcRef,
+    },
+    services::database::Databases,
+};
+
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    ChangeItem(Item, DbItem),
+    ChangeItemLevel(Item, usize),
+    RemoveItem(ItemArray, usize),
+    AddItem(ItemArray),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+}
+
+/// The items of the `Data` tree, the ids in the arrays point to the item objects
+pub struct Me1Inventory {
+    _db_handle: ContextHandle<Databases>,
+    item_db: Option<Rc<Me1ItemDb>>,
+    status: Option<String>,
+}
+
+impl Component for Me1Inventory {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1Inventory { _db_handle, item_db: databases.get_me1_item_db(), status: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.item_db = dbs.get_me1_item_db();
+                true
+            }
+            Msg::ChangeItem(item, new_item) => {
+                item.set_db_item(new_item);
+                false
+            }
+            Msg::ChangeItemLevel(item, item_level) => {
+                item.item_level.set(item_level as u8);
+                false
+            }
+            Msg::RemoveItem(items, idx) => {
+                self.status = items.remove(idx).err().map(|err| err.to_string());
+                true
+            }
+            Msg::AddItem(items) => {
+                let mut player = ctx.props().player.borrow_mut();
+                let db_item = DbItem { item_id: 0, manufacturer_id: 0 };
+                self.status = player.add_item(&items, db_item).err().map(|err| err.to_string());
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.item_db.is_none() {
+            return html! {
+                <>
+                    <p>{ "Loading database..." }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            };
+        }
+
+        let player = ctx.props().player.borrow();
+        let squad = player.squad();
+        let squad = squad.iter().map(|character| self.character(ctx, &player, character));
+
+        let inventory = player.inventory().map(|inventory| {
+            let items = self.items(ctx, &player, "Inventory", &inventory.items);
+            let buyback_items = inventory
+                .buyback_items
+                .map(|buyback_items| self.items(ctx, &player, "Buyback items", &buyback_items));
+            html! {
+                <>
+                    { items }
+                    { for buyback_items }
+                </>
+            }
+        });
+
+        let status = self.status.as_ref().map(|status| {
+            html! {
+                <>
+                    <p>{ format_code(status) }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                { for status }
+                <div class="flex divide-solid divide-x divide-default-border">
+                    <div class="flex-1 pr-1 min-w-0">
+                        { for squad }
+                    </div>
+                    <div class="flex-1 flex flex-col gap-1 pl-1 min-w-0">
+                        { for inventory }
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl Me1Inventory {
+    fn item_selects(&self, ctx: &Context<Self>, item: Item) -> Html {
+        let link = ctx.link();
+        let current_item = item.db_item();
+        let current_level = item.item_level.get() as usize;
+        let onselect_item = {
+            let item = item.clone();
+            link.callback(move |new_item| Msg::ChangeItem(item.clone(), new_item))
+        };
+        let onselect_level = link.callback(move |idx| Msg::ChangeItemLevel(item.clone(), idx));
+
+        html! {
+            <>
+                <ItemSelect
+                    item_db={Rc::clone(self.item_db.as_ref().unwrap())}
+                    {current_item}
+                    onselect={onselect_item}
+                />
+                <Select
+                    options={ItemLevel::variants()}
+                    current_idx={current_level}
+                    onselect={onselect_level}
+                    sized=false
+                />
+            </>
+        }
+    }
+
+    fn slots(&self, ctx: &Context<Self>, player: &Player, slots: &ItemArray) -> Html {
+        let slots = slots.object_ids().into_iter().map(|object_id| {
+            let item = match player.item(object_id) {
+                Some(item) => self.item_selects(ctx, item),
+                None => html! { <span class="text-white/50">{ tr("Empty") }</span> },
+            };
+            html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    { item }
+                </div>
+            }
+        });
+        html! { for slots }
+    }
+
+    fn character(&self, ctx: &Context<Self>, player: &Player, character: &Character) -> Html {
+        let name = match character.tag.as_str() {
+            "Player" => "Shepard",
+            "hench_asari" => "Liara",
+            "hench_humanfemale" => "Ashley",
+            "hench_humanmale" => "Kaidan",
+            "hench_krogan" => "Wrex",
+            "hench_quarian" => "Tali",
+            "hench_turian" => "Garrus",
+            _ => "Jenkins",
+        };
+
+        html! {
+            <div class="flex flex-col gap-1 mb-1">
+                <div>
+                    <p>{ name }</p>
+                    <hr class="border-t border-default-border" />
+                </div>
+                <Table title="Equipement">
+                    { self.slots(ctx, player, &character.equipment) }
+                </Table>
+                <Table title="Quick slots">
+                    { self.slots(ctx, player, &character.quick_slots) }
+                </Table>
+            </div>
+        }
+    }
+
+    fn items(&self, ctx: &Context<Self>, player: &Player, title: &str, items: &ItemArray) -> Html {
+        let link = ctx.link();
+        let add = {
+            let items = items.clone();
+            link.callback(move |_| Msg::AddItem(items.clone()))
+        };
+
+        let rows = items.object_ids().into_iter().enumerate().filter_map(|(idx, object_id)| {
+            let item = player.item(object_id)?;
+            let remove = {
+                let items = items.clone();
+                link.callback(move |_| Msg::RemoveItem(items.clone(), idx))
+            };
+            Some(html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    { remove_link(remove) }
+                    { self.item_selects(ctx, item) }
+                </div>
+            })
+        });
+
+        html! {
+            <Table title={title.to_owned()}>
+                { for rows }
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={add}
+                >
+                    {"add"}
+                </button>
+            </Table>
+        }
+    }
+}
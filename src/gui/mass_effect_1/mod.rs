@@ -1,6 +1,11 @@
 mod general;
+mod inventory;
 mod plot;
+mod plot_presets;
 mod raw_data;
 mod raw_plot;
+mod world;
 
-pub use self::{general::*, plot::*, raw_data::*, raw_plot::*};
+pub use self::{
+    general::*, inventory::*, plot::*, plot_presets::*, raw_data::*, raw_plot::*, world::*,
+};
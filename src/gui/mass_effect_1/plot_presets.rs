@@ -0,0 +1,45 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::{
+    gui::{
+        shared::{FloatPlotType, IntPlotType, PlotPresets},
+        Theme,
+    },
+    save_data::{shared::plot::BitVec, RcRef},
+    services::database::Databases,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+    pub integers: IntPlotType,
+    pub floats: FloatPlotType,
+}
+
+#[function_component(Me1PlotPresets)]
+pub fn me1_plot_presets(props: &Props) -> Html {
+    let dbs = use_context::<Databases>().expect("no database provider");
+    match (dbs.clone().get_me1_plot_presets(), dbs.get_me1_raw_plot()) {
+        (Some(presets), Some(plot_db)) => {
+            let Props { booleans, integers, floats } = props;
+            html! {
+                <PlotPresets
+                    theme={Theme::MassEffect1}
+                    booleans={RcRef::clone(booleans)}
+                    integers={integers.clone()}
+                    floats={floats.clone()}
+                    presets={Rc::clone(&presets)}
+                    plot_db={Rc::clone(&plot_db)}
+                />
+            }
+        }
+        _ => html! {
+            <>
+                <p>{ "Loading database..." }</p>
+                <hr class="border-t border-default-border" />
+            </>
+        },
+    }
+}
@@ -1,3 +1,4 @@
+pub mod properties;
 pub mod property;
 
 use std::cell::Ref;
@@ -5,8 +6,11 @@ use std::cell::Ref;
 use yew::prelude::*;
 
 use crate::{
-    gui::{components::Table, mass_effect_1::raw_data::property::Property},
-    save_data::{mass_effect_1::player::Player, RcRef},
+    gui::{components::Table, mass_effect_1::raw_data::properties::Properties},
+    save_data::{
+        mass_effect_1::{data::PropertiesOwner, player::Player},
+        RcRef,
+    },
 };
 
 #[derive(Properties, PartialEq)]
@@ -41,21 +45,12 @@ impl Component for Me1RawData {
             })
             .unwrap_or_default();
 
-        let properties = &player.get_data(object_id).properties;
-        let len = properties.len();
-        let take = if len > 0 { len - 1 } else { 0 };
-        let properties = properties.iter().take(take).map(|property| {
-            html! {
-                <Property
-                    player={RcRef::clone(&ctx.props().player)}
-                    property={RcRef::clone(property)}
-                />
-            }
-        });
-
         html! {
             <Table>
-                { for properties }
+                <Properties
+                    player={RcRef::clone(&ctx.props().player)}
+                    owner={PropertiesOwner::Object(object_id)}
+                />
             </Table>
         }
     }
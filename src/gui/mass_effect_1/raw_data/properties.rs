@@ -0,0 +1,129 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::{
+        components::{raw_ui::RawUiStruct, InputText, Select},
+        format_code,
+        i18n::tr,
+        mass_effect_1::property::Property,
+        mass_effect_1_le::remove_link,
+    },
+    save_data::{
+        mass_effect_1::{
+            data::{PropertiesOwner, PropertyType},
+            player::Player,
+        },
+        RcRef,
+    },
+};
+
+pub enum Msg {
+    Remove(usize),
+    PropertyType(usize),
+    Add,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+    pub owner: PropertiesOwner,
+    pub label: Option<String>,
+}
+
+/// The properties of an object, a struct or an array item, with a form to add new ones
+pub struct Properties {
+    name: RcRef<String>,
+    struct_name: RcRef<String>,
+    property_type: usize,
+    status: Option<String>,
+}
+
+impl Component for Properties {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Properties {
+            name: Default::default(),
+            struct_name: Default::default(),
+            property_type: 0,
+            status: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let Props { player, owner, .. } = ctx.props();
+        match msg {
+            Msg::Remove(idx) => {
+                player.borrow_mut().remove_property(owner, idx);
+                self.status = None;
+            }
+            Msg::PropertyType(property_type) => self.property_type = property_type,
+            Msg::Add => {
+                let name = self.name.borrow().trim().to_owned();
+                let struct_name = self.struct_name.borrow().trim().to_owned();
+                let property_type = PropertyType::from(self.property_type);
+                let added =
+                    player.borrow_mut().add_property(owner, &name, property_type, &struct_name);
+                self.status = added.err().map(|err| err.to_string());
+                if self.status.is_none() {
+                    self.name = Default::default();
+                    self.struct_name = Default::default();
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { player, owner, label } = ctx.props();
+        let link = ctx.link();
+
+        let properties = player.borrow().properties(owner);
+        // The last one is always `None`
+        let take = properties.len().saturating_sub(1);
+        let properties = properties.into_iter().take(take).enumerate().map(|(idx, property)| {
+            html! {
+                <div class="flex items-start gap-1 min-w-0">
+                    { remove_link(link.callback(move |_| Msg::Remove(idx))) }
+                    <Property player={RcRef::clone(player)} {property} />
+                </div>
+            }
+        });
+
+        let is_struct = matches!(PropertyType::from(self.property_type), PropertyType::Struct);
+        let struct_name = is_struct.then(|| {
+            html! { <InputText label="Struct name" value={RcRef::clone(&self.struct_name)} /> }
+        });
+        let status = self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+
+        let content = html! {
+            <>
+                { for properties }
+                <div class="flex items-center gap-1 min-w-0">
+                    <Select
+                        options={PropertyType::variants()}
+                        current_idx={self.property_type}
+                        onselect={link.callback(Msg::PropertyType)}
+                        sized=false
+                    />
+                    <InputText label="Name" value={RcRef::clone(&self.name)} />
+                    { for struct_name }
+                    <button class="button" onclick={link.callback(|_| Msg::Add)}>
+                        { tr("Add property") }
+                    </button>
+                </div>
+                { for status }
+            </>
+        };
+
+        match label {
+            Some(label) => html! {
+                <RawUiStruct label={label.clone()}>
+                    { content }
+                </RawUiStruct>
+            },
+            None => content,
+        }
+    }
+}
@@ -5,19 +5,26 @@ use yew::prelude::*;
 use crate::{
     gui::{
         components::{raw_ui::RawUiStruct, CallbackType, InputText},
+        format_code,
+        mass_effect_1::raw_data::properties::Properties,
+        mass_effect_1_le::remove_link,
         raw_ui::RawUi,
     },
     save_data::{
         mass_effect_1::{
-            data::{ArrayType, Property as DataProperty, StructType},
+            data::{
+                ArrayType, PropertiesOwner, Property as DataProperty, PropertyType, StructType,
+            },
             player::Player,
         },
-        List, RcCell, RcRef,
+        RcCell, RcRef,
     },
 };
 
 pub enum Msg {
     DuplicateName(RcCell<u32>, CallbackType),
+    AddArrayItem(Option<PropertyType>),
+    RemoveArrayItem(usize),
 }
 
 #[derive(Properties, PartialEq)]
@@ -37,14 +44,16 @@ impl Props {
     }
 }
 
-pub struct Property;
+pub struct Property {
+    status: Option<String>,
+}
 
 impl Component for Property {
     type Message = Msg;
     type Properties = Props;
 
     fn create(_ctx: &Context<Self>) -> Self {
-        Property
+        Property { status: None }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -65,6 +74,18 @@ impl Component for Property {
 
                 true
             }
+            Msg::AddArrayItem(item_type) => {
+                let Props { player, property, .. } = ctx.props();
+                let added = player.borrow().add_array_item(property, item_type);
+                self.status = added.err().map(|err| err.to_string());
+                true
+            }
+            Msg::RemoveArrayItem(idx) => {
+                let Props { player, property, .. } = ctx.props();
+                player.borrow().remove_array_item(property, idx);
+                self.status = None;
+                true
+            }
             _ => unreachable!(),
         }
     }
@@ -102,6 +123,7 @@ impl Component for Property {
         match &*ctx.props().property() {
             DataProperty::Array { name_id, array, .. } => {
                 let label = get_name(name_id);
+                let link = ctx.link();
 
                 let items = array.iter().enumerate().map(|(idx, item)| match item {
                     ArrayType::Int(int) => int.view(&idx.to_string()),
@@ -112,8 +134,7 @@ impl Component for Property {
                             let object_name = player.get_name(object.object_name_id);
 
                             let label = format!("{} : {}", object_name, idx);
-                            let properties = &player.get_data(*object_id).properties;
-                            self.view_properties(ctx, label, properties)
+                            self.view_properties(ctx, label, PropertiesOwner::Object(*object_id))
                         } else {
                             // Null
                             html! { "Null" }
@@ -121,14 +142,44 @@ impl Component for Property {
                     }
                     ArrayType::Vector(vector) => vector.view(&idx.to_string()),
                     ArrayType::String(string) => string.view(&idx.to_string()),
-                    ArrayType::Properties(properties) => {
-                        self.view_properties(ctx, idx.to_string(), properties)
+                    ArrayType::Properties(_) => {
+                        let owner =
+                            PropertiesOwner::ArrayItem(RcRef::clone(&ctx.props().property), idx);
+                        html! {
+                            <div class="flex items-start gap-1 min-w-0">
+                                { remove_link(link.callback(move |_| Msg::RemoveArrayItem(idx))) }
+                                { self.view_properties(ctx, idx.to_string(), owner) }
+                            </div>
+                        }
                     }
                 });
 
+                // The items of an empty array have no type, the user says it is an array of structs
+                let add = if array.is_empty() {
+                    Some(("add struct", Some(PropertyType::Struct)))
+                } else {
+                    let is_struct_array =
+                        array.iter().all(|item| matches!(item, ArrayType::Properties(_)));
+                    is_struct_array.then(|| ("add", None))
+                };
+                let add = add.map(|(label, item_type)| {
+                    html! {
+                        <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                            onclick={link.callback(move |_| Msg::AddArrayItem(item_type))}
+                        >
+                            { label }
+                        </button>
+                    }
+                });
+
+                let status =
+                    self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+
                 html! {
                     <RawUiStruct {label}>
                         { for items }
+                        { for add }
+                        { for status }
                     </RawUiStruct>
                 }
             }
@@ -178,8 +229,7 @@ impl Component for Property {
                         let object_name = player.get_name(object.object_name_id);
 
                         let label = format!("{} : {}", object_name, label);
-                        let properties = &player.get_data(*object_id).properties;
-                        self.view_properties(ctx, label, properties)
+                        self.view_properties(ctx, label, PropertiesOwner::Object(*object_id))
                     }
                     Ordering::Less => {
                         // Class
@@ -210,35 +260,25 @@ impl Component for Property {
                     StructType::LinearColor(color) => color.view(&label),
                     StructType::Vector(vector) => vector.view(&label),
                     StructType::Rotator(rotator) => rotator.view(&label),
-                    StructType::Properties(properties) => {
-                        self.view_properties(ctx, label, properties)
+                    StructType::Properties(_) => {
+                        let owner = PropertiesOwner::Struct(RcRef::clone(&ctx.props().property));
+                        self.view_properties(ctx, label, owner)
                     }
                 }
             }
+            DataProperty::Unknown { name_id, type_id, .. } => {
+                let label = get_name(name_id);
+                view_text(player.get_name(*type_id), label)
+            }
             DataProperty::None { .. } => unreachable!(),
         }
     }
 }
 
 impl Property {
-    fn view_properties(
-        &self, ctx: &Context<Self>, label: String, properties: &List<RcRef<DataProperty>>,
-    ) -> Html {
-        let len = properties.len();
-        let take = if len > 0 { len - 1 } else { 0 };
-        let properties = properties.iter().take(take).map(|property| {
-            html! {
-                <Property
-                    player={RcRef::clone(&ctx.props().player)}
-                    property={RcRef::clone(property)}
-                />
-            }
-        });
-
+    fn view_properties(&self, ctx: &Context<Self>, label: String, owner: PropertiesOwner) -> Html {
         html! {
-            <RawUiStruct {label}>
-                { for properties }
-            </RawUiStruct>
+            <Properties player={RcRef::clone(&ctx.props().player)} {owner} {label} />
         }
     }
 }
@@ -0,0 +1,175 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{
+    gui::{
+        components::Table, format_code, i18n::tr, mass_effect_1::raw_data::properties::Properties,
+        mass_effect_1_le::remove_link,
+    },
+    save_data::{
+        mass_effect_1::{data::PropertiesOwner, player::Player, world::Level},
+        RcRef,
+    },
+};
+
+pub enum Msg {
+    Filter(InputEvent),
+    Restore(i32, usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub world: RcRef<Player>,
+}
+
+/// The levels of `WorldSavePackage.sav`, their objects and the destroyed ones
+pub struct Me1World {
+    filter: String,
+    status: Option<String>,
+}
+
+impl Component for Me1World {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Me1World { filter: String::new(), status: None }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Filter(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.filter = input.value().to_lowercase();
+                }
+            }
+            Msg::Restore(object_id, idx) => {
+                let world = ctx.props().world.borrow();
+                let level = world
+                    .world_maps()
+                    .into_iter()
+                    .flat_map(|map| map.levels)
+                    .find(|level| level.object_id == object_id);
+                if let Some(level) = level {
+                    self.status =
+                        level.restore_destroyed_object(idx).err().map(|err| err.to_string());
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let world = ctx.props().world.borrow();
+
+        let maps = world.world_maps().into_iter().filter_map(|map| {
+            let map_matches = self.matches(&map.name);
+            let levels = map
+                .levels
+                .iter()
+                .filter_map(|level| {
+                    let matches = map_matches || self.matches(&level.name);
+                    self.view_level(ctx, &world, level, matches)
+                })
+                .collect::<Vec<_>>();
+
+            (!levels.is_empty()).then(|| {
+                html! {
+                    <Table title={map.name} opened={!self.filter.is_empty()}>
+                        { for levels }
+                    </Table>
+                }
+            })
+        });
+
+        let status = self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                { for status }
+                <input type="text" class="input" placeholder={tr("Filter by map, level or actor")}
+                    value={self.filter.clone()}
+                    oninput={ctx.link().callback(Msg::Filter)}
+                />
+                { for maps }
+            </div>
+        }
+    }
+}
+
+impl Me1World {
+    fn matches(&self, name: &str) -> bool {
+        self.filter.is_empty() || name.to_lowercase().contains(&self.filter)
+    }
+
+    fn view_level(
+        &self, ctx: &Context<Self>, world: &Player, level: &Level, matches: bool,
+    ) -> Option<Html> {
+        let link = ctx.link();
+        let object_id = level.object_id;
+
+        let objects = level
+            .objects
+            .iter()
+            .map(|object_id| {
+                let label = world
+                    .owner_name(*object_id)
+                    .unwrap_or_else(|| world.object_class_name(*object_id));
+                (*object_id, label)
+            })
+            .filter(|(_, label)| matches || self.matches(label))
+            .map(|(object_id, label)| {
+                html! {
+                    <Properties
+                        player={RcRef::clone(&ctx.props().world)}
+                        owner={PropertiesOwner::Object(object_id)}
+                        {label}
+                    />
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let destroyed_objects = level
+            .destroyed_objects()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, path)| matches || self.matches(path))
+            .map(|(idx, path)| {
+                html! {
+                    <div class="flex items-center gap-1 min-w-0">
+                        { remove_link(link.callback(move |_| Msg::Restore(object_id, idx))) }
+                        <span class="truncate" title={path.clone()}>{ path }</span>
+                    </div>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if objects.is_empty() && destroyed_objects.is_empty() {
+            return None;
+        }
+
+        let objects = (!objects.is_empty()).then(|| {
+            html! {
+                <Table title={format!("{} - {}", level.name, tr("Objects"))} opened={false}>
+                    { for objects }
+                </Table>
+            }
+        });
+        let destroyed_objects = (!destroyed_objects.is_empty()).then(|| {
+            html! {
+                <Table title={format!("{} - {}", level.name, tr("Destroyed objects"))}
+                    opened={false}
+                >
+                    { for destroyed_objects }
+                </Table>
+            }
+        });
+
+        Some(html! {
+            <>
+                { for objects }
+                { for destroyed_objects }
+            </>
+        })
+    }
+}
@@ -9,14 +9,17 @@ use yew::{context::ContextHandle, prelude::*};
 use crate::{
     gui::{
         components::{CallbackType, Helper, InputNumber, InputText, NumberType, Select, Table},
+        i18n::tr_format,
         mass_effect_1_le::bonus_talents::BonusTalents,
         raw_ui::RawUi,
+        shared::SquadClipboard,
     },
     save_data::{
         mass_effect_1_le::{
-            player::{Item, Me1LeClass, Player},
+            player::{respec_talents, Item, Me1LeClass, Player},
             player_class_db::{Me1LePlayerClass, Me1LePlayerClassDb},
             squad::Henchman,
+            talent_db::{CHARM_TALENT_ID, INTIMIDATE_TALENT_ID},
             Me1LeSaveData,
         },
         shared::{
@@ -106,8 +109,60 @@ impl VanguardSpec {
     }
 }
 
+/// The names and the talent ids of the specializations of a class, `None` first
+pub fn specializations(player_class: &Me1LeClass) -> (&'static [&'static str], &'static [i32]) {
+    match *player_class {
+        Me1LeClass::Soldier => (SoldierSpec::variants(), SoldierSpec::ids()),
+        Me1LeClass::Engineer => (EngineerSpec::variants(), EngineerSpec::ids()),
+        Me1LeClass::Adept => (AdeptSpec::variants(), AdeptSpec::ids()),
+        Me1LeClass::Infiltrator => (InfiltratorSpec::variants(), InfiltratorSpec::ids()),
+        Me1LeClass::Sentinel => (SentinelSpec::variants(), SentinelSpec::ids()),
+        Me1LeClass::Vanguard => (VanguardSpec::variants(), VanguardSpec::ids()),
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Stat {
+    Health,
+    HealthMax,
+    Shield,
+    Stability,
+    Toxic,
+    Stamina,
+    Focus,
+    Precision,
+    Coordination,
+    XpLevel,
+    Charm,
+    Intimidate,
+    Haggle,
+    SpectreRank,
+}
+
+impl Stat {
+    // Upper bounds accepted by the editor, Spectre Training has 12 ranks
+    const ATTRIBUTE_MAX: i32 = 100;
+    const LEVEL_MAX: i32 = 60;
+    const SPECTRE_RANK_MAX: u8 = 12;
+    // The save does not store the maximum shields, they come from the armor and its mods.
+    // Not a game value, a bound well above any armor so a typo does not write absurd shields
+    const SHIELD_MAX: f32 = 10_000.0;
+
+    fn helper(self) -> &'static str {
+        match self {
+            Stat::Stamina | Stat::Focus | Stat::Precision | Stat::Coordination => "0 - 100",
+            Stat::XpLevel => "1 - 60",
+            Stat::SpectreRank => "0 - 12, 0 if you are not a Spectre",
+            Stat::Health => "0 - Max Health",
+            Stat::Shield => "0 - 10000",
+            _ => "0 or more",
+        }
+    }
+}
+
 pub enum Msg {
     DatabaseLoaded(Databases),
+    Stat(Stat, CallbackType),
     Gender(usize),
     Origin(usize),
     Notoriety(usize),
@@ -216,10 +271,8 @@ impl Component for Me1LeGeneral {
                     (RcCell::clone(&player.talent_points), RcRef::clone(&player.complex_talents))
                 };
 
-                for talent in complex_talents.borrow_mut().iter_mut() {
-                    talent_points.update(|tp| tp + talent.borrow().current_rank());
-                    talent.borrow_mut().set_current_rank(0);
-                }
+                let refunded = respec_talents(&complex_talents.borrow());
+                talent_points.update(|tp| tp + refunded);
                 true
             }
             Msg::TalentPoints(CallbackType::Int(talent_points)) => {
@@ -228,7 +281,8 @@ impl Component for Me1LeGeneral {
             }
             Msg::PlayerClass(class_idx) => {
                 if let Some(ref player_class_db) = self.player_class_db {
-                    if let Some(new_class_data) = player_class_db.get(class_idx) {
+                    let new_class = Me1LeClass::from(class_idx);
+                    if let Some(new_class_data) = player_class_db.class(&new_class) {
                         let Me1LePlayerClass {
                             player_class,
                             localized_class_name,
@@ -243,7 +297,7 @@ impl Component for Me1LeGeneral {
 
                         *player.player_class_mut() = player_class.clone();
                         player.set_specialization_bonus_id(-1);
-                        player.set_localized_class_name(*localized_class_name);
+                        *player.localized_class_name_mut() = *localized_class_name;
                         player.set_auto_levelup_template_id(*auto_levelup_template_id);
 
                         // Simple talents
@@ -270,8 +324,8 @@ impl Component for Me1LeGeneral {
                             let mut player_complex_talents = player.complex_talents_mut();
 
                             const IGNORED_COMPLEXES: &[i32] = &[
-                                108, // Charm
-                                109, // Intimidate
+                                CHARM_TALENT_ID,
+                                INTIMIDATE_TALENT_ID,
                                 259, // Spectre
                             ];
 
@@ -365,14 +419,7 @@ impl Component for Me1LeGeneral {
                 true
             }
             Msg::PlayerSpecialization(spec_idx) => {
-                let specs = match *player.player_class() {
-                    Me1LeClass::Soldier => SoldierSpec::ids(),
-                    Me1LeClass::Engineer => EngineerSpec::ids(),
-                    Me1LeClass::Adept => AdeptSpec::ids(),
-                    Me1LeClass::Infiltrator => InfiltratorSpec::ids(),
-                    Me1LeClass::Sentinel => SentinelSpec::ids(),
-                    Me1LeClass::Vanguard => VanguardSpec::ids(),
-                };
+                let (_, specs) = specializations(&player.player_class());
 
                 let complex_talents = player.complex_talents.borrow_mut();
                 let current_spec = complex_talents.iter().find(|talent| {
@@ -398,6 +445,55 @@ impl Component for Me1LeGeneral {
                 }
                 true
             }
+            Msg::Stat(stat, value) => {
+                match (stat, value) {
+                    (Stat::SpectreRank, CallbackType::Byte(rank)) => {
+                        let rank = rank.min(Stat::SPECTRE_RANK_MAX);
+                        player.set_spectre_rank(rank);
+
+                        // ME1 plot
+                        if let Some(mut is_spectre) = plot.booleans_mut().get_mut(3916) {
+                            *is_spectre = rank > 0;
+                        }
+                    }
+                    (_, CallbackType::Int(value)) => {
+                        let (cell, min, max) = match stat {
+                            Stat::Stamina => (&player.stamina, 0, Stat::ATTRIBUTE_MAX),
+                            Stat::Focus => (&player.focus, 0, Stat::ATTRIBUTE_MAX),
+                            Stat::Precision => (&player.precision, 0, Stat::ATTRIBUTE_MAX),
+                            Stat::Coordination => (&player.coordination, 0, Stat::ATTRIBUTE_MAX),
+                            Stat::XpLevel => (&player.xp_level, 1, Stat::LEVEL_MAX),
+                            _ => unreachable!(),
+                        };
+                        cell.set(value.clamp(min, max));
+                    }
+                    (Stat::Health, CallbackType::Float(health)) => {
+                        let health_max = player.health_max();
+                        player.set_health(health.clamp(0.0, health_max));
+                    }
+                    (Stat::HealthMax, CallbackType::Float(health_max)) => {
+                        let health_max = health_max.max(0.0);
+                        player.set_health_max(health_max);
+                        player.health.update(|health| health.min(health_max));
+                    }
+                    (Stat::Shield, CallbackType::Float(shield)) => {
+                        player.set_shield(shield.clamp(0.0, Stat::SHIELD_MAX));
+                    }
+                    (_, CallbackType::Float(value)) => {
+                        let cell = match stat {
+                            Stat::Stability => &player.stability,
+                            Stat::Toxic => &player.toxic,
+                            Stat::Charm => &player.skill_charm,
+                            Stat::Intimidate => &player.skill_intimidate,
+                            Stat::Haggle => &player.skill_haggle,
+                            _ => unreachable!(),
+                        };
+                        cell.set(value.max(0.0));
+                    }
+                    _ => unreachable!(),
+                }
+                true
+            }
             Msg::BonusTalent(has_talent_points) => {
                 if let Some(talent_points) = has_talent_points {
                     player.talent_points.update(|tp| tp + talent_points);
@@ -418,13 +514,16 @@ impl Component for Me1LeGeneral {
                     <div class="flex-1 pr-1 flex flex-col gap-1">
                         { Self::role_play(ctx, save_game.player()) }
                         { Self::gameplay(ctx, save_game.player()) }
+                        { Self::attributes(ctx, save_game.player()) }
                         { Self::bonus_talents(ctx, player_class_db, save_game.player()) }
                     </div>
                     <div class="flex-1 pl-1 flex flex-col gap-1">
                         { Self::general(ctx, save_game.player().game_options()) }
                         { Self::morality(save_game.plot()) }
+                        { Self::persuasion(ctx, save_game.player(), save_game.plot()) }
                         { Self::resources(save_game.player()) }
                         { Self::squad(ctx, save_game.squad()) }
+                        <SquadClipboard<Henchman> squad={RcRef::clone(&save_game.squad)} />
                     </div>
                 </div>
             }
@@ -488,14 +587,7 @@ impl Me1LeGeneral {
         let current_player_class = player_class.clone() as usize;
         let current_spec_id = specialization_bonus_id.get();
 
-        let (spec_variants, spec_ids) = match *player_class {
-            Me1LeClass::Soldier => (SoldierSpec::variants(), SoldierSpec::ids()),
-            Me1LeClass::Engineer => (EngineerSpec::variants(), EngineerSpec::ids()),
-            Me1LeClass::Adept => (AdeptSpec::variants(), AdeptSpec::ids()),
-            Me1LeClass::Infiltrator => (InfiltratorSpec::variants(), InfiltratorSpec::ids()),
-            Me1LeClass::Sentinel => (SentinelSpec::variants(), SentinelSpec::ids()),
-            Me1LeClass::Vanguard => (VanguardSpec::variants(), VanguardSpec::ids()),
-        };
+        let (spec_variants, spec_ids) = specializations(&player_class);
 
         let current_spec_idx = spec_ids
             .iter()
@@ -600,6 +692,68 @@ impl Me1LeGeneral {
         }
     }
 
+    fn stat(ctx: &Context<Self>, label: &str, stat: Stat, value: NumberType) -> Html {
+        html! {
+            <InputNumber label={label.to_owned()} {value}
+                helper={stat.helper()}
+                onchange={ctx.link().callback(move |value| Msg::Stat(stat, value))}
+            />
+        }
+    }
+
+    fn attributes(ctx: &Context<Self>, player: Ref<'_, Player>) -> Html {
+        let int = |value: i32| NumberType::Int(value.into());
+        let float = |value: f32| NumberType::Float(value.into());
+        html! {
+            <Table title="Attributes">
+                { Self::stat(ctx, "Health", Stat::Health, float(player.health())) }
+                { Self::stat(ctx, "Max Health", Stat::HealthMax, float(player.health_max())) }
+                { Self::stat(ctx, "Shield", Stat::Shield, float(player.shield())) }
+                { Self::stat(ctx, "Stability", Stat::Stability, float(player.stability())) }
+                { Self::stat(ctx, "Toxic", Stat::Toxic, float(player.toxic())) }
+                { Self::stat(ctx, "Stamina", Stat::Stamina, int(player.stamina())) }
+                { Self::stat(ctx, "Focus", Stat::Focus, int(player.focus())) }
+                { Self::stat(ctx, "Precision", Stat::Precision, int(player.precision())) }
+                { Self::stat(ctx, "Coordination", Stat::Coordination, int(player.coordination())) }
+                { Self::stat(ctx, "XP Level", Stat::XpLevel, int(player.xp_level())) }
+            </Table>
+        }
+    }
+
+    fn persuasion(ctx: &Context<Self>, player: Ref<'_, Player>, plot: Ref<'_, PlotTable>) -> Html {
+        // The Paragon and Renegade points unlock the ranks of the Charm and Intimidate talents
+        let talent = |talent_id: i32, label: &str, points: Option<i32>| {
+            let rank = player.complex_talents().iter().find_map(|talent| {
+                let talent = talent.borrow();
+                (talent.talent_id() == talent_id)
+                    .then(|| (talent.current_rank(), talent.max_rank()))
+            });
+            let points = points.map(|points| points.to_string()).unwrap_or_default();
+            let rank = rank
+                .map(|(rank, max_rank)| tr_format("talent rank {} / {}", &[&rank, &max_rank]))
+                .unwrap_or_default();
+            html! {
+                <span class="text-white/50">{ tr_format(label, &[&points]) }{ " - " }{ rank }</span>
+            }
+        };
+        let paragon = plot.integers().get(47).map(RcCell::get);
+        let renegade = plot.integers().get(46).map(RcCell::get);
+        let float = |value: f32| NumberType::Float(value.into());
+
+        html! {
+            <Table title="Persuasion">
+                { talent(CHARM_TALENT_ID, "{} Paragon points", paragon) }
+                { Self::stat(ctx, "Charm", Stat::Charm, float(player.skill_charm())) }
+                { talent(INTIMIDATE_TALENT_ID, "{} Renegade points", renegade) }
+                { Self::stat(ctx, "Intimidate", Stat::Intimidate, float(player.skill_intimidate())) }
+                { Self::stat(ctx, "Haggle", Stat::Haggle, float(player.skill_haggle())) }
+                { Self::stat(ctx, "Spectre Rank", Stat::SpectreRank,
+                    NumberType::Byte(player.spectre_rank().into()))
+                }
+            </Table>
+        }
+    }
+
     fn resources(player: Ref<'_, Player>) -> Html {
         let Player { credits, medigel, grenades, omnigel, .. } = &*player;
         html! {
@@ -3,14 +3,20 @@ use std::{cell::Ref, rc::Rc};
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::components::{Select, Table},
+    gui::{
+        components::{CheckBox, InputNumber, NumberType, Select, Table},
+        i18n::tr,
+        raw_ui::RawUi,
+    },
     save_data::{
         mass_effect_1_le::{
             item_db::{DbItem, Me1ItemDb},
+            legacy::{self, BaseObject, Object, Shop},
             player::{Inventory, Item, ItemLevel, Player},
             squad::Henchman,
+            LegacyObject, NoExportData,
         },
-        RcRef,
+        RcCell, RcRef,
     },
     services::database::Databases,
 };
@@ -24,12 +30,20 @@ pub enum Msg {
     ChangeItemLevel(RcRef<Item>, usize),
     RemoveItem(RcRef<Vec<RcRef<Item>>>, usize),
     AddItem(RcRef<Vec<RcRef<Item>>>),
+    ChangeStockItem(RcRef<BaseObject>, DbItem),
+    ChangeStockItemLevel(RcRef<BaseObject>, usize),
+    RemoveStockItem(RcRef<legacy::Inventory>, usize),
+    AddStockItem(RcRef<legacy::Inventory>, String),
+    RemovePlotItem(RcRef<legacy::Inventory>, usize),
+    ResetShop(RcRef<Shop>),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub player: RcRef<Player>,
     pub squad: RcRef<Vec<RcRef<Henchman>>>,
+    /// The shops are in the legacy maps, missing from character exports
+    pub no_export: Option<RcRef<NoExportData>>,
 }
 
 impl Props {
@@ -78,13 +92,74 @@ impl Component for Me1LeInventory {
                 false
             }
             Msg::RemoveItem(item_list, idx) => {
-                item_list.borrow_mut().remove(idx);
-                true
+                let mut items = item_list.borrow_mut();
+                let removed = idx < items.len();
+                if removed {
+                    items.remove(idx);
+                }
+                removed
             }
             Msg::AddItem(item_list) => {
                 item_list.borrow_mut().push(Default::default());
                 true
             }
+            Msg::ChangeStockItem(object, new_item) => {
+                match object.borrow()._object {
+                    Object::Item(ref item) => {
+                        let mut item = item.borrow_mut();
+                        item.set_item_id(new_item.item_id);
+                        item.set_manufacturer_id(new_item.manufacturer_id);
+                    }
+                    Object::ItemMod(ref item_mod) => {
+                        let mut item_mod = item_mod.borrow_mut();
+                        item_mod.set_item_id(new_item.item_id);
+                        item_mod.set_manufacturer_id(new_item.manufacturer_id);
+                    }
+                    _ => (),
+                }
+                false
+            }
+            Msg::ChangeStockItemLevel(object, item_level) => {
+                match object.borrow()._object {
+                    Object::Item(ref item) => {
+                        *item.borrow_mut().item_level_mut() = ItemLevel::from(item_level)
+                    }
+                    Object::ItemMod(ref item_mod) => {
+                        *item_mod.borrow_mut().item_level_mut() = ItemLevel::from(item_level)
+                    }
+                    _ => (),
+                }
+                false
+            }
+            Msg::RemoveStockItem(inventory, idx) => {
+                let mut inventory = inventory.borrow_mut();
+                let mut items = inventory.items_mut();
+                let removed = idx < items.len();
+                if removed {
+                    items.remove(idx);
+                }
+                removed
+            }
+            Msg::AddStockItem(inventory, actor) => {
+                let mut inventory = inventory.borrow_mut();
+                let mut items = inventory.items_mut();
+                let item = BaseObject::new_item(&actor, &items);
+                items.push(item.into());
+                true
+            }
+            Msg::RemovePlotItem(inventory, idx) => {
+                let mut inventory = inventory.borrow_mut();
+                let mut plot_items = inventory.plot_items_mut();
+                let removed = idx < plot_items.len();
+                if removed {
+                    plot_items.remove(idx);
+                }
+                removed
+            }
+            Msg::ResetShop(shop) => {
+                shop.borrow_mut().reset();
+                true
+            }
         }
     }
 
@@ -99,6 +174,7 @@ impl Component for Me1LeInventory {
                     </div>
                     <div class="flex-1 flex flex-col gap-1 pl-1 min-w-0">
                         { self.inventory(ctx, player.inventory()) }
+                        { for ctx.props().no_export.as_ref().map(|no_export| self.shops(ctx, no_export)) }
                     </div>
                 </div>
             }
@@ -136,6 +212,13 @@ impl Me1LeInventory {
             let item = RcRef::clone(item);
             ctx.link().callback(move |idx| Msg::ChangeItemLevel(RcRef::clone(&item), idx))
         };
+        self.item_selects(current_item, current_level, onselect_item, onselect_level)
+    }
+
+    fn item_selects(
+        &self, current_item: DbItem, current_level: usize, onselect_item: Callback<DbItem>,
+        onselect_level: Callback<usize>,
+    ) -> Html {
         html! {
             <>
                 <ItemSelect
@@ -229,22 +312,7 @@ impl Me1LeInventory {
 
             html! {
                 <div class="flex items-center gap-1 min-w-0">
-                    <div class="py-px">
-                        <a class={classes![
-                                "rounded-none",
-                                "select-none",
-                                "hover:bg-theme-hover",
-                                "active:bg-theme-active",
-                                "bg-theme-bg",
-                                "px-1",
-                                "py-0",
-                                "cursor-pointer",
-                            ]}
-                            onclick={link.callback(move |_| Msg::RemoveItem(RcRef::clone(&item_list), idx))}
-                        >
-                            {"remove"}
-                        </a>
-                    </div>
+                    { remove_link(link.callback(move |_| Msg::RemoveItem(RcRef::clone(&item_list), idx))) }
                     { item }
                 </div>
             }
@@ -280,4 +348,161 @@ impl Me1LeInventory {
             </>
         }
     }
+
+    fn shops(&self, ctx: &Context<Self>, no_export: &RcRef<NoExportData>) -> Html {
+        let no_export = no_export.borrow();
+        let mut shops = Vec::new();
+        for LegacyObject { map_name, object, .. } in no_export.legacy_objects() {
+            let object = object.borrow();
+            if let Object::Shop(ref shop) = object._object {
+                let actor = object.actor_name();
+                let title = format!("{} - {}", map_name, actor);
+                shops.push(self.shop(ctx, title, actor, shop));
+            }
+        }
+
+        html! {
+            <>
+                <div>
+                    <p>{ tr("Shops") }</p>
+                    <hr class="border-t border-default-border" />
+                </div>
+                { for shops }
+            </>
+        }
+    }
+
+    fn shop(&self, ctx: &Context<Self>, title: String, actor: String, shop: &RcRef<Shop>) -> Html {
+        let link = ctx.link();
+        let reset = {
+            let shop = RcRef::clone(shop);
+            link.callback(move |_| Msg::ResetShop(RcRef::clone(&shop)))
+        };
+
+        let shop = shop.borrow();
+        let inventories = shop.inventory();
+        let inventories = inventories.iter().filter_map(|proxy| {
+            let object = proxy.borrow().proxy().as_ref().map(RcRef::clone)?;
+            let object = object.borrow();
+            match object._object {
+                Object::Inventory(ref inventory) => Some(RcRef::clone(inventory)),
+                _ => None,
+            }
+        });
+        let stock =
+            inventories.map(|inventory| self.stock(ctx, &actor, inventory)).collect::<Vec<_>>();
+
+        html! {
+            <Table {title} opened={false}>
+                <div class="flex items-center gap-2">
+                    <InputNumber label="Last player level"
+                        value={NumberType::Int(RcCell::clone(&shop.last_player_level))}
+                    />
+                    <CheckBox label="Initialized" value={RcCell::clone(&shop.is_initialized)} />
+                    <button class="button" onclick={reset}>{ tr("Reset stock") }</button>
+                </div>
+                { for stock }
+            </Table>
+        }
+    }
+
+    fn stock(&self, ctx: &Context<Self>, actor: &str, inventory: RcRef<legacy::Inventory>) -> Html {
+        let link = ctx.link();
+        let add = {
+            let inventory = RcRef::clone(&inventory);
+            let actor = actor.to_owned();
+            link.callback(move |_| Msg::AddStockItem(RcRef::clone(&inventory), actor.clone()))
+        };
+
+        let inv = inventory.borrow();
+        let items = inv.items();
+        let items = items.iter().enumerate().filter_map(|(idx, object)| {
+            let (current_item, current_level) = match object.borrow()._object {
+                Object::Item(ref item) => {
+                    let item = item.borrow();
+                    let db_item =
+                        DbItem { item_id: item.item_id(), manufacturer_id: item.manufacturer_id() };
+                    let item_level = *item.item_level() as usize;
+                    (db_item, item_level)
+                }
+                Object::ItemMod(ref item_mod) => {
+                    let item_mod = item_mod.borrow();
+                    let db_item = DbItem {
+                        item_id: item_mod.item_id(),
+                        manufacturer_id: item_mod.manufacturer_id(),
+                    };
+                    let item_level = *item_mod.item_level() as usize;
+                    (db_item, item_level)
+                }
+                _ => return None,
+            };
+            let onselect_item = {
+                let object = RcRef::clone(object);
+                link.callback(move |new_item| Msg::ChangeStockItem(RcRef::clone(&object), new_item))
+            };
+            let onselect_level = {
+                let object = RcRef::clone(object);
+                link.callback(move |idx| Msg::ChangeStockItemLevel(RcRef::clone(&object), idx))
+            };
+            let remove = {
+                let inventory = RcRef::clone(&inventory);
+                link.callback(move |_| Msg::RemoveStockItem(RcRef::clone(&inventory), idx))
+            };
+            Some(html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    { remove_link(remove) }
+                    { self.item_selects(current_item, current_level, onselect_item, onselect_level) }
+                </div>
+            })
+        });
+
+        let plot_items = inv.plot_items();
+        let plot_items = plot_items.iter().enumerate().map(|(idx, plot_item)| {
+            let remove = {
+                let inventory = RcRef::clone(&inventory);
+                link.callback(move |_| Msg::RemovePlotItem(RcRef::clone(&inventory), idx))
+            };
+            let plot_item = plot_item.borrow();
+            html! {
+                <div class="flex items-center gap-1 min-w-0">
+                    { remove_link(remove) }
+                    <InputNumber label="Price" value={NumberType::Int(RcCell::clone(&plot_item.base_price))} />
+                    { plot_item.localized_name.view("Name") }
+                </div>
+            }
+        });
+
+        html! {
+            <>
+                { for items }
+                <button class="rounded-none hover:bg-theme-hover active:bg-theme-active bg-theme-bg px-1"
+                    onclick={add}
+                >
+                    {"add"}
+                </button>
+                { for plot_items }
+            </>
+        }
+    }
+}
+
+pub fn remove_link(onclick: Callback<MouseEvent>) -> Html {
+    html! {
+        <div class="py-px">
+            <a class={classes![
+                    "rounded-none",
+                    "select-none",
+                    "hover:bg-theme-hover",
+                    "active:bg-theme-active",
+                    "bg-theme-bg",
+                    "px-1",
+                    "py-0",
+                    "cursor-pointer",
+                ]}
+                {onclick}
+            >
+                {"remove"}
+            </a>
+        </div>
+    }
 }
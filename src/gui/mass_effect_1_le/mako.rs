@@ -0,0 +1,143 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::{
+        components::{CheckBox, InputNumber, NumberType, Table},
+        i18n::tr,
+        raw_ui::RawUi,
+    },
+    save_data::{
+        mass_effect_1_le::{
+            legacy::{Object, Vehicle as LegacyVehicle, VehicleBehavior},
+            player::Player,
+            LegacyObject, NoExportData, Vehicle,
+        },
+        RcCell, RcRef,
+    },
+};
+
+pub enum Msg {
+    Restore(RcRef<Vehicle>),
+    RestoreLegacy(RcRef<LegacyVehicle>),
+    Unstick(RcRef<VehicleBehavior>, RcRef<LegacyVehicle>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+    pub no_export: RcRef<NoExportData>,
+}
+
+/// The Mako of the player, and its state in each map it has been driven in
+pub struct Me1LeMako;
+
+impl Component for Me1LeMako {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Me1LeMako
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Restore(mako) => mako.borrow_mut().restore(),
+            Msg::RestoreLegacy(vehicle) => vehicle.borrow_mut().restore(),
+            Msg::Unstick(behavior, vehicle) => {
+                let mut behavior = behavior.borrow_mut();
+                behavior.set_powertrain_enabled(true);
+                behavior.set_vehicle_fonction_enabled(true);
+                vehicle.borrow_mut().unstick();
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let Props { player, no_export } = &ctx.props();
+        let no_export = no_export.borrow();
+
+        let mako = no_export.mako();
+        let restore = {
+            let mako = RcRef::clone(&no_export.mako);
+            link.callback(move |_| Msg::Restore(RcRef::clone(&mako)))
+        };
+
+        let mut vehicles = Vec::new();
+        for LegacyObject { map_name, object, .. } in no_export.legacy_objects() {
+            let object = object.borrow();
+            if let Object::VehicleBehavior(ref behavior) = object._object {
+                let title = format!("{} - {}", map_name, object.actor_name());
+                vehicles.extend(Self::view_vehicle(link, title, behavior));
+            }
+        }
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <Table title="Mako">
+                    { mako.first_name.view("Name") }
+                    { mako.localized_last_name.view("Localized Name") }
+                    <div class="flex items-center gap-2">
+                        <InputNumber label="Health" value={NumberType::Float(RcCell::clone(&mako.health))} />
+                        <InputNumber label="Shield" value={NumberType::Float(RcCell::clone(&mako.shield))} />
+                        <button class="button" onclick={restore}>{ tr("Repair") }</button>
+                    </div>
+                    <CheckBox label="Driving" value={RcCell::clone(&player.borrow().is_driving)} />
+                </Table>
+                { for vehicles }
+            </div>
+        }
+    }
+}
+
+impl Me1LeMako {
+    fn view_vehicle(
+        link: &html::Scope<Self>, title: String, behavior: &RcRef<VehicleBehavior>,
+    ) -> Option<Html> {
+        let vehicle = {
+            let behavior = behavior.borrow();
+            let owner = behavior.owner().as_ref().map(RcRef::clone)?;
+            let owner = owner.borrow();
+            match owner._object {
+                Object::Vehicle(ref vehicle) => RcRef::clone(vehicle),
+                _ => return None,
+            }
+        };
+
+        let restore = {
+            let vehicle = RcRef::clone(&vehicle);
+            link.callback(move |_| Msg::RestoreLegacy(RcRef::clone(&vehicle)))
+        };
+        let unstick = {
+            let behavior = RcRef::clone(behavior);
+            let vehicle = RcRef::clone(&vehicle);
+            link.callback(move |_| Msg::Unstick(RcRef::clone(&behavior), RcRef::clone(&vehicle)))
+        };
+
+        let behavior = behavior.borrow();
+        let vehicle = vehicle.borrow();
+        Some(html! {
+            <Table {title} opened={false}>
+                <div class="flex items-center gap-2">
+                    <InputNumber label="Health" value={NumberType::Float(RcCell::clone(&vehicle.health))} />
+                    <InputNumber label="Shield" value={NumberType::Float(RcCell::clone(&vehicle.shield))} />
+                    <button class="button" onclick={restore}>{ tr("Repair") }</button>
+                    <button class="button" onclick={unstick}>{ tr("Unstick") }</button>
+                </div>
+                { vehicle.first_name.view("Name") }
+                { vehicle.localized_last_name.view("Localized Name") }
+                <CheckBox label="Powertrain enabled" value={RcCell::clone(&behavior.powertrain_enabled)} />
+                <CheckBox label="Vehicle function enabled"
+                    value={RcCell::clone(&behavior.vehicle_fonction_enabled)}
+                />
+                <CheckBox label="Hidden" value={RcCell::clone(&vehicle.hidden)} />
+                <CheckBox label="Stasis" value={RcCell::clone(&vehicle.stasis)} />
+                { vehicle.location.view("Location") }
+                { vehicle.rotation.view("Rotation") }
+                { vehicle.velocity.view("Velocity") }
+                { vehicle.acceleration.view("Acceleration") }
+            </Table>
+        })
+    }
+}
@@ -17,8 +17,11 @@ use crate::{
 mod bonus_talents;
 mod general;
 mod inventory;
+mod mako;
+mod talents;
+mod world;
 
-pub use self::{general::*, inventory::*};
+pub use self::{general::*, inventory::*, mako::*, talents::*, world::*};
 
 impl RawUi for RcRef<Me1LeSaveData> {
     fn view(&self, _: &str) -> yew::Html {
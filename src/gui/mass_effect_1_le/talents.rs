@@ -0,0 +1,241 @@
+use std::rc::Rc;
+
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{CallbackType, InputNumber, NumberType, Table},
+        i18n::{tr, tr_format},
+    },
+    save_data::{
+        mass_effect_1_le::{
+            player::{respec_talents, set_talent_rank, ComplexTalent, Player},
+            squad::Henchman,
+            talent_db::Me1LeTalentDb,
+        },
+        RcCell, RcRef,
+    },
+    services::database::Databases,
+};
+
+type Talents = RcRef<Vec<RcRef<ComplexTalent>>>;
+
+struct Character<'a> {
+    name: &'a str,
+    level: &'a RcCell<i32>,
+    talent_points: &'a RcCell<i32>,
+    talent_pool_points: &'a RcCell<i32>,
+    talents: &'a Talents,
+}
+
+pub enum Msg {
+    DatabaseLoaded(Databases),
+    Set(RcCell<i32>, CallbackType),
+    Rank {
+        talents: Talents,
+        idx: usize,
+        rank: CallbackType,
+        level: i32,
+        talent_points: RcCell<i32>,
+    },
+    Respec(Talents, RcCell<i32>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub player: RcRef<Player>,
+    pub squad: RcRef<Vec<RcRef<Henchman>>>,
+}
+
+/// The talent trees of Shepard and the squad, ranks are bought with the talent points
+pub struct Me1LeTalents {
+    _db_handle: ContextHandle<Databases>,
+    talent_db: Option<Rc<Me1LeTalentDb>>,
+}
+
+impl Component for Me1LeTalents {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (databases, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::DatabaseLoaded))
+            .expect("no database provider");
+
+        Me1LeTalents { _db_handle, talent_db: databases.get_me1_le_talents() }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DatabaseLoaded(dbs) => {
+                self.talent_db = dbs.get_me1_le_talents();
+            }
+            Msg::Set(cell, CallbackType::Int(value)) => cell.set(value),
+            Msg::Rank { talents, idx, rank: CallbackType::Int(rank), level, talent_points } => {
+                let left =
+                    set_talent_rank(&talents.borrow(), idx, rank, level, talent_points.get());
+                talent_points.set(left);
+            }
+            Msg::Respec(talents, talent_points) => {
+                let refunded = respec_talents(&talents.borrow());
+                talent_points.update(|tp| tp + refunded);
+            }
+            _ => unreachable!(),
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let talent_db = match self.talent_db {
+            Some(ref talent_db) => talent_db,
+            None => {
+                return html! {
+                    <>
+                        <p>{ "Loading database..." }</p>
+                        <hr class="border-t border-default-border" />
+                    </>
+                }
+            }
+        };
+
+        let Props { player, squad } = &ctx.props();
+        let player = player.borrow();
+        let shepard = self.character(
+            ctx,
+            talent_db,
+            Character {
+                name: "Shepard",
+                level: &player.level,
+                talent_points: &player.talent_points,
+                talent_pool_points: &player.talent_pool_points,
+                talents: &player.complex_talents,
+            },
+        );
+
+        let characters = [
+            ("hench_humanfemale", "Ashley"),
+            ("hench_turian", "Garrus"),
+            ("hench_humanmale", "Kaidan"),
+            ("hench_asari", "Liara"),
+            ("hench_quarian", "Tali"),
+            ("hench_krogan", "Wrex"),
+        ];
+        let squad = squad.borrow();
+        let squad = squad.iter().filter_map(|character| {
+            let character = character.borrow();
+            let name = characters
+                .iter()
+                .find_map(|&(tag, name)| (*character.tag() == tag).then(|| name))?;
+            Some(self.character(
+                ctx,
+                talent_db,
+                Character {
+                    name,
+                    level: &character.level,
+                    talent_points: &character.talent_points,
+                    talent_pool_points: &character.talent_pool_points,
+                    talents: &character.complex_talents,
+                },
+            ))
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                { shepard }
+                { for squad }
+            </div>
+        }
+    }
+}
+
+impl Me1LeTalents {
+    fn character(
+        &self, ctx: &Context<Self>, talent_db: &Me1LeTalentDb, character: Character<'_>,
+    ) -> Html {
+        let Character { name, level, talent_points, talent_pool_points, talents } = character;
+        let link = ctx.link();
+        let set = |cell: &RcCell<i32>| {
+            let cell = RcCell::clone(cell);
+            link.callback(move |value| Msg::Set(RcCell::clone(&cell), value))
+        };
+        let respec = {
+            let talents = RcRef::clone(talents);
+            let talent_points = RcCell::clone(talent_points);
+            link.callback(move |_| {
+                Msg::Respec(RcRef::clone(&talents), RcCell::clone(&talent_points))
+            })
+        };
+
+        let list = talents.borrow();
+        let mut sorted: Vec<_> = list.iter().enumerate().collect();
+        sorted.sort_by_key(|(_, talent)| talent.borrow().visual_order());
+
+        let rows = sorted.into_iter().map(|(idx, talent)| {
+            let talent = talent.borrow();
+            let onchange = {
+                let talents = RcRef::clone(talents);
+                let level = level.get();
+                let talent_points = RcCell::clone(talent_points);
+                link.callback(move |rank| Msg::Rank {
+                    talents: RcRef::clone(&talents),
+                    idx,
+                    rank,
+                    level,
+                    talent_points: RcCell::clone(&talent_points),
+                })
+            };
+
+            let max_rank = talent.max_rank_at(level.get());
+            let requirements = (!talent.is_unlocked(&list)).then(|| {
+                let prereq_ids = talent.prereq_talent_ids();
+                let prereq_ranks = talent.prereq_talent_ranks();
+                let requirements = prereq_ids
+                    .iter()
+                    .zip(prereq_ranks.iter())
+                    .map(|(id, rank)| {
+                        tr_format("{} rank {}", &[&talent_db.name(id.get()), &rank.get()])
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                html! {
+                    <span class="text-white/50">{ tr_format("Requires {}", &[&requirements]) }</span>
+                }
+            });
+
+            html! {
+                <div class="flex items-center gap-2">
+                    <InputNumber label={talent_db.name(talent.talent_id())}
+                        value={NumberType::Int(talent.current_rank().into())}
+                        {onchange}
+                    />
+                    <span class="text-white/50">
+                        { tr_format("max {} / {} at this level", &[&talent.max_rank(), &max_rank]) }
+                    </span>
+                    { for requirements }
+                </div>
+            }
+        });
+
+        html! {
+            <Table title={name.to_owned()}>
+                <div class="flex flex-wrap items-center gap-2">
+                    <InputNumber label="Level"
+                        value={NumberType::Int(level.get().into())}
+                        onchange={set(level)}
+                    />
+                    <InputNumber label="Talent Points"
+                        value={NumberType::Int(talent_points.get().into())}
+                        onchange={set(talent_points)}
+                    />
+                    <InputNumber label="Talent Pool Points"
+                        value={NumberType::Int(talent_pool_points.get().into())}
+                        onchange={set(talent_pool_points)}
+                    />
+                    <button class="button" onclick={respec}>{ tr("Respec all") }</button>
+                </div>
+                { for rows }
+            </Table>
+        }
+    }
+}
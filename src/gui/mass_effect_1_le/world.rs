@@ -0,0 +1,219 @@
+use indexmap::IndexMap;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{
+    gui::{
+        components::{CheckBox, InputNumber, NumberType, Table},
+        i18n::tr,
+        raw_ui::RawUi,
+    },
+    save_data::{
+        mass_effect_1_le::{
+            legacy::{ArtPlaceableBehavior, Object, PawnBehavior, World},
+            LegacyObject, NoExportData,
+        },
+        RcCell, RcRef,
+    },
+};
+
+pub enum Msg {
+    Filter(InputEvent),
+    Kill(RcRef<PawnBehavior>),
+    Revive(RcRef<PawnBehavior>),
+    Relock(RcRef<ArtPlaceableBehavior>),
+    Reset(RcRef<ArtPlaceableBehavior>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub no_export: RcRef<NoExportData>,
+}
+
+/// The NPCs and containers of each level, by level name
+type LevelViews = IndexMap<String, (Vec<Html>, Vec<Html>)>;
+
+/// The state of the levels visited in ME1, by map, level and actor
+pub struct Me1LeWorld {
+    filter: String,
+}
+
+impl Component for Me1LeWorld {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Me1LeWorld { filter: String::new() }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Filter(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.filter = input.value().to_lowercase();
+                }
+            }
+            Msg::Kill(pawn) => pawn.borrow_mut().kill(),
+            Msg::Revive(pawn) => pawn.borrow_mut().revive(),
+            Msg::Relock(placeable) => placeable.borrow_mut().relock(),
+            Msg::Reset(placeable) => placeable.borrow_mut().reset(),
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let no_export = ctx.props().no_export.borrow();
+        let legacy_maps = no_export.legacy_maps();
+
+        let worlds = legacy_maps.iter().filter_map(|(map_name, map)| {
+            let world = map.borrow().world().as_ref().map(RcRef::clone)?;
+            let world = match world.borrow()._object {
+                Object::World(ref world) => RcRef::clone(world),
+                _ => return None,
+            };
+            Some(self.view_world(map_name, world))
+        });
+
+        let link = ctx.link();
+        let mut maps: IndexMap<String, LevelViews> = IndexMap::new();
+        for LegacyObject { map_name, level_name, object } in no_export.legacy_objects() {
+            let object = object.borrow();
+            let actor_name = object.actor_name();
+            if !(self.matches(&map_name) || self.matches(&level_name) || self.matches(&actor_name))
+            {
+                continue;
+            }
+
+            let level = || maps.entry(map_name).or_default().entry(level_name).or_default();
+            match object._object {
+                Object::PawnBehavior(ref pawn) => {
+                    level().0.push(Self::view_pawn(link, actor_name, pawn))
+                }
+                Object::ArtPlaceableBehavior(ref placeable)
+                    if placeable.borrow().is_interactive() =>
+                {
+                    level().1.push(Self::view_placeable(link, actor_name, placeable))
+                }
+                _ => (),
+            }
+        }
+
+        let maps = maps.into_iter().map(|(map_name, levels)| {
+            let levels = levels.into_iter().map(|(level_name, (pawns, placeables))| {
+                Self::view_level(&level_name, pawns, placeables)
+            });
+            html! {
+                <Table title={map_name} opened={!self.filter.is_empty()}>
+                    { for levels }
+                </Table>
+            }
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                { for worlds }
+                <input type="text" class="input" placeholder={tr("Filter by map, level or actor")}
+                    value={self.filter.clone()}
+                    oninput={ctx.link().callback(Msg::Filter)}
+                />
+                { for maps }
+            </div>
+        }
+    }
+}
+
+impl Me1LeWorld {
+    fn matches(&self, name: &str) -> bool {
+        self.filter.is_empty() || name.to_lowercase().contains(&self.filter)
+    }
+
+    fn view_world(&self, map_name: &str, world: RcRef<World>) -> Html {
+        let world = world.borrow();
+        html! {
+            <Table title={format!("{} - {}", tr("World"), map_name)}>
+                { world.cinematics_seen.view("Cinematics seen") }
+                { world.scanned_clusters.view("Scanned clusters") }
+                { world.scanned_systems.view("Scanned systems") }
+                { world.scanned_planets.view("Scanned planets") }
+            </Table>
+        }
+    }
+
+    fn view_level(level_name: &str, pawns: Vec<Html>, placeables: Vec<Html>) -> Html {
+        let pawns = (!pawns.is_empty()).then(|| {
+            html! {
+                <Table title={format!("{} - {}", level_name, tr("NPCs"))} opened={false}>
+                    { for pawns }
+                </Table>
+            }
+        });
+        let placeables = (!placeables.is_empty()).then(|| {
+            html! {
+                <Table title={format!("{} - {}", level_name, tr("Containers"))} opened={false}>
+                    { for placeables }
+                </Table>
+            }
+        });
+
+        html! {
+            <>
+                { for pawns }
+                { for placeables }
+            </>
+        }
+    }
+
+    fn view_pawn(link: &html::Scope<Self>, actor_name: String, pawn: &RcRef<PawnBehavior>) -> Html {
+        let kill = {
+            let pawn = RcRef::clone(pawn);
+            link.callback(move |_| Msg::Kill(RcRef::clone(&pawn)))
+        };
+        let revive = {
+            let pawn = RcRef::clone(pawn);
+            link.callback(move |_| Msg::Revive(RcRef::clone(&pawn)))
+        };
+
+        let pawn = pawn.borrow();
+        html! {
+            <div class="flex items-center gap-2">
+                <span class="w-60 truncate" title={actor_name.clone()}>{ actor_name }</span>
+                <CheckBox label="Dead" value={RcCell::clone(&pawn.is_dead)} />
+                <InputNumber label="Health" value={NumberType::Float(RcCell::clone(&pawn.health))} />
+                <InputNumber label="Level" value={NumberType::Int(RcCell::clone(&pawn.level))} />
+                <InputNumber label="Talent Points"
+                    value={NumberType::Int(RcCell::clone(&pawn.talent_points))}
+                />
+                <button class="button" onclick={kill}>{ tr("Kill") }</button>
+                <button class="button" onclick={revive}>{ tr("Revive") }</button>
+            </div>
+        }
+    }
+
+    fn view_placeable(
+        link: &html::Scope<Self>, actor_name: String, placeable: &RcRef<ArtPlaceableBehavior>,
+    ) -> Html {
+        let relock = {
+            let placeable = RcRef::clone(placeable);
+            link.callback(move |_| Msg::Relock(RcRef::clone(&placeable)))
+        };
+        let reset = {
+            let placeable = RcRef::clone(placeable);
+            link.callback(move |_| Msg::Reset(RcRef::clone(&placeable)))
+        };
+
+        let placeable = placeable.borrow();
+        html! {
+            <div class="flex items-center gap-2">
+                <span class="w-60 truncate" title={actor_name.clone()}>{ actor_name }</span>
+                <span class="w-20">{ placeable.current_fsm_state_name().clone() }</span>
+                <CheckBox label="Destroyed" value={RcCell::clone(&placeable.is_destroyed)} />
+                <CheckBox label="Looted" value={RcCell::clone(&placeable.generated_treasure)} />
+                <CheckBox label="Skill game failed"
+                    value={RcCell::clone(&placeable.skill_game_failed)}
+                />
+                <button class="button" onclick={relock}>{ tr("Re-lock") }</button>
+                <button class="button" onclick={reset}>{ tr("Reset") }</button>
+            </div>
+        }
+    }
+}
@@ -6,10 +6,10 @@ use crate::{
     gui::{
         components::{Helper, InputText, Select, Table},
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{BonusPowerType, BonusPowers, SquadClipboard},
     },
     save_data::{
-        mass_effect_2::{player::Player, Difficulty},
+        mass_effect_2::{player::Player, squad::Henchman, Difficulty},
         shared::{
             player::{Notoriety, Origin},
             plot::PlotTable,
@@ -205,7 +205,7 @@ impl Component for Me2General {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let (difficulty, end_game_state, player, plot) = match ctx.props().save_game {
+        let (difficulty, end_game_state, player, plot, squad) = match ctx.props().save_game {
             Me2Type::Vanilla(ref me2) => {
                 let me2 = me2.borrow();
                 (
@@ -213,6 +213,7 @@ impl Component for Me2General {
                     RcRef::clone(&me2.end_game_state),
                     RcRef::clone(&me2.player),
                     RcRef::clone(&me2.plot),
+                    RcRef::clone(&me2.squad),
                 )
             }
             Me2Type::Legendary(ref me2) => {
@@ -222,6 +223,7 @@ impl Component for Me2General {
                     RcRef::clone(&me2.end_game_state),
                     RcRef::clone(&me2.player),
                     RcRef::clone(&me2.plot),
+                    RcRef::clone(&me2.squad),
                 )
             }
         };
@@ -237,6 +239,7 @@ impl Component for Me2General {
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(difficulty, end_game_state) }
                     { Self::bonus_powers(player.borrow()) }
+                    <SquadClipboard<Henchman> {squad} />
                 </div>
             </div>
         }
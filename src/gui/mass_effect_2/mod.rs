@@ -1,8 +1,13 @@
 mod general;
 mod plot;
+mod plot_checker;
+mod plot_presets;
 mod raw_plot;
+mod suicide_mission;
 
-pub use self::{general::*, plot::*, raw_plot::*};
+pub use self::{
+    general::*, plot::*, plot_checker::*, plot_presets::*, raw_plot::*, suicide_mission::*,
+};
 
 use crate::save_data::{
     mass_effect_2::{Me2LeSaveGame, Me2SaveGame},
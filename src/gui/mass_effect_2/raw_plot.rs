@@ -6,6 +6,7 @@ use crate::{
     gui::{
         components::{Tab, TabBar},
         shared::{FloatPlotType, IntPlotType, PlotType, RawPlot},
+        Theme,
     },
     save_data::{shared::plot::BitVec, RcRef},
     services::database::Databases,
@@ -27,13 +28,13 @@ pub fn me2_raw_plot(props: &Props) -> Html {
         html! {
             <TabBar>
                 <Tab title="Booleans">
-                    <RawPlot plots={PlotType::Boolean(RcRef::clone(booleans))} plot_db={Rc::clone(plot_db)} />
+                    <RawPlot plots={PlotType::Boolean(RcRef::clone(booleans))} plot_db={Rc::clone(plot_db)} theme={Theme::MassEffect2} />
                 </Tab>
                 <Tab title="Integers">
-                    <RawPlot plots={PlotType::Int(integers.clone())} plot_db={Rc::clone(plot_db)} />
+                    <RawPlot plots={PlotType::Int(integers.clone())} plot_db={Rc::clone(plot_db)} theme={Theme::MassEffect2} />
                 </Tab>
                 <Tab title="Floats">
-                    <RawPlot plots={PlotType::Float(floats.clone())} plot_db={Rc::clone(plot_db)} />
+                    <RawPlot plots={PlotType::Float(floats.clone())} plot_db={Rc::clone(plot_db)} theme={Theme::MassEffect2} />
                 </Tab>
             </TabBar>
         }
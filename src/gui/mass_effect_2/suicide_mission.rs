@@ -0,0 +1,249 @@
+use yew::prelude::*;
+
+use crate::{
+    gui::{
+        components::{Select, Table},
+        format_code,
+        shared::{set_preset_values, FloatPlotType, IntPlotType, SavePlotState},
+    },
+    save_data::{
+        mass_effect_2::suicide_mission::{Choices, CrewSurvivors, Squadmate, SuicideMission},
+        shared::plot::BitVec,
+        RcRef,
+    },
+};
+
+const SQUADMATE_OPTIONS: &[&str] = &[
+    "None",
+    "Garrus",
+    "Grunt",
+    "Jack",
+    "Jacob",
+    "Kasumi",
+    "Legion",
+    "Miranda",
+    "Mordin",
+    "Samara / Morinth",
+    "Tali",
+    "Thane",
+    "Zaeed",
+];
+
+#[derive(Clone, Copy)]
+pub enum Role {
+    TechSpecialist,
+    FirstLeader,
+    BioticSpecialist,
+    SecondLeader,
+    CrewEscort,
+    Squad(usize),
+}
+
+pub enum Msg {
+    Choose(Role, usize),
+    Write,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+    pub integers: IntPlotType,
+    pub floats: FloatPlotType,
+}
+
+pub struct Me2SuicideMission {
+    // Read once, the written outcome must not change the simulation
+    mission: SuicideMission,
+    choices: Choices,
+    status: Option<String>,
+}
+
+impl Component for Me2SuicideMission {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Me2SuicideMission { mission: Self::mission(ctx), choices: Default::default(), status: None }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.mission = Self::mission(ctx);
+        self.status = None;
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Choose(role, idx) => {
+                let choice = idx.checked_sub(1).and_then(|idx| Squadmate::ALL.get(idx)).copied();
+                let Choices {
+                    tech_specialist,
+                    first_leader,
+                    biotic_specialist,
+                    second_leader,
+                    crew_escort,
+                    final_squad,
+                } = &mut self.choices;
+                let role = match role {
+                    Role::TechSpecialist => tech_specialist,
+                    Role::FirstLeader => first_leader,
+                    Role::BioticSpecialist => biotic_specialist,
+                    Role::SecondLeader => second_leader,
+                    Role::CrewEscort => crew_escort,
+                    Role::Squad(idx) => &mut final_squad[idx],
+                };
+                *role = choice;
+                self.status = None;
+                true
+            }
+            Msg::Write => {
+                if let Ok(outcome) = self.mission.simulate(&self.choices) {
+                    let preset = self.mission.outcome_preset(&self.choices, &outcome);
+                    let Props { booleans, integers, floats } = ctx.props();
+                    self.status =
+                        Some(match set_preset_values(booleans, integers, floats, &preset) {
+                            Ok(()) => format!(
+                                "Survival flags written, {} squadmate(s) marked as dead",
+                                outcome.dead.len()
+                            ),
+                            Err(err) => format!("Survival flags not written: {}", err),
+                        });
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                { self.view_state() }
+                { self.view_choices(ctx) }
+                { self.view_outcome(ctx) }
+            </div>
+        }
+    }
+}
+
+impl Me2SuicideMission {
+    fn mission(ctx: &Context<Self>) -> SuicideMission {
+        let Props { booleans, integers, .. } = ctx.props();
+        let state =
+            SavePlotState { booleans: booleans.borrow(), integers, is_female: false, level: 0 };
+        SuicideMission::from_plot(&state)
+    }
+
+    fn view_state(&self) -> Html {
+        let mission = &self.mission;
+        let squadmates = mission.squadmates.iter().map(|state| {
+            let loyal = if state.is_loyal { "loyal" } else { "not loyal" };
+            html! { <p>{ format!("{} - {}", mission.name(state.squadmate), loyal) }</p> }
+        });
+        let upgrade = |name: &str, applied: bool| {
+            let applied = if applied { "applied" } else { "missing" };
+            html! { <p>{ format!("{} - {}", name, applied) }</p> }
+        };
+
+        html! {
+            <div class="flex gap-1">
+                <Table title="Recruited squadmates">
+                    { for squadmates }
+                </Table>
+                <Table title="Normandy">
+                    { upgrade("Heavy Ship Armor", mission.heavy_ship_armor) }
+                    { upgrade("Multicore Shielding", mission.multicore_shielding) }
+                    { upgrade("Thanix Cannon", mission.thanix_cannon) }
+                    <p>{ format!("Missions after crew abducted - {}", mission.crew_delay) }</p>
+                </Table>
+            </div>
+        }
+    }
+
+    fn view_choices(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let select = |role: Role, choice: Option<Squadmate>, label: &'static str| {
+            let current_idx = choice
+                .and_then(|choice| Squadmate::ALL.iter().position(|&squadmate| squadmate == choice))
+                .map(|idx| idx + 1)
+                .unwrap_or_default();
+            html! {
+                <div class="flex items-center gap-1 cursor-default">
+                    <Select
+                        options={SQUADMATE_OPTIONS}
+                        {current_idx}
+                        onselect={link.callback(move |idx| Msg::Choose(role, idx))}
+                    />
+                    { label }
+                </div>
+            }
+        };
+
+        let Choices {
+            tech_specialist,
+            first_leader,
+            biotic_specialist,
+            second_leader,
+            crew_escort,
+            final_squad,
+        } = self.choices;
+        html! {
+            <Table title="Choices">
+                { select(Role::TechSpecialist, tech_specialist, "Tech specialist") }
+                { select(Role::FirstLeader, first_leader, "First fireteam leader") }
+                { select(Role::BioticSpecialist, biotic_specialist, "Biotic specialist") }
+                { select(Role::SecondLeader, second_leader, "Second fireteam leader") }
+                { select(Role::CrewEscort, crew_escort, "Crew escort") }
+                { select(Role::Squad(0), final_squad[0], "Final squad") }
+                { select(Role::Squad(1), final_squad[1], "Final squad") }
+            </Table>
+        }
+    }
+
+    fn view_outcome(&self, ctx: &Context<Self>) -> Html {
+        let mission = &self.mission;
+        let outcome = match mission.simulate(&self.choices) {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                return html! {
+                    <Table title="Outcome">
+                        <p>{ err.to_string() }</p>
+                    </Table>
+                }
+            }
+        };
+
+        let dead = outcome.dead.iter().map(|(&squadmate, reason)| {
+            html! { <p>{ format!("{} - {}", mission.name(squadmate), reason) }</p> }
+        });
+        let survivors =
+            outcome.survivors.iter().map(|&squadmate| mission.name(squadmate)).collect::<Vec<_>>();
+        let crew = match outcome.crew {
+            CrewSurvivors::All => "The whole crew survived",
+            CrewSurvivors::Half => "Half of the crew died, including Kelly",
+            CrewSurvivors::OnlyChakwas => "Only Dr. Chakwas survived",
+        };
+
+        let write = if outcome.shepard_survives {
+            html! {
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Write)}>
+                        {"Write the outcome to the save"}
+                    </button>
+                </div>
+            }
+        } else {
+            html! { <p>{ "Shepard dies, less than 2 squadmates survived" }</p> }
+        };
+        let status = self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+
+        html! {
+            <Table title="Outcome">
+                <p>{ format!("Survivors - {}", survivors.join(", ")) }</p>
+                { for dead }
+                <p>{ crew }</p>
+                { write }
+                { for status }
+            </Table>
+        }
+    }
+}
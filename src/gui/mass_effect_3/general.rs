@@ -6,10 +6,10 @@ use crate::{
     gui::{
         components::{Helper, InputText, Select, Table},
         raw_ui::RawUi,
-        shared::{BonusPowerType, BonusPowers},
+        shared::{BonusPowerType, BonusPowers, SquadClipboard},
     },
     save_data::{
-        mass_effect_3::{player::Player, plot::PlotTable, Me3SaveGame},
+        mass_effect_3::{player::Player, plot::PlotTable, squad::Henchman, Me3SaveGame},
         shared::player::{Notoriety, Origin},
         RcRef,
     },
@@ -236,6 +236,7 @@ impl Component for Me3General {
                 <div class="flex-1 pl-1 flex flex-col gap-1">
                     { Self::general(&save_game) }
                     { Self::bonus_powers(save_game.player()) }
+                    <SquadClipboard<Henchman> squad={RcRef::clone(&save_game.squad)} />
                 </div>
             </div>
         }
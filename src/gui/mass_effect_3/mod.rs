@@ -1,9 +1,13 @@
 mod general;
 mod plot;
+mod plot_checker;
+mod plot_presets;
 mod plot_variable;
 mod raw_plot;
 
-pub use self::{general::*, plot::*, plot_variable::*, raw_plot::*};
+pub use self::{
+    general::*, plot::*, plot_checker::*, plot_presets::*, plot_variable::*, raw_plot::*,
+};
 
 use yew::prelude::*;
 
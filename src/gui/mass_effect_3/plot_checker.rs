@@ -0,0 +1,44 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::{
+    gui::shared::{FloatPlotType, IntPlotType, PlotChecker},
+    save_data::{shared::plot::BitVec, RcRef},
+    services::database::Databases,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+    pub integers: IntPlotType,
+    pub floats: FloatPlotType,
+    pub is_female: bool,
+    pub level: i32,
+}
+
+#[function_component(Me3PlotChecker)]
+pub fn me3_plot_checker(props: &Props) -> Html {
+    let dbs = use_context::<Databases>().expect("no database provider");
+    match dbs.get_me3_plot_rules() {
+        Some(rules) => {
+            let Props { booleans, integers, floats, is_female, level } = props;
+            html! {
+                <PlotChecker
+                    booleans={RcRef::clone(booleans)}
+                    integers={integers.clone()}
+                    floats={floats.clone()}
+                    is_female={*is_female}
+                    level={*level}
+                    rules={Rc::clone(&rules)}
+                />
+            }
+        }
+        None => html! {
+            <>
+                <p>{ "Loading database..." }</p>
+                <hr class="border-t border-default-border" />
+            </>
+        },
+    }
+}
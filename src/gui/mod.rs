@@ -1,5 +1,6 @@
 mod app;
 pub mod components;
+pub mod i18n;
 mod mass_effect_1;
 mod mass_effect_1_le;
 mod mass_effect_2;
@@ -7,6 +7,7 @@ use crate::{
     gui::components::{raw_ui::*, *},
     save_data::{
         mass_effect_1_le::legacy::BaseObject, shared::appearance::LinearColor, Guid, RcCell, RcRef,
+        StringRef,
     },
 };
 
@@ -114,6 +115,14 @@ impl RawUi for RcRef<Guid> {
     }
 }
 
+impl RawUi for RcRef<StringRef> {
+    fn view(&self, label: &str) -> yew::Html {
+        html! {
+            <RawUiStringRef label={label.to_owned()} string_ref={RcRef::clone(self)} />
+        }
+    }
+}
+
 impl RawUi for RcRef<LinearColor> {
     fn view(&self, label: &str) -> yew::Html {
         html! {
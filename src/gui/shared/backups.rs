@@ -0,0 +1,279 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::Context as AnyhowContext;
+use js_sys::Date;
+use wasm_bindgen::JsValue;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{InputNumber, NumberType},
+        format_code,
+    },
+    save_data::RcCell,
+    services::{
+        rpc::{self, Backup, BackupSettings, RestoreBackupParams, RpcFile, Settings},
+        save_handler::{Action, SaveGame, SaveHandler, SaveHandlerProvider},
+        save_library,
+    },
+};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Refresh,
+    Listed(Result<Vec<Backup>, String>),
+    Loaded(PathBuf, Result<SaveGame, String>),
+    ApplySettings,
+    Restore(PathBuf),
+    Done(Result<String, String>),
+    Close,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub opened: bool,
+    pub onclose: Callback<()>,
+}
+
+pub struct SaveBackups {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    backups: Vec<Backup>,
+    diffs: HashMap<PathBuf, Result<Vec<String>, String>>,
+    retention: RcCell<i32>,
+    max_size_mb: RcCell<i32>,
+    status: Option<String>,
+}
+
+impl Component for SaveBackups {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        if ctx.props().opened {
+            ctx.link().send_message(Msg::Refresh);
+        }
+
+        SaveBackups {
+            _save_handle,
+            save_handler,
+            backups: Vec::new(),
+            diffs: HashMap::new(),
+            retention: Default::default(),
+            max_size_mb: Default::default(),
+            status: None,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if ctx.props().opened {
+            ctx.link().send_message(Msg::Refresh);
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                let is_same_save = self.save_handler.is_same_save(&save_handler);
+                self.save_handler = save_handler;
+                // The diffs are relative to the current save
+                if ctx.props().opened && !is_same_save {
+                    ctx.link().send_message(Msg::Refresh);
+                }
+                false
+            }
+            Msg::Refresh => {
+                let path = match self.save_handler.save_game {
+                    Some(ref save_game) => save_game.file_path().to_owned(),
+                    None => return false,
+                };
+                ctx.link().send_future(async move {
+                    let list = rpc::list_backups(path).await.context("Failed to list the backups");
+                    Msg::Listed(list.map_err(|err| format!("{:#}", err)))
+                });
+                false
+            }
+            Msg::Listed(Ok(backups)) => {
+                let BackupSettings { retention, max_size_mb } = self.save_handler.settings.backup;
+                self.retention.set(retention as i32);
+                self.max_size_mb.set(max_size_mb as i32);
+
+                self.diffs.clear();
+                for backup in &backups {
+                    let path = backup.path.clone();
+                    ctx.link().send_future(async move {
+                        let load = async {
+                            let RpcFile { path, file } = rpc::reload_save(path.clone()).await?;
+                            SaveHandlerProvider::deserialize(path, file.decode()?)
+                        };
+                        let save_game = load.await.map_err(|err| format!("{:#}", err));
+                        Msg::Loaded(path, save_game)
+                    });
+                }
+                self.backups = backups;
+                true
+            }
+            Msg::Listed(Err(err)) => {
+                self.status = Some(err);
+                true
+            }
+            Msg::Loaded(path, save_game) => {
+                let diff = match (save_game, self.save_handler.save_game.as_deref()) {
+                    (Ok(backup), Some(current)) => Ok(save_library::diff(current, &backup)),
+                    (Ok(_), None) => Ok(Vec::new()),
+                    (Err(err), _) => Err(err),
+                };
+                self.diffs.insert(path, diff);
+                true
+            }
+            Msg::ApplySettings => {
+                let backup = BackupSettings {
+                    retention: self.retention.get().max(1) as usize,
+                    max_size_mb: self.max_size_mb.get().max(1) as u64,
+                };
+                let settings = Settings { backup, ..(*self.save_handler.settings).clone() };
+                self.save_handler.action(Action::SetSettings(settings));
+                false
+            }
+            Msg::Restore(backup) => {
+                if let Some(ref save_game) = self.save_handler.save_game {
+                    let path = save_game.file_path().to_owned();
+                    let save_handler = self.save_handler.clone();
+                    ctx.link().send_future(async move {
+                        let params = RestoreBackupParams { backup, path: path.clone() };
+                        let result = rpc::restore_backup(params)
+                            .await
+                            .map(|_| {
+                                save_handler.action(Action::OpenSaveAt(path));
+                                String::from(
+                                    "Backup restored, the replaced save has been backed up",
+                                )
+                            })
+                            .context("Failed to restore the backup");
+                        Msg::Done(result.map_err(|err| format!("{:#}", err)))
+                    });
+                }
+                false
+            }
+            Msg::Done(result) => {
+                self.status = Some(match result {
+                    Ok(status) => status,
+                    Err(err) => err,
+                });
+                ctx.link().send_message(Msg::Refresh);
+                true
+            }
+            Msg::Close => {
+                ctx.props().onclose.emit(());
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if !ctx.props().opened {
+            return Html::default();
+        }
+
+        let link = ctx.link();
+        let status = self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+        let backups = if !self.backups.is_empty() {
+            self.backups.iter().map(|backup| self.view_backup(ctx, backup)).collect::<Html>()
+        } else {
+            html! { <p>{ "No backup of this save yet" }</p> }
+        };
+
+        html! {
+            <div class={classes![
+                "absolute",
+                "top-8",
+                "left-1/2",
+                "-translate-x-1/2",
+                "w-2/3",
+                "flex",
+                "flex-col",
+                "gap-1",
+                "border",
+                "border-default-border",
+                "bg-default-bg",
+                "p-1",
+                "z-40",
+            ]}>
+                <div class="flex items-center gap-2">
+                    <span class="flex-auto">{ "Backups" }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Close)}>
+                        {"Close"}
+                    </button>
+                </div>
+                <hr class="border-t border-default-border" />
+                <div class="flex items-center gap-2">
+                    <InputNumber
+                        label="Versions kept"
+                        value={NumberType::Int(RcCell::clone(&self.retention))}
+                    />
+                    <InputNumber
+                        label="Size cap (MB)"
+                        value={NumberType::Int(RcCell::clone(&self.max_size_mb))}
+                        helper="Total size of the backups of a save, the most recent one is always kept"
+                    />
+                    <button class="button" onclick={link.callback(|_| Msg::ApplySettings)}>
+                        {"Apply"}
+                    </button>
+                </div>
+                { for status }
+                <hr class="border-t border-default-border" />
+                <div class="flex flex-col gap-1 max-h-[70vh] overflow-y-auto">
+                    { backups }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl SaveBackups {
+    fn view_backup(&self, ctx: &Context<Self>, backup: &Backup) -> Html {
+        let diff = match self.diffs.get(&backup.path) {
+            Some(Ok(changes)) if changes.is_empty() => {
+                html! { <p>{ "Same as the current save" }</p> }
+            }
+            Some(Ok(changes)) => {
+                changes.iter().map(|change| html! { <p>{ change }</p> }).collect::<Html>()
+            }
+            Some(Err(err)) => html! { <p>{ format_code(err) }</p> },
+            None => html! { <p>{ "Loading..." }</p> },
+        };
+
+        let path = backup.path.clone();
+        html! {
+            <div class="flex items-start gap-2 px-1 hover:bg-theme-hover">
+                <button class="button" onclick={ctx.link().callback(move |_| Msg::Restore(path.clone()))}>
+                    {"Restore"}
+                </button>
+                <span class="w-40">{ format_timestamp(backup.timestamp) }</span>
+                <span class="w-20">{ format!("{} KB", backup.size / 1024) }</span>
+                <div class="flex flex-col">
+                    { diff }
+                </div>
+            </div>
+        }
+    }
+}
+
+fn format_timestamp(millis: u64) -> String {
+    let date = Date::new(&JsValue::from_f64(millis as f64));
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date(),
+        date.get_hours(),
+        date.get_minutes(),
+        date.get_seconds(),
+    )
+}
@@ -0,0 +1,365 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context as AnyhowContext, Result};
+use web_sys::HtmlTextAreaElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{InputText, Select},
+        format_code,
+    },
+    save_data::{shared::plot::PlotPreset, RcRef},
+    services::{
+        batch::{self, Assignment, BatchOperation, BatchResult},
+        rpc,
+        save_handler::{Action, SaveHandler},
+    },
+};
+
+const OPERATIONS: &[&str] = &["Plot preset", "Script", "Field assignments"];
+
+pub enum Msg {
+    AddFiles,
+    AddPattern,
+    FilesAdded(Result<Vec<PathBuf>, String>),
+    RemoveFile(usize),
+    ClearFiles,
+    SelectOperation(usize),
+    ImportPreset,
+    PresetImported(Box<PlotPreset>),
+    OpenScript,
+    ScriptOpened(String),
+    ScriptInput(InputEvent),
+    AssignmentsInput(InputEvent),
+    Run,
+    Done(Vec<BatchResult>),
+    Close,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub opened: bool,
+    pub onclose: Callback<()>,
+}
+
+/// Applies a preset, a script or field assignments to many saves, each one is backed up
+pub struct BatchEdit {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    paths: Vec<PathBuf>,
+    pattern: RcRef<String>,
+    operation_idx: usize,
+    preset: Option<PlotPreset>,
+    script: String,
+    assignments: String,
+    running: bool,
+    results: Vec<BatchResult>,
+    status: Option<String>,
+}
+
+impl Component for BatchEdit {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) =
+            ctx.link().context::<SaveHandler>(Callback::noop()).expect("no save handler provider");
+
+        BatchEdit {
+            _save_handle,
+            save_handler,
+            paths: Vec::new(),
+            pattern: Default::default(),
+            operation_idx: 0,
+            preset: None,
+            script: String::new(),
+            assignments: String::new(),
+            running: false,
+            results: Vec::new(),
+            status: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::AddFiles => {
+                ctx.link().send_future(async {
+                    let paths = rpc::open_saves_dialog().await.context("Failed to add the saves");
+                    Msg::FilesAdded(paths.map_err(|err| format!("{:#}", err)))
+                });
+                false
+            }
+            Msg::AddPattern => {
+                let pattern = self.pattern.borrow().trim().to_owned();
+                if !pattern.is_empty() {
+                    ctx.link().send_future(async {
+                        let paths = rpc::expand_batch_paths(vec![pattern])
+                            .await
+                            .context("Failed to add the saves");
+                        Msg::FilesAdded(paths.map_err(|err| format!("{:#}", err)))
+                    });
+                }
+                false
+            }
+            Msg::FilesAdded(Ok(paths)) => {
+                for path in paths {
+                    if !self.paths.contains(&path) {
+                        self.paths.push(path);
+                    }
+                }
+                self.status = None;
+                true
+            }
+            Msg::FilesAdded(Err(err)) => {
+                self.status = Some(err);
+                true
+            }
+            Msg::RemoveFile(idx) => {
+                self.paths.remove(idx);
+                true
+            }
+            Msg::ClearFiles => {
+                self.paths.clear();
+                true
+            }
+            Msg::SelectOperation(idx) => {
+                self.operation_idx = idx;
+                true
+            }
+            Msg::ImportPreset => {
+                let callback = ctx.link().callback(|preset| Msg::PresetImported(Box::new(preset)));
+                self.save_handler.action(Action::ImportPlotPreset(callback));
+                false
+            }
+            Msg::PresetImported(preset) => {
+                self.preset = Some(*preset);
+                true
+            }
+            Msg::OpenScript => {
+                self.save_handler
+                    .action(Action::OpenScript(ctx.link().callback(Msg::ScriptOpened)));
+                false
+            }
+            Msg::ScriptOpened(script) => {
+                self.script = script;
+                true
+            }
+            Msg::ScriptInput(event) => {
+                if let Some(textarea) = event.target_dyn_into::<HtmlTextAreaElement>() {
+                    self.script = textarea.value();
+                }
+                false
+            }
+            Msg::AssignmentsInput(event) => {
+                if let Some(textarea) = event.target_dyn_into::<HtmlTextAreaElement>() {
+                    self.assignments = textarea.value();
+                }
+                false
+            }
+            Msg::Run => {
+                match self.operation() {
+                    Ok(operation) => {
+                        let paths = self.paths.clone();
+                        ctx.link().send_future(async move {
+                            Msg::Done(batch::run(paths, &operation).await)
+                        });
+                        self.running = true;
+                        self.status = None;
+                        self.results.clear();
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                true
+            }
+            Msg::Done(results) => {
+                let failed = results.iter().filter(|result| result.result.is_err()).count();
+                let mut status =
+                    format!("{} save(s) edited, {} failed", results.len() - failed, failed);
+
+                // The open save is now older than the one on disk
+                let is_open_save_edited = self.save_handler.save_game.as_ref().map(|save_game| {
+                    results
+                        .iter()
+                        .any(|result| result.result.is_ok() && result.path == save_game.file_path())
+                });
+                if is_open_save_edited.unwrap_or_default() {
+                    status.push_str(
+                        ", the open save has been edited, `Reload` it to see the changes",
+                    );
+                }
+
+                self.status = Some(status);
+                self.results = results;
+                self.running = false;
+                true
+            }
+            Msg::Close => {
+                ctx.props().onclose.emit(());
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if !ctx.props().opened {
+            return Html::default();
+        }
+
+        let link = ctx.link();
+        let files = self.paths.iter().enumerate().map(|(idx, path)| {
+            html! {
+                <div class="flex items-center gap-2 px-1 hover:bg-theme-hover">
+                    <button class="button" onclick={link.callback(move |_| Msg::RemoveFile(idx))}>
+                        {"Remove"}
+                    </button>
+                    <span>{ path.display().to_string() }</span>
+                </div>
+            }
+        });
+        let status = self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+        let results = self.results.iter().map(|BatchResult { path, result }| {
+            let result = match result {
+                Ok(done) => format!("OK - {}", done),
+                Err(err) => format!("Failed - {}", err),
+            };
+            html! { <p>{ format_code(format!("`{}` {}", path.display(), result)) }</p> }
+        });
+        let run = if self.running {
+            html! { <span>{ "Running..." }</span> }
+        } else {
+            html! {
+                <button class="button" onclick={link.callback(|_| Msg::Run)}>
+                    {"Run"}
+                </button>
+            }
+        };
+
+        html! {
+            <div class={classes![
+                "absolute",
+                "top-8",
+                "left-1/2",
+                "-translate-x-1/2",
+                "w-2/3",
+                "flex",
+                "flex-col",
+                "gap-1",
+                "border",
+                "border-default-border",
+                "bg-default-bg",
+                "p-1",
+                "z-40",
+            ]}>
+                <div class="flex items-center gap-2">
+                    <span class="flex-auto">{ "Batch edit" }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Close)}>
+                        {"Close"}
+                    </button>
+                </div>
+                <hr class="border-t border-default-border" />
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(|_| Msg::AddFiles)}>
+                        {"Add saves"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={link.callback(|_| Msg::ClearFiles)}>
+                        {"Clear"}
+                    </button>
+                </div>
+                <div class="flex items-center gap-2">
+                    <InputText label="Glob pattern" value={RcRef::clone(&self.pattern)}
+                        helper="Like `C:\\Saves\\**\\*.pcsav`"
+                    />
+                    <button class="button" onclick={link.callback(|_| Msg::AddPattern)}>
+                        {"Add"}
+                    </button>
+                </div>
+                <div class="flex flex-col max-h-[25vh] overflow-y-auto">
+                    { for files }
+                </div>
+                <hr class="border-t border-default-border" />
+                <div class="flex items-center gap-2">
+                    <Select
+                        options={OPERATIONS}
+                        current_idx={self.operation_idx}
+                        onselect={link.callback(Msg::SelectOperation)}
+                    />
+                    { run }
+                </div>
+                { self.view_operation(ctx) }
+                <hr class="border-t border-default-border" />
+                { for status }
+                <div class="flex flex-col max-h-[30vh] overflow-y-auto">
+                    { for results }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl BatchEdit {
+    fn view_operation(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        match self.operation_idx {
+            0 => {
+                let preset = match self.preset {
+                    Some(ref preset) => format!("`{}`", preset.name),
+                    None => String::from("No preset imported"),
+                };
+                html! {
+                    <div class="flex items-center gap-2">
+                        <button class="button" onclick={link.callback(|_| Msg::ImportPreset)}>
+                            {"Import"}
+                        </button>
+                        <span>{ format_code(preset) }</span>
+                    </div>
+                }
+            }
+            1 => html! {
+                <>
+                    <div class="flex items-center gap-2">
+                        <button class="button" onclick={link.callback(|_| Msg::OpenScript)}>
+                            {"Open"}
+                        </button>
+                        <span>{ format_code("The save is available as `save`, like in the script console") }</span>
+                    </div>
+                    <textarea
+                        class="input min-h-[100px] font-mono resize-none"
+                        spellcheck="false"
+                        value={self.script.clone()}
+                        oninput={link.callback(Msg::ScriptInput)}
+                    />
+                </>
+            },
+            _ => html! {
+                <>
+                    <p>{ format_code("One `field = value` per line, like `player.credits = 1000000` or `plot.bool[42] = true`. Float values need a decimal point.") }</p>
+                    <textarea
+                        class="input min-h-[100px] font-mono resize-none"
+                        spellcheck="false"
+                        placeholder="player.credits = 1000000"
+                        value={self.assignments.clone()}
+                        oninput={link.callback(Msg::AssignmentsInput)}
+                    />
+                </>
+            },
+        }
+    }
+
+    fn operation(&self) -> Result<BatchOperation> {
+        if self.paths.is_empty() {
+            bail!("No save to edit");
+        }
+
+        match self.operation_idx {
+            0 => match self.preset {
+                Some(ref preset) => Ok(BatchOperation::Preset(Box::new(preset.clone()))),
+                None => bail!("No preset imported"),
+            },
+            1 if self.script.trim().is_empty() => bail!("The script is empty"),
+            1 => Ok(BatchOperation::Script(self.script.clone())),
+            _ => Assignment::parse(&self.assignments).map(BatchOperation::Assignments),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use gloo::{events::EventListener, timers::future::TimeoutFuture, utils};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlInputElement, KeyboardEvent};
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::components::navigate,
+    services::{
+        database::Databases,
+        save_handler::SaveHandler,
+        search::{self, SearchEntry},
+    },
+};
+
+const MAX_RESULTS: usize = 100;
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Databases(Databases),
+    KeyDown(KeyboardEvent),
+    Input(InputEvent),
+    Jump(usize),
+    Highlight(String),
+    Close,
+    Noop,
+}
+
+pub struct GlobalSearch {
+    _save_handle: ContextHandle<SaveHandler>,
+    _db_handle: ContextHandle<Databases>,
+    _keydown_listener: EventListener,
+    save_handler: SaveHandler,
+    dbs: Databases,
+    input_ref: NodeRef,
+    opened: bool,
+    query: String,
+    index: Vec<SearchEntry>,
+    results: Vec<usize>,
+}
+
+impl Component for GlobalSearch {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+        let (dbs, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::Databases))
+            .expect("no database provider");
+
+        let _keydown_listener = {
+            let link = ctx.link().clone();
+            EventListener::new(&utils::window(), "keydown", move |event| {
+                if let Some(event) = event.dyn_ref::<KeyboardEvent>() {
+                    link.send_message(Msg::KeyDown(event.clone()));
+                }
+            })
+        };
+
+        GlobalSearch {
+            _save_handle,
+            _db_handle,
+            _keydown_listener,
+            save_handler,
+            dbs,
+            input_ref: Default::default(),
+            opened: false,
+            query: String::new(),
+            index: Vec::new(),
+            results: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                let is_same_save = self.save_handler.is_same_save(&save_handler);
+                self.save_handler = save_handler;
+                if is_same_save {
+                    return false;
+                }
+                self.opened = false;
+                true
+            }
+            Msg::Databases(dbs) => {
+                self.dbs = dbs;
+                // A database finished loading while searching
+                if self.opened {
+                    self.update_index();
+                    self.update_results();
+                    return true;
+                }
+                false
+            }
+            Msg::KeyDown(event) => {
+                let is_ctrl_f =
+                    (event.ctrl_key() || event.meta_key()) && event.key().eq_ignore_ascii_case("f");
+                if is_ctrl_f && self.save_handler.save_game.is_some() {
+                    event.prevent_default();
+                    if !self.opened {
+                        self.opened = true;
+                        self.update_index();
+                        self.update_results();
+                    }
+                    true
+                } else if event.key() == "Escape" && self.opened {
+                    self.opened = false;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::Input(event) => {
+                if let Some(input) = event.target_dyn_into::<HtmlInputElement>() {
+                    self.query = input.value();
+                    self.update_results();
+                }
+                true
+            }
+            Msg::Jump(idx) => {
+                if let Some(entry) = self.index.get(idx) {
+                    let path = entry.path.clone();
+                    ctx.link().send_future(async move {
+                        navigate(&path).await;
+                        // Let the tables render their rows
+                        TimeoutFuture::new(200).await;
+                        Msg::Highlight(path.last().cloned().unwrap_or_default())
+                    });
+                }
+                self.opened = false;
+                true
+            }
+            Msg::Highlight(label) => {
+                if let Some(element) = Self::find_control(&label) {
+                    element.scroll_into_view();
+                    let _ = element.class_list().add_1("search-highlight");
+                    ctx.link().send_future(async move {
+                        TimeoutFuture::new(2000).await;
+                        let _ = element.class_list().remove_1("search-highlight");
+                        Msg::Noop
+                    });
+                }
+                false
+            }
+            Msg::Close => {
+                self.opened = false;
+                true
+            }
+            Msg::Noop => false,
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if let Some(input) = self.input_ref.cast::<HtmlInputElement>() {
+            let _ = input.focus();
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if !self.opened {
+            return Html::default();
+        }
+
+        let link = ctx.link();
+        let results = self.results.iter().map(|&idx| {
+            let entry = &self.index[idx];
+            html! {
+                <a class="px-1 hover:bg-theme-hover active:bg-theme-active cursor-pointer truncate"
+                    onclick={link.callback(move |_| Msg::Jump(idx))}
+                >
+                    { entry.to_string() }
+                </a>
+            }
+        });
+        let more = (self.results.len() == MAX_RESULTS).then(|| {
+            html! { <p class="px-1">{ format!("Only the first {} results are displayed", MAX_RESULTS) }</p> }
+        });
+
+        html! {
+            <div class={classes![
+                "absolute",
+                "top-8",
+                "left-1/2",
+                "-translate-x-1/2",
+                "w-1/2",
+                "flex",
+                "flex-col",
+                "gap-1",
+                "border",
+                "border-default-border",
+                "bg-default-bg",
+                "p-1",
+                "z-40",
+            ]}>
+                <div class="flex items-center gap-1">
+                    <input type="text" class="flex-auto input" placeholder="Search plots, items and raw data"
+                        value={self.query.clone()}
+                        oninput={link.callback(Msg::Input)}
+                        ref={self.input_ref.clone()}
+                    />
+                    <button class="button" onclick={link.callback(|_| Msg::Close)}>
+                        {"Close"}
+                    </button>
+                </div>
+                <div class="flex flex-col max-h-96 overflow-y-auto">
+                    { for results }
+                    { for more }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl GlobalSearch {
+    fn update_index(&mut self) {
+        self.index = match self.save_handler.save_game {
+            Some(ref save_game) => search::build_index(save_game, &self.dbs),
+            None => Vec::new(),
+        };
+    }
+
+    fn update_results(&mut self) {
+        let query = self.query.trim().to_lowercase();
+        self.results = if !query.is_empty() {
+            self.index
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.matches(&query))
+                .map(|(idx, _)| idx)
+                .take(MAX_RESULTS)
+                .collect()
+        } else {
+            Vec::new()
+        };
+    }
+
+    fn find_control(label: &str) -> Option<Element> {
+        let controls =
+            utils::document().query_selector_all("section label, section button").ok()?;
+        let controls: Vec<Element> = (0..controls.length())
+            .filter_map(|idx| controls.get(idx))
+            .filter_map(|node| node.dyn_into::<Element>().ok())
+            .collect();
+
+        let text = |element: &Element| element.text_content().unwrap_or_default();
+        // Some labels are followed by a helper
+        controls
+            .iter()
+            .find(|element| text(element).trim() == label)
+            .or_else(|| controls.iter().find(|element| text(element).trim().starts_with(label)))
+            .cloned()
+    }
+}
@@ -3,15 +3,18 @@ use std::cell::{Ref, RefMut};
 use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
-    gui::{components::Table, raw_ui::RawUiChildren},
+    gui::{components::Table, i18n::tr, raw_ui::RawUiChildren},
     save_data::{shared::appearance::HeadMorph as DataHeadMorph, RcRef},
     services::save_handler::{Action, SaveHandler},
 };
 
 pub enum Msg {
+    SaveHandler(SaveHandler),
     Import,
     HeadMorphImported(DataHeadMorph),
     Export,
+    Copy,
+    Paste,
     RemoveHeadMorph,
 }
 
@@ -40,14 +43,23 @@ impl Component for HeadMorph {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let (save_handler, _db_handle) =
-            ctx.link().context::<SaveHandler>(Callback::noop()).expect("no save handler provider");
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
 
         HeadMorph { _db_handle, save_handler }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
+            Msg::SaveHandler(save_handler) => {
+                // Only the clipboard is shown
+                let can_paste = save_handler.can_paste::<DataHeadMorph>();
+                let changed = can_paste != self.save_handler.can_paste::<DataHeadMorph>();
+                self.save_handler = save_handler;
+                changed
+            }
             Msg::Import => {
                 let callback = ctx.link().callback(Msg::HeadMorphImported);
                 self.save_handler.action(Action::ImportHeadMorph(callback));
@@ -63,6 +75,19 @@ impl Component for HeadMorph {
                 }
                 false
             }
+            Msg::Copy => {
+                if let Some(ref head_morph) = *ctx.props().head_morph() {
+                    self.save_handler.copy("Head morph", &*head_morph.borrow());
+                }
+                false
+            }
+            Msg::Paste => match self.save_handler.paste() {
+                Some(head_morph) => {
+                    *ctx.props().head_morph_mut() = Some(RcRef::new(head_morph));
+                    true
+                }
+                None => false,
+            },
             Msg::RemoveHeadMorph => {
                 ctx.props().head_morph_mut().take();
                 true
@@ -76,15 +101,26 @@ impl Component for HeadMorph {
             html! {
                 <>
                     <button class="button" onclick={ctx.link().callback(|_| Msg::Export)}>
-                        {"Export"}
+                        { tr("Export") }
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::Copy)}>
+                        { tr("Copy") }
                     </button>
                     <span>{"-"}</span>
                     <button class="button" onclick={ctx.link().callback(|_| Msg::RemoveHeadMorph)}>
-                        {"Remove head morph"}
+                        { tr("Remove head morph") }
                     </button>
                 </>
             }
         });
+        let paste = self.save_handler.can_paste::<DataHeadMorph>().then(|| {
+            html! {
+                <button class="button" onclick={ctx.link().callback(|_| Msg::Paste)}>
+                    { tr("Paste") }
+                </button>
+            }
+        });
         let raw = head_morph.as_ref().map(|head_morph| {
             html! {
                 <Table title="Raw">
@@ -96,8 +132,9 @@ impl Component for HeadMorph {
             <div class="flex-auto flex flex-col gap-1">
                 <div class="flex items-center gap-2">
                     <button class="button" onclick={ctx.link().callback(|_| Msg::Import)}>
-                        {"Import"}
+                        { tr("Import") }
                     </button>
+                    { for paste }
                     { for export_remove }
                 </div>
                 <hr class="border-t border-default-border" />
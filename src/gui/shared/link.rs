@@ -32,8 +32,8 @@ impl Component for Link {
                 // let history = window.history().expect("no history");
                 // history.push_state(&main_tab, "").expect("push history");
 
-                let mut state = PopStateEventInit::new();
-                state.state(&main_tab);
+                let state = PopStateEventInit::new();
+                state.set_state(&main_tab);
                 if let Ok(event) = PopStateEvent::new_with_event_init_dict("popstate", &state) {
                     let _ = window.dispatch_event(&event);
                 }
@@ -1,16 +1,33 @@
+mod backups;
+mod batch_edit;
 mod bonus_powers;
+mod global_search;
 mod head_morph;
 mod link;
 mod plot_category;
+mod plot_checker;
+mod plot_presets;
 mod raw_plot;
+mod save_library;
+mod save_watcher;
+mod script_console;
+mod settings;
+mod squad_clipboard;
 
-pub use self::{bonus_powers::*, head_morph::*, link::*, plot_category::*, raw_plot::*};
+pub use self::{
+    backups::*, batch_edit::*, bonus_powers::*, global_search::*, head_morph::*, link::*,
+    plot_category::*, plot_checker::*, plot_presets::*, raw_plot::*, save_library::*,
+    save_watcher::*, script_console::*, settings::*, squad_clipboard::*,
+};
+
+use std::cell::Ref;
 
+use anyhow::{bail, Context, Result};
 use indexmap::IndexMap;
 use yew::prelude::*;
 
 use crate::save_data::{
-    shared::plot::{BitVec, PlotTable},
+    shared::plot::{check_plot_id, plot_key, BitVec, PlotKind, PlotState, PlotTable},
     RcCell, RcRef,
 };
 
@@ -68,6 +85,217 @@ impl PartialEq for PlotType {
     }
 }
 
+/// A plot value parsed by `PlotType::parse_value`
+#[derive(Clone, Copy)]
+pub enum PlotValue {
+    Boolean(bool),
+    Int(i32),
+    Float(f32),
+}
+
+impl PlotType {
+    pub fn kind(&self) -> PlotKind {
+        match self {
+            PlotType::Boolean(_) => PlotKind::Boolean,
+            PlotType::Int(_) => PlotKind::Integer,
+            PlotType::Float(_) => PlotKind::Float,
+        }
+    }
+
+    /// Checks that the plot `id` can be added, it cannot be too far past the end of the table
+    pub fn check_id(&self, id: usize) -> Result<()> {
+        match self {
+            PlotType::Boolean(booleans) => check_plot_id(id, booleans.borrow().len()),
+            PlotType::Int(IntPlotType::Vec(vec)) => check_plot_id(id, vec.borrow().len()),
+            PlotType::Float(FloatPlotType::Vec(vec)) => check_plot_id(id, vec.borrow().len()),
+            PlotType::Int(IntPlotType::IndexMap(_))
+            | PlotType::Float(FloatPlotType::IndexMap(_)) => plot_key(id).map(drop),
+        }
+    }
+
+    /// Parses `value` according to the type of the plots
+    pub fn parse_value(&self, value: &str) -> Result<PlotValue> {
+        let value = match self {
+            PlotType::Boolean(_) => match value {
+                "1" | "true" => PlotValue::Boolean(true),
+                "0" | "false" => PlotValue::Boolean(false),
+                _ => bail!("Invalid boolean `{}`", value),
+            },
+            PlotType::Int(_) => PlotValue::Int(
+                value.parse().with_context(|| format!("Invalid integer `{}`", value))?,
+            ),
+            PlotType::Float(_) => PlotValue::Float(
+                value.parse().with_context(|| format!("Invalid float `{}`", value))?,
+            ),
+        };
+        Ok(value)
+    }
+
+    /// Sets the plot `id`, adding it if needed.
+    /// `value` is parsed according to the type of the plots.
+    pub fn set_plot(&self, id: usize, value: &str) -> Result<()> {
+        let value = self.parse_value(value)?;
+        self.set_value(id, value)
+    }
+
+    /// Sets the plot `id` to a value from `parse_value`, adding it if needed
+    pub fn set_value(&self, id: usize, value: PlotValue) -> Result<()> {
+        self.check_id(id)?;
+        match (self, value) {
+            (PlotType::Boolean(booleans), PlotValue::Boolean(value)) => {
+                let mut booleans = booleans.borrow_mut();
+                if id >= booleans.len() {
+                    booleans.resize(id + 1, false);
+                }
+                booleans.set(id, value);
+            }
+            (PlotType::Int(IntPlotType::Vec(vec)), PlotValue::Int(value)) => {
+                vec_set(vec, id, value)
+            }
+            (PlotType::Int(IntPlotType::IndexMap(index_map)), PlotValue::Int(value)) => {
+                index_map_set(index_map, id, value)?
+            }
+            (PlotType::Float(FloatPlotType::Vec(vec)), PlotValue::Float(value)) => {
+                vec_set(vec, id, value)
+            }
+            (PlotType::Float(FloatPlotType::IndexMap(index_map)), PlotValue::Float(value)) => {
+                index_map_set(index_map, id, value)?
+            }
+            _ => bail!("Invalid value type for the plot {}", id),
+        }
+        Ok(())
+    }
+
+    /// Resets the plot `id` to its default value, or removes it from an `IndexMap`
+    pub fn clear_plot(&self, id: usize) {
+        match self {
+            PlotType::Boolean(booleans) => {
+                if let Some(mut plot) = booleans.borrow_mut().get_mut(id) {
+                    *plot = false;
+                }
+            }
+            PlotType::Int(IntPlotType::Vec(vec)) => vec_clear(vec, id),
+            PlotType::Int(IntPlotType::IndexMap(index_map)) => {
+                if let Ok(key) = plot_key(id) {
+                    index_map.borrow_mut().shift_remove(&key);
+                }
+            }
+            PlotType::Float(FloatPlotType::Vec(vec)) => vec_clear(vec, id),
+            PlotType::Float(FloatPlotType::IndexMap(index_map)) => {
+                if let Ok(key) = plot_key(id) {
+                    index_map.borrow_mut().shift_remove(&key);
+                }
+            }
+        }
+    }
+
+    /// Value of the plot `id` in the format of `set_plot`
+    pub fn plot_to_string(&self, id: usize) -> Option<String> {
+        match self {
+            PlotType::Boolean(booleans) => {
+                booleans.borrow().get(id).map(|plot| (*plot as u8).to_string())
+            }
+            PlotType::Int(IntPlotType::Vec(vec)) => vec.borrow().get(id).map(RcCell::to_string),
+            PlotType::Int(IntPlotType::IndexMap(index_map)) => plot_key(id)
+                .ok()
+                .and_then(|key| index_map.borrow().get(&key).map(RcCell::to_string)),
+            PlotType::Float(FloatPlotType::Vec(vec)) => vec.borrow().get(id).map(RcCell::to_string),
+            PlotType::Float(FloatPlotType::IndexMap(index_map)) => plot_key(id)
+                .ok()
+                .and_then(|key| index_map.borrow().get(&key).map(RcCell::to_string)),
+        }
+    }
+
+    /// Removes the unset plots at the end of a `Vec`, or every unset plot of an `IndexMap`.
+    /// Returns the number of removed plots.
+    pub fn trim(&self) -> usize {
+        match self {
+            PlotType::Boolean(booleans) => {
+                let mut booleans = booleans.borrow_mut();
+                let len = booleans.len();
+                let new_len = booleans.last_one().map(|idx| idx + 1).unwrap_or_default();
+                booleans.truncate(new_len);
+                len - new_len
+            }
+            PlotType::Int(IntPlotType::Vec(vec)) => vec_trim(vec),
+            PlotType::Int(IntPlotType::IndexMap(index_map)) => index_map_trim(index_map),
+            PlotType::Float(FloatPlotType::Vec(vec)) => vec_trim(vec),
+            PlotType::Float(FloatPlotType::IndexMap(index_map)) => index_map_trim(index_map),
+        }
+    }
+}
+
+/// The plots and player fields of the loaded save, as seen by the plot rules
+pub struct SavePlotState<'a> {
+    pub booleans: Ref<'a, BitVec>,
+    pub integers: &'a IntPlotType,
+    pub is_female: bool,
+    pub level: i32,
+}
+
+impl PlotState for SavePlotState<'_> {
+    fn boolean(&self, id: usize) -> bool {
+        self.booleans.get(id).map(|plot| *plot).unwrap_or_default()
+    }
+
+    fn integer(&self, id: usize) -> i32 {
+        match self.integers {
+            IntPlotType::Vec(vec) => vec.borrow().get(id).map(RcCell::get).unwrap_or_default(),
+            IntPlotType::IndexMap(index_map) => plot_key(id)
+                .ok()
+                .and_then(|key| index_map.borrow().get(&key).map(RcCell::get))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_female(&self) -> bool {
+        self.is_female
+    }
+
+    fn level(&self) -> i32 {
+        self.level
+    }
+}
+
+fn vec_set<T: Copy + Default>(vec: &RcRef<Vec<RcCell<T>>>, id: usize, value: T) {
+    let mut vec = vec.borrow_mut();
+    if id >= vec.len() {
+        vec.resize_with(id + 1, Default::default);
+    }
+    vec[id].set(value);
+}
+
+fn vec_clear<T: Copy + Default>(vec: &RcRef<Vec<RcCell<T>>>, id: usize) {
+    if let Some(plot) = vec.borrow().get(id) {
+        plot.set(T::default());
+    }
+}
+
+fn vec_trim<T: Copy + Default + PartialEq>(vec: &RcRef<Vec<RcCell<T>>>) -> usize {
+    let mut vec = vec.borrow_mut();
+    let len = vec.len();
+    while vec.last().map(|plot| plot.get() == T::default()).unwrap_or_default() {
+        vec.pop();
+    }
+    len - vec.len()
+}
+
+fn index_map_set<T: Copy + Default>(
+    index_map: &RcRef<IndexMap<i32, RcCell<T>>>, id: usize, value: T,
+) -> Result<()> {
+    index_map.borrow_mut().entry(plot_key(id)?).or_default().set(value);
+    Ok(())
+}
+
+fn index_map_trim<T: Copy + Default + PartialEq>(
+    index_map: &RcRef<IndexMap<i32, RcCell<T>>>,
+) -> usize {
+    let mut index_map = index_map.borrow_mut();
+    let len = index_map.len();
+    index_map.retain(|_, plot| plot.get() != T::default());
+    len - index_map.len()
+}
+
 impl RawUi for RcRef<PlotTable> {
     fn view(&self, _: &str) -> yew::Html {
         html! {
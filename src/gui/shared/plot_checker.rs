@@ -0,0 +1,151 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::{
+    gui::{components::Table, format_code},
+    save_data::{
+        shared::plot::{BitVec, PlotRule, PlotRuleDb},
+        RcRef,
+    },
+};
+
+use super::{set_preset_values, FloatPlotType, IntPlotType, SavePlotState};
+
+pub enum Msg {
+    Check,
+    Fix(usize, usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub booleans: RcRef<BitVec>,
+    pub integers: IntPlotType,
+    pub floats: FloatPlotType,
+    pub is_female: bool,
+    pub level: i32,
+    pub rules: Rc<PlotRuleDb>,
+}
+
+pub struct PlotChecker {
+    violations: Vec<usize>,
+    status: Option<String>,
+}
+
+impl Component for PlotChecker {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        PlotChecker { violations: Self::check(ctx), status: None }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        self.violations = Self::check(ctx);
+        self.status = None;
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Check => {
+                self.violations = Self::check(ctx);
+                self.status = None;
+                true
+            }
+            Msg::Fix(rule_idx, fix_idx) => {
+                let Props { booleans, integers, floats, rules, .. } = ctx.props();
+                let rule = &rules.rules[rule_idx];
+                if let Some(fix) = rule.fixes.get(fix_idx) {
+                    self.status = Some(match set_preset_values(booleans, integers, floats, fix) {
+                        Ok(()) => format!("`{}` fixed with `{}`", rule.name, fix.name),
+                        Err(err) => format!("`{}` has not been fixed: {}", rule.name, err),
+                    });
+                }
+                self.violations = Self::check(ctx);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let rules = &ctx.props().rules.rules;
+
+        let status = self.status.as_ref().map(|status| {
+            html! {
+                <>
+                    <p>{ format_code(status) }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        });
+
+        let violations = if !self.violations.is_empty() {
+            self.violations
+                .iter()
+                .map(|&rule_idx| self.view_violation(ctx, rule_idx, &rules[rule_idx]))
+                .collect::<Html>()
+        } else {
+            html! { <p>{ "No inconsistency found" }</p> }
+        };
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(|_| Msg::Check)}>
+                        {"Check again"}
+                    </button>
+                    <span>{ format!("{} rule(s) violated", self.violations.len()) }</span>
+                </div>
+                <hr class="border-t border-default-border" />
+                { for status }
+                { violations }
+            </div>
+        }
+    }
+}
+
+impl PlotChecker {
+    fn check(ctx: &Context<Self>) -> Vec<usize> {
+        let Props { booleans, integers, is_female, level, rules, .. } = ctx.props();
+        let state = SavePlotState {
+            booleans: booleans.borrow(),
+            integers,
+            is_female: *is_female,
+            level: *level,
+        };
+
+        rules
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.is_violated(&state))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    fn view_violation(&self, ctx: &Context<Self>, rule_idx: usize, rule: &PlotRule) -> Html {
+        let link = ctx.link();
+        let description = (!rule.description.is_empty()).then(|| {
+            html! { <p>{ format_code(&rule.description) }</p> }
+        });
+
+        let fixes = rule.fixes.iter().enumerate().map(|(fix_idx, fix)| {
+            html! {
+                <button class="button" onclick={link.callback(move |_| Msg::Fix(rule_idx, fix_idx))}>
+                    { &fix.name }
+                </button>
+            }
+        });
+
+        html! {
+            <Table title={rule.name.clone()}>
+                { for description }
+                <div class="flex items-center gap-2">
+                    { for fixes }
+                </div>
+            </Table>
+        }
+    }
+}
@@ -0,0 +1,574 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Result};
+use indexmap::{map::Entry, IndexMap};
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::{InputText, Table},
+        format_code, Theme,
+    },
+    save_data::{
+        shared::plot::{
+            check_plot_id, parse_plot_ids, plot_key, BitVec, PlotCondition, PlotPreset,
+            PlotPresetDb, RawPlotDb,
+        },
+        RcCell, RcRef,
+    },
+    services::save_handler::{Action, SaveGame, SaveHandler},
+};
+
+use super::{FloatPlotType, IntPlotType, SavePlotState};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Apply(usize),
+    ApplyCustom,
+    Import,
+    Imported(Box<PlotPreset>),
+    Export,
+    Copy,
+    Paste,
+    CreateFromSelection,
+    Compare,
+    SaveToCompareOpened(SaveGame),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub theme: Theme,
+    pub booleans: RcRef<BitVec>,
+    pub integers: IntPlotType,
+    pub floats: FloatPlotType,
+    pub presets: Rc<PlotPresetDb>,
+    pub plot_db: Rc<RawPlotDb>,
+}
+
+pub struct PlotPresets {
+    _db_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    custom: Option<Rc<PlotPreset>>,
+    name: RcRef<String>,
+    boolean_ids: RcRef<String>,
+    integer_ids: RcRef<String>,
+    float_ids: RcRef<String>,
+    status: Option<String>,
+}
+
+impl Component for PlotPresets {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _db_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        PlotPresets {
+            _db_handle,
+            save_handler,
+            custom: None,
+            name: Default::default(),
+            boolean_ids: Default::default(),
+            integer_ids: Default::default(),
+            float_ids: Default::default(),
+            status: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                // Only the clipboard is shown
+                let can_paste = save_handler.can_paste::<PlotPreset>();
+                let changed = can_paste != self.save_handler.can_paste::<PlotPreset>();
+                self.save_handler = save_handler;
+                changed
+            }
+            Msg::Apply(idx) => {
+                if let Some(preset) = ctx.props().presets.presets.get(idx) {
+                    self.status = Some(Self::apply(ctx, preset));
+                }
+                true
+            }
+            Msg::ApplyCustom => {
+                if let Some(ref preset) = self.custom {
+                    self.status = Some(Self::apply(ctx, preset));
+                }
+                true
+            }
+            Msg::Import => {
+                let callback = ctx.link().callback(|preset| Msg::Imported(Box::new(preset)));
+                self.save_handler.action(Action::ImportPlotPreset(callback));
+                false
+            }
+            Msg::Imported(preset) => {
+                self.status = None;
+                self.custom = Some(preset.into());
+                true
+            }
+            Msg::Export => {
+                if let Some(ref preset) = self.custom {
+                    self.save_handler.action(Action::ExportPlotPreset(Rc::clone(preset)));
+                }
+                false
+            }
+            Msg::Copy => {
+                if let Some(ref preset) = self.custom {
+                    let label = format!("Plot preset {}", preset.name);
+                    self.save_handler.copy(label, preset.as_ref());
+                }
+                false
+            }
+            Msg::Paste => match self.save_handler.paste::<PlotPreset>() {
+                Some(preset) => {
+                    self.status = None;
+                    self.custom = Some(preset.into());
+                    true
+                }
+                None => false,
+            },
+            Msg::CreateFromSelection => {
+                match self.create_from_selection(ctx) {
+                    Ok(preset) => {
+                        self.status = None;
+                        self.custom = Some(preset.into());
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                true
+            }
+            Msg::Compare => {
+                let callback = ctx.link().callback(Msg::SaveToCompareOpened);
+                self.save_handler.action(Action::OpenSaveToCompare(callback));
+                false
+            }
+            Msg::SaveToCompareOpened(save_game) => {
+                match Self::create_from_diff(ctx, &save_game) {
+                    Ok(preset) => {
+                        self.status = None;
+                        self.custom = Some(preset.into());
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let status = self.status.as_ref().map(|status| {
+            html! {
+                <>
+                    <p>{ format_code(status) }</p>
+                    <hr class="border-t border-default-border" />
+                </>
+            }
+        });
+
+        let custom = self.custom.as_ref().map(|preset| {
+            let buttons = html! {
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(|_| Msg::ApplyCustom)}>
+                        {"Apply"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Export)}>
+                        {"Export"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Copy)}>
+                        {"Copy"}
+                    </button>
+                </div>
+            };
+            self.view_preset(ctx, preset, buttons, true)
+        });
+
+        // A preset or a plot range copied from another save
+        let paste = self.save_handler.can_paste::<PlotPreset>().then(|| {
+            html! {
+                <button class="button" onclick={link.callback(|_| Msg::Paste)}>
+                    {"Paste"}
+                </button>
+            }
+        });
+
+        let presets = ctx.props().presets.presets.iter().enumerate().map(|(idx, preset)| {
+            let buttons = html! {
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(move |_| Msg::Apply(idx))}>
+                        {"Apply"}
+                    </button>
+                </div>
+            };
+            self.view_preset(ctx, preset, buttons, false)
+        });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(|_| Msg::Import)}>
+                        {"Import"}
+                    </button>
+                    { for paste }
+                    <span>{"-"}</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Compare)}>
+                        {"Compare with another save"}
+                    </button>
+                </div>
+                <hr class="border-t border-default-border" />
+                { for status }
+                <Table title="Create from selection" opened={false}
+                    helper={"Plot ids are separated by commas or spaces, ranges are written `start-end`.\n\
+                        The current values of the selected plots are stored in the preset."}
+                >
+                    { self.view_selection_input("Name", &self.name) }
+                    { self.view_selection_input("Booleans", &self.boolean_ids) }
+                    { self.view_selection_input("Integers", &self.integer_ids) }
+                    { self.view_selection_input("Floats", &self.float_ids) }
+                    <button class="button" onclick={link.callback(|_| Msg::CreateFromSelection)}>
+                        {"Create"}
+                    </button>
+                </Table>
+                { for custom }
+                { for presets }
+            </div>
+        }
+    }
+}
+
+impl PlotPresets {
+    fn view_selection_input(&self, label: &'static str, value: &RcRef<String>) -> Html {
+        let value = RcRef::clone(value);
+        html! {
+            <InputText label={label.to_owned()} {value} />
+        }
+    }
+
+    fn view_preset(
+        &self, ctx: &Context<Self>, preset: &PlotPreset, buttons: Html, opened: bool,
+    ) -> Html {
+        let plot_db = &ctx.props().plot_db;
+        let label = |labels: &IndexMap<usize, String>, id: &usize| match labels.get(id) {
+            Some(label) => format!("{} - {}", id, label),
+            None => id.to_string(),
+        };
+
+        let description = (!preset.description.is_empty()).then(|| {
+            html! { <p>{ format_code(&preset.description) }</p> }
+        });
+
+        let state = Self::plot_state(ctx);
+        let conditions = preset.conditions.iter().map(|condition| {
+            let is_met = condition.is_met(&state);
+            let text = match *condition {
+                PlotCondition::Boolean(id, value) => {
+                    format!("Requires `{}` = {}", label(&plot_db.booleans, &id), value)
+                }
+                PlotCondition::IntegerEquals(id, value) => {
+                    format!("Requires `{}` = {}", label(&plot_db.integers, &id), value)
+                }
+                PlotCondition::IntegerAtLeast(id, value) => {
+                    format!("Requires `{}` >= {}", label(&plot_db.integers, &id), value)
+                }
+                PlotCondition::IntegerAtMost(id, value) => {
+                    format!("Requires `{}` <= {}", label(&plot_db.integers, &id), value)
+                }
+            };
+            let not_met = (!is_met).then(|| " (not met)");
+            html! { <p>{ format_code(text) }{ for not_met }</p> }
+        });
+
+        let booleans = preset.booleans.iter().map(|(id, value)| {
+            html! { <p>{ format!("{} = {}", label(&plot_db.booleans, id), value) }</p> }
+        });
+        let integers = preset.integers.iter().map(|(id, value)| {
+            html! { <p>{ format!("{} = {}", label(&plot_db.integers, id), value) }</p> }
+        });
+        let floats = preset.floats.iter().map(|(id, value)| {
+            html! { <p>{ format!("{} = {}", label(&plot_db.floats, id), value) }</p> }
+        });
+
+        html! {
+            <Table title={preset.name.clone()} {opened}>
+                { buttons }
+                { for description }
+                { for conditions }
+                { for booleans }
+                { for integers }
+                { for floats }
+            </Table>
+        }
+    }
+
+    fn apply(ctx: &Context<Self>, preset: &PlotPreset) -> String {
+        let unmet = {
+            // Released before the plots are set
+            let state = Self::plot_state(ctx);
+            preset.conditions.iter().filter(|condition| !condition.is_met(&state)).count()
+        };
+        if unmet > 0 {
+            return format!(
+                "`{}` has not been applied, {} condition(s) not met",
+                preset.name, unmet
+            );
+        }
+
+        let Props { booleans, integers, floats, .. } = ctx.props();
+        match set_preset_values(booleans, integers, floats, preset) {
+            Ok(()) => format!("`{}` applied", preset.name),
+            Err(err) => format!("`{}` has not been applied: {}", preset.name, err),
+        }
+    }
+
+    fn plot_state(ctx: &Context<Self>) -> SavePlotState<'_> {
+        let Props { booleans, integers, .. } = ctx.props();
+        // The preset conditions only read the plots
+        SavePlotState { booleans: booleans.borrow(), integers, is_female: false, level: 0 }
+    }
+
+    fn create_from_selection(&self, ctx: &Context<Self>) -> Result<PlotPreset> {
+        let name = self.name.borrow().trim().to_owned();
+        if name.is_empty() {
+            bail!("The preset needs a name");
+        }
+
+        let Props { booleans, integers, floats, .. } = ctx.props();
+        let current = plot_snapshot(booleans, integers, floats);
+
+        let select = |ids: &RcRef<String>| -> Result<Vec<usize>> {
+            let mut selected = Vec::new();
+            for (range, value) in parse_plot_ids(&ids.borrow())? {
+                if value.is_some() {
+                    bail!("Selected plots cannot have a value");
+                }
+                selected.extend(range);
+            }
+            Ok(selected)
+        };
+        let preset = PlotPreset {
+            name,
+            booleans: select(&self.boolean_ids)?
+                .into_iter()
+                .map(|id| (id, current.booleans.get(&id).copied().unwrap_or_default()))
+                .collect(),
+            integers: select(&self.integer_ids)?
+                .into_iter()
+                .map(|id| (id, current.integers.get(&id).copied().unwrap_or_default()))
+                .collect(),
+            floats: select(&self.float_ids)?
+                .into_iter()
+                .map(|id| (id, current.floats.get(&id).copied().unwrap_or_default()))
+                .collect(),
+            ..Default::default()
+        };
+
+        if preset.is_empty() {
+            bail!("No plot selected");
+        }
+        Ok(preset)
+    }
+
+    fn create_from_diff(ctx: &Context<Self>, save_game: &SaveGame) -> Result<PlotPreset> {
+        if save_game.theme() != ctx.props().theme {
+            bail!("The save to compare must be from the same game");
+        }
+
+        let Props { booleans, integers, floats, .. } = ctx.props();
+        let current = plot_snapshot(booleans, integers, floats);
+        let other = save_plot_snapshot(save_game);
+
+        let preset = PlotPreset {
+            name: String::from("Difference with the compared save"),
+            description: String::from(
+                "Applying this preset to the compared save gives it the same plots as the current save.",
+            ),
+            ..current.diff(&other)
+        };
+
+        if preset.is_empty() {
+            bail!("Both saves have the same plots");
+        }
+        Ok(preset)
+    }
+}
+
+/// Sets the plot values of `preset` on the plot table of the save, without checking its conditions
+pub fn set_save_preset_values(save_game: &SaveGame, preset: &PlotPreset) -> Result<()> {
+    with_save_plot(save_game, |booleans, integers, floats| {
+        set_preset_values(booleans, integers, floats, preset)
+    })
+}
+
+/// A copy of the plot values of the save
+pub fn save_plot_snapshot(save_game: &SaveGame) -> PlotPreset {
+    with_save_plot(save_game, plot_snapshot)
+}
+
+fn with_save_plot<R>(
+    save_game: &SaveGame, f: impl FnOnce(&RcRef<BitVec>, &IntPlotType, &FloatPlotType) -> R,
+) -> R {
+    match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let state = save_game.state();
+            let plot = state.plot();
+            f(
+                &plot.booleans,
+                &IntPlotType::Vec(RcRef::clone(&plot.integers)),
+                &FloatPlotType::Vec(RcRef::clone(&plot.floats)),
+            )
+        }
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let save_data = save_game.save_data.borrow();
+            let plot = save_data.plot();
+            f(
+                &plot.booleans,
+                &IntPlotType::Vec(RcRef::clone(&plot.integers)),
+                &FloatPlotType::Vec(RcRef::clone(&plot.floats)),
+            )
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let plot = save_game.plot();
+            f(
+                &plot.booleans,
+                &IntPlotType::Vec(RcRef::clone(&plot.integers)),
+                &FloatPlotType::Vec(RcRef::clone(&plot.floats)),
+            )
+        }
+        SaveGame::MassEffect2 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let plot = save_game.plot();
+            f(
+                &plot.booleans,
+                &IntPlotType::Vec(RcRef::clone(&plot.integers)),
+                &FloatPlotType::Vec(RcRef::clone(&plot.floats)),
+            )
+        }
+        SaveGame::MassEffect2Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let plot = save_game.plot();
+            f(
+                &plot.booleans,
+                &IntPlotType::Vec(RcRef::clone(&plot.integers)),
+                &FloatPlotType::Vec(RcRef::clone(&plot.floats)),
+            )
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let plot = save_game.plot();
+            f(
+                &plot.booleans,
+                &IntPlotType::IndexMap(RcRef::clone(&plot.integers)),
+                &FloatPlotType::IndexMap(RcRef::clone(&plot.floats)),
+            )
+        }
+    }
+}
+
+/// Sets the plot values of `preset` without checking its conditions
+pub fn set_preset_values(
+    booleans: &RcRef<BitVec>, integers: &IntPlotType, floats: &FloatPlotType, preset: &PlotPreset,
+) -> Result<()> {
+    // Check every id first so an invalid preset is not half applied
+    let booleans_len = booleans.borrow().len();
+    preset.booleans.keys().try_for_each(|&id| check_plot_id(id, booleans_len))?;
+    match integers {
+        IntPlotType::Vec(vec) => {
+            let len = vec.borrow().len();
+            preset.integers.keys().try_for_each(|&id| check_plot_id(id, len))?;
+        }
+        IntPlotType::IndexMap(_) => {
+            preset.integers.keys().try_for_each(|&id| plot_key(id).map(drop))?;
+        }
+    }
+    match floats {
+        FloatPlotType::Vec(vec) => {
+            let len = vec.borrow().len();
+            preset.floats.keys().try_for_each(|&id| check_plot_id(id, len))?;
+        }
+        FloatPlotType::IndexMap(_) => {
+            preset.floats.keys().try_for_each(|&id| plot_key(id).map(drop))?;
+        }
+    }
+
+    // Booleans
+    {
+        let mut booleans = booleans.borrow_mut();
+        for (&id, &value) in &preset.booleans {
+            if id >= booleans.len() {
+                booleans.resize(id + 1, false);
+            }
+            booleans.set(id, value);
+        }
+    }
+
+    // Integers
+    for (&id, &value) in &preset.integers {
+        match integers {
+            IntPlotType::Vec(vec) => {
+                let mut vec = vec.borrow_mut();
+                if id >= vec.len() {
+                    vec.resize_with(id + 1, Default::default);
+                }
+                vec[id].set(value);
+            }
+            IntPlotType::IndexMap(index_map) => match index_map.borrow_mut().entry(plot_key(id)?) {
+                Entry::Occupied(plot) => plot.get().set(value),
+                Entry::Vacant(plot) => {
+                    plot.insert(RcCell::new(value));
+                }
+            },
+        }
+    }
+
+    // Floats
+    for (&id, &value) in &preset.floats {
+        match floats {
+            FloatPlotType::Vec(vec) => {
+                let mut vec = vec.borrow_mut();
+                if id >= vec.len() {
+                    vec.resize_with(id + 1, Default::default);
+                }
+                vec[id].set(value);
+            }
+            FloatPlotType::IndexMap(index_map) => match index_map.borrow_mut().entry(plot_key(id)?)
+            {
+                Entry::Occupied(plot) => plot.get().set(value),
+                Entry::Vacant(plot) => {
+                    plot.insert(RcCell::new(value));
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+fn plot_snapshot(
+    booleans: &RcRef<BitVec>, integers: &IntPlotType, floats: &FloatPlotType,
+) -> PlotPreset {
+    let booleans = booleans.borrow().iter().by_vals().enumerate().collect();
+    let integers = match integers {
+        IntPlotType::Vec(vec) => vec.borrow().iter().map(RcCell::get).enumerate().collect(),
+        IntPlotType::IndexMap(index_map) => index_map
+            .borrow()
+            .iter()
+            .filter_map(|(&id, value)| Some((usize::try_from(id).ok()?, value.get())))
+            .collect(),
+    };
+    let floats = match floats {
+        FloatPlotType::Vec(vec) => vec.borrow().iter().map(RcCell::get).enumerate().collect(),
+        FloatPlotType::IndexMap(index_map) => index_map
+            .borrow()
+            .iter()
+            .filter_map(|(&id, value)| Some((usize::try_from(id).ok()?, value.get())))
+            .collect(),
+    };
+    PlotPreset { booleans, integers, floats, ..Default::default() }
+}
@@ -1,19 +1,29 @@
 use std::{
     cell::{Ref, RefMut},
+    ops::RangeInclusive,
     rc::Rc,
 };
 
+use anyhow::{bail, Result};
+
 use gloo::{events::EventListener, timers::future::TimeoutFuture, utils};
 use indexmap::{map::Entry, IndexMap};
-use web_sys::{HtmlElement, HtmlInputElement};
-use yew::prelude::*;
+use web_sys::{HtmlElement, HtmlInputElement, HtmlTextAreaElement};
+use yew::{context::ContextHandle, prelude::*};
 
 use crate::{
     gui::{
-        components::{CheckBox, Helper, InputNumber, NumberType},
+        components::{
+            navigation_listener, CheckBox, Helper, InputNumber, InputText, NumberType, Table,
+        },
         raw_ui::RawUi,
+        Theme,
+    },
+    save_data::{
+        shared::plot::{parse_plot_ids, RawPlotDb, PLOT_IDS_MAX},
+        RcCell, RcRef,
     },
-    save_data::{shared::plot::RawPlotDb, RcCell, RcRef},
+    services::database::{Databases, UserPlotLabelsAction},
 };
 
 use super::{FloatPlotType, IntPlotType, PlotType};
@@ -26,12 +36,27 @@ pub enum Msg {
     Filter(InputEvent),
     Filtered,
     Add,
+    Databases(Databases),
+    EditLabel(usize),
+    SaveLabel,
+    RemoveLabel,
+    CancelLabel,
+    ImportLabels,
+    ExportLabels,
+    Navigate(Vec<String>),
+    BulkSet,
+    BulkClear,
+    BulkIds(InputEvent),
+    BulkPaste,
+    BulkCopy,
+    BulkTrim,
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
     pub plots: PlotType,
     pub plot_db: Rc<RawPlotDb>,
+    pub theme: Theme,
     #[prop_or_default]
     filter: RcRef<String>,
     #[prop_or_default]
@@ -54,6 +79,10 @@ impl Props {
 
 pub struct RawPlot {
     _resize_listener: EventListener,
+    _navigation_listener: EventListener,
+    _db_handle: ContextHandle<Databases>,
+    dbs: Databases,
+    plots: PlotType,
     scroll_ref: NodeRef,
     row_height: i32,
     skip: usize,
@@ -61,6 +90,15 @@ pub struct RawPlot {
     label_list: Option<IndexMap<usize, Option<String>>>,
     is_filtering: bool,
     pending_filter: Option<InputEvent>,
+    editing_label: Option<usize>,
+    label_input: RcRef<String>,
+    bulk_from: RcCell<i32>,
+    bulk_to: RcCell<i32>,
+    bulk_value: RcRef<String>,
+    bulk_ids: String,
+    bulk_ids_ref: NodeRef,
+    bulk_status: Option<String>,
+    select_bulk_ids: bool,
 }
 
 impl Component for RawPlot {
@@ -74,10 +112,23 @@ impl Component for RawPlot {
                 link.send_message(Msg::Scrolled)
             })
         };
+        let _navigation_listener = {
+            let link = ctx.link().clone();
+            navigation_listener(move |path| link.send_message(Msg::Navigate(path)))
+        };
         ctx.link().send_message(Msg::Scrolled);
 
+        let (dbs, _db_handle) = ctx
+            .link()
+            .context::<Databases>(ctx.link().callback(Msg::Databases))
+            .expect("no database provider");
+
         let mut this = RawPlot {
             _resize_listener,
+            _navigation_listener,
+            _db_handle,
+            dbs,
+            plots: ctx.props().plots.clone(),
             scroll_ref: Default::default(),
             row_height: 23,
             skip: 0,
@@ -85,6 +136,15 @@ impl Component for RawPlot {
             label_list: None,
             is_filtering: false,
             pending_filter: None,
+            editing_label: None,
+            label_input: Default::default(),
+            bulk_from: Default::default(),
+            bulk_to: Default::default(),
+            bulk_value: RcRef::new(String::from("1")),
+            bulk_ids: String::new(),
+            bulk_ids_ref: Default::default(),
+            bulk_status: None,
+            select_bulk_ids: false,
         };
         this.add_missing_plots(ctx);
         this.update_label_list(ctx);
@@ -201,6 +261,163 @@ impl Component for RawPlot {
                 }
                 false
             }
+            Msg::Databases(dbs) => {
+                self.dbs = dbs;
+                false
+            }
+            Msg::EditLabel(idx) => {
+                let label = ctx.props().plot_db.labels(ctx.props().plots.kind()).get(&idx).cloned();
+                *self.label_input.borrow_mut() = label.unwrap_or_default();
+                self.editing_label = Some(idx);
+                true
+            }
+            Msg::SaveLabel => {
+                if let Some(idx) = self.editing_label.take() {
+                    let label = self.label_input.borrow().trim().to_owned();
+                    let kind = ctx.props().plots.kind();
+                    let action = if label.is_empty() {
+                        UserPlotLabelsAction::Remove(kind, idx)
+                    } else {
+                        UserPlotLabelsAction::Set(kind, idx, label)
+                    };
+                    self.dbs.user_plot_labels_action(ctx.props().theme, action);
+                }
+                true
+            }
+            Msg::RemoveLabel => {
+                if let Some(idx) = self.editing_label.take() {
+                    let action = UserPlotLabelsAction::Remove(ctx.props().plots.kind(), idx);
+                    self.dbs.user_plot_labels_action(ctx.props().theme, action);
+                }
+                true
+            }
+            Msg::CancelLabel => {
+                self.editing_label = None;
+                true
+            }
+            Msg::ImportLabels => {
+                self.dbs.user_plot_labels_action(ctx.props().theme, UserPlotLabelsAction::Import);
+                false
+            }
+            Msg::ExportLabels => {
+                self.dbs.user_plot_labels_action(ctx.props().theme, UserPlotLabelsAction::Export);
+                false
+            }
+            Msg::Navigate(path) => {
+                // The path ends with the row label, `<id> - <label>`
+                let idx = path
+                    .last()
+                    .and_then(|label| label.split(" - ").next())
+                    .and_then(|idx| idx.parse::<usize>().ok());
+
+                if let Some(idx) = idx {
+                    let position = |label_list: &Option<IndexMap<usize, Option<String>>>| {
+                        label_list.as_ref().and_then(|list| list.get_index_of(&idx))
+                    };
+
+                    if position(&self.label_list).is_none() && !ctx.props().filter().is_empty() {
+                        ctx.props().filter_mut().clear();
+                        self.update_label_list(ctx);
+                    }
+
+                    if let (Some(position), Some(scroll)) =
+                        (position(&self.label_list), self.scroll_ref.cast::<HtmlElement>())
+                    {
+                        scroll.set_scroll_top(position as i32 * self.row_height);
+                        ctx.link().send_message(Msg::Scrolled);
+                        return true;
+                    }
+                }
+                false
+            }
+            Msg::BulkSet => {
+                let value = self.bulk_value.borrow().trim().to_owned();
+                let result = self.bulk_range(ctx).and_then(|range| {
+                    let count = range.clone().count();
+                    for id in range {
+                        ctx.props().plots.set_plot(id, &value)?;
+                    }
+                    Ok(format!("{} plots set", count))
+                });
+                self.bulk_done(ctx, result)
+            }
+            Msg::BulkClear => {
+                let result = self.bulk_range(ctx).map(|range| {
+                    let count = range.clone().count();
+                    for id in range {
+                        ctx.props().plots.clear_plot(id);
+                    }
+                    format!("{} plots cleared", count)
+                });
+                self.bulk_done(ctx, result)
+            }
+            Msg::BulkIds(event) => {
+                if let Some(textarea) = event.target_dyn_into::<HtmlTextAreaElement>() {
+                    self.bulk_ids = textarea.value();
+                }
+                false
+            }
+            Msg::BulkPaste => {
+                let default_value = self.bulk_value.borrow().trim().to_owned();
+                let plots = &ctx.props().plots;
+                let result = parse_plot_ids(&self.bulk_ids).and_then(|ids| {
+                    // Every entry is checked before any plot is set, so an invalid one sets none
+                    let ids = ids
+                        .into_iter()
+                        .map(|(range, value)| {
+                            plots.check_id(*range.end())?;
+                            let value = plots.parse_value(value.unwrap_or(&default_value))?;
+                            Ok((range, value))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+
+                    let count: usize = ids.iter().map(|(range, _)| range.clone().count()).sum();
+                    for (range, value) in ids {
+                        for id in range {
+                            plots.set_value(id, value)?;
+                        }
+                    }
+                    Ok(format!("{} plots set", count))
+                });
+                self.bulk_done(ctx, result)
+            }
+            Msg::BulkCopy => {
+                let plots = &ctx.props().plots;
+                let lines: Vec<String> = self
+                    .label_list
+                    .iter()
+                    .flat_map(|list| list.keys())
+                    .take(PLOT_IDS_MAX)
+                    .filter_map(|&id| {
+                        plots.plot_to_string(id).map(|value| format!("{}={}", id, value))
+                    })
+                    .collect();
+
+                self.bulk_ids = lines.join("\n");
+                self.select_bulk_ids = true;
+                self.bulk_status = Some(format!(
+                    "{} plots copied, you can now copy them with Ctrl+C",
+                    lines.len()
+                ));
+                true
+            }
+            Msg::BulkTrim => {
+                let plots = &ctx.props().plots;
+                let len = Self::plots_len(plots);
+                plots.trim();
+                self.add_missing_plots(ctx);
+                let removed = len.saturating_sub(Self::plots_len(plots));
+                self.bulk_done(ctx, Ok(format!("{} plots removed", removed)))
+            }
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if self.select_bulk_ids {
+            self.select_bulk_ids = false;
+            if let Some(textarea) = self.bulk_ids_ref.cast::<HtmlTextAreaElement>() {
+                textarea.select();
+            }
         }
     }
 
@@ -208,6 +425,14 @@ impl Component for RawPlot {
         self.add_missing_plots(ctx);
         self.update_label_list(ctx);
 
+        // Only go back to the top when displaying other plots, not when the labels change
+        if self.plots == ctx.props().plots {
+            ctx.link().send_message(Msg::Scrolled);
+            return true;
+        }
+        self.plots = ctx.props().plots.clone();
+        self.editing_label = None;
+
         if let Some(scroll) = self.scroll_ref.cast::<HtmlElement>() {
             if scroll.scroll_top() != 0 {
                 scroll.set_scroll_top(0);
@@ -256,12 +481,49 @@ impl Component for RawPlot {
                     },
                 };
                 html! {
-                    <div class="raw-plot-row">
+                    <div class="raw-plot-row flex items-center gap-2 group">
                         { for row }
+                        <button class="button invisible group-hover:visible"
+                            onclick={ctx.link().callback(move |_| Msg::EditLabel(idx))}
+                        >
+                            {"Label"}
+                        </button>
                     </div>
                 }
             });
 
+        let label_editor = self.editing_label.map(|idx| {
+            let has_user_label = self
+                .dbs
+                .get_user_plot_labels(ctx.props().theme)
+                .map(|user_labels| user_labels.labels(ctx.props().plots.kind()).contains_key(&idx))
+                .unwrap_or_default();
+            let remove = has_user_label.then(|| {
+                html! {
+                    <button class="button" type="button" onclick={ctx.link().callback(|_| Msg::RemoveLabel)}>
+                        {"Remove"}
+                    </button>
+                }
+            });
+            html! {
+                <form class="flex gap-1 w-2/3"
+                    onsubmit={ctx.link().callback(|e: FocusEvent| {
+                        e.prevent_default();
+                        Msg::SaveLabel
+                    })}
+                >
+                    <InputText label={format!("Label of plot `{}`", idx)} value={RcRef::clone(&self.label_input)}
+                        helper="Your labels are saved separately from the plot database and take precedence over it"
+                    />
+                    <input type="submit" class="button" value="Save" />
+                    { for remove }
+                    <button class="button" type="button" onclick={ctx.link().callback(|_| Msg::CancelLabel)}>
+                        {"Cancel"}
+                    </button>
+                </form>
+            }
+        });
+
         let add_helper = match ctx.props().plots {
             PlotType::Boolean(_)
             | PlotType::Int(IntPlotType::Vec(_))
@@ -275,6 +537,53 @@ impl Component for RawPlot {
             },
             _ => Html::default(),
         };
+        let bulk_status = self.bulk_status.as_ref().map(|status| html! { <span>{ status }</span> });
+        let bulk = html! {
+            <Table title="Bulk operations" opened={false}>
+                <div class="flex items-center gap-2">
+                    <InputNumber label="From" value={NumberType::Int(RcCell::clone(&self.bulk_from))} />
+                    <InputNumber label="To" value={NumberType::Int(RcCell::clone(&self.bulk_to))} />
+                    <InputText label="Value" value={RcRef::clone(&self.bulk_value)}
+                        helper="`1` / `0` for booleans, also used by the pasted ids without a value"
+                    />
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::BulkSet)}>
+                        {"Set range"}
+                    </button>
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::BulkClear)}>
+                        {"Clear range"}
+                    </button>
+                </div>
+                <div class="flex items-start gap-2">
+                    <textarea class="input flex-auto h-16 font-mono resize-none"
+                        spellcheck="false"
+                        placeholder="1, 10-20, 42=3"
+                        value={self.bulk_ids.clone()}
+                        oninput={ctx.link().callback(Msg::BulkIds)}
+                        ref={self.bulk_ids_ref.clone()}
+                    />
+                    <div class="flex flex-col gap-1">
+                        <button class="button" onclick={ctx.link().callback(|_| Msg::BulkPaste)}>
+                            {"Paste ids"}
+                        </button>
+                        <button class="button" onclick={ctx.link().callback(|_| Msg::BulkCopy)}>
+                            {"Copy filtered"}
+                        </button>
+                    </div>
+                </div>
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={ctx.link().callback(|_| Msg::BulkTrim)}>
+                        {"Trim"}
+                    </button>
+                    <Helper text=
+                        "Removes the unset plots at the end of the list, or every unset plot of the ME3 integers and floats.\n\
+                        Use it to undo a plot added with a huge id, once the plot is cleared.\n\
+                        The plots known by the database are kept."
+                    />
+                    { for bulk_status }
+                </div>
+            </Table>
+        };
+
         let len = label_list.map(|list| list.len()).unwrap_or_default();
         html! {
             <div class="flex-auto flex flex-col gap-1">
@@ -295,7 +604,17 @@ impl Component for RawPlot {
                         <input type="submit" class="button" value="Add" />
                         { add_helper }
                     </form>
+                    <div class="flex items-center gap-1">
+                        <button class="button" onclick={ctx.link().callback(|_| Msg::ImportLabels)}>
+                            {"Import labels"}
+                        </button>
+                        <button class="button" onclick={ctx.link().callback(|_| Msg::ExportLabels)}>
+                            {"Export labels"}
+                        </button>
+                    </div>
                 </div>
+                { for label_editor }
+                { bulk }
                 <hr class="border-t border-default-border" />
                 <div class="flex-auto h-0 overflow-y-auto"
                     onscroll={ctx.link().callback(|_| {gloo::console::log!("Scrolled"); Msg::Scrolled})}
@@ -317,6 +636,37 @@ impl Component for RawPlot {
 }
 
 impl RawPlot {
+    fn bulk_range(&self, ctx: &Context<Self>) -> Result<RangeInclusive<usize>> {
+        let (from, to) = (self.bulk_from.get(), self.bulk_to.get());
+        if from < 0 || to < from {
+            bail!("Invalid plot range `{}-{}`", from, to);
+        }
+
+        let range = from as usize..=to as usize;
+        if range.clone().count() > PLOT_IDS_MAX {
+            bail!("Too many plots, the maximum is {}", PLOT_IDS_MAX);
+        }
+        ctx.props().plots.check_id(*range.end())?;
+        Ok(range)
+    }
+
+    fn bulk_done(&mut self, ctx: &Context<Self>, result: Result<String>) -> bool {
+        self.bulk_status = Some(result.unwrap_or_else(|err| err.to_string()));
+        self.update_label_list(ctx);
+        ctx.link().send_message(Msg::Scrolled);
+        true
+    }
+
+    fn plots_len(plots: &PlotType) -> usize {
+        match plots {
+            PlotType::Boolean(booleans) => booleans.borrow().len(),
+            PlotType::Int(IntPlotType::Vec(vec)) => vec.borrow().len(),
+            PlotType::Int(IntPlotType::IndexMap(index_map)) => index_map.borrow().len(),
+            PlotType::Float(FloatPlotType::Vec(vec)) => vec.borrow().len(),
+            PlotType::Float(FloatPlotType::IndexMap(index_map)) => index_map.borrow().len(),
+        }
+    }
+
     fn add_missing_plots(&mut self, ctx: &Context<Self>) {
         let Props { plots, plot_db, .. } = &mut ctx.props();
 
@@ -0,0 +1,480 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use anyhow::{bail, Context as AnyhowContext, Result};
+use indexmap::IndexMap;
+use js_sys::Math;
+use web_sys::HtmlInputElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::format_code,
+    services::{
+        career,
+        rpc::{self, CareerFiles, LibrarySave, RenameCareerParams, RpcFile},
+        save_handler::{Action, SaveGame, SaveHandler, SaveHandlerProvider},
+        save_library::{self, SaveSummary},
+    },
+};
+
+type Career = (String, String);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CareerAction {
+    Clone,
+    Rename,
+    Delete,
+}
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Listed(Result<Vec<LibrarySave>, String>),
+    Refresh,
+    Toggle(Career),
+    Summarized(PathBuf, u64, Result<SaveSummary, String>),
+    Open(PathBuf),
+    StartAction(Career, CareerAction),
+    NameInput(InputEvent),
+    Confirm,
+    Cancel,
+    ActionDone(Result<String, String>),
+    Close,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub opened: bool,
+    pub onclose: Callback<()>,
+}
+
+pub struct SaveLibrary {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    careers: IndexMap<Career, Vec<LibrarySave>>,
+    expanded: HashSet<Career>,
+    // Kept while the library is closed, a save is only parsed again if it has been modified
+    summaries: HashMap<PathBuf, (u64, Result<SaveSummary, String>)>,
+    error: Option<String>,
+    pending: Option<(Career, CareerAction, String)>,
+    status: Option<String>,
+}
+
+impl Component for SaveLibrary {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        if ctx.props().opened {
+            ctx.link().send_message(Msg::Refresh);
+        }
+
+        SaveLibrary {
+            _save_handle,
+            save_handler,
+            careers: IndexMap::new(),
+            expanded: HashSet::new(),
+            summaries: HashMap::new(),
+            error: None,
+            pending: None,
+            status: None,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>) -> bool {
+        if ctx.props().opened {
+            ctx.link().send_message(Msg::Refresh);
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                false
+            }
+            Msg::Refresh => {
+                ctx.link().send_future(async {
+                    let saves = rpc::list_saves().await.context("Failed to list the saves");
+                    Msg::Listed(saves.map_err(|err| format!("{:#}", err)))
+                });
+                false
+            }
+            Msg::Listed(Ok(saves)) => {
+                self.error = None;
+                self.careers = Self::group_by_career(saves);
+                for career in self.expanded.clone() {
+                    self.summarize_career(ctx, &career);
+                }
+                true
+            }
+            Msg::Listed(Err(err)) => {
+                self.error = Some(err);
+                true
+            }
+            Msg::Toggle(career) => {
+                if !self.expanded.remove(&career) {
+                    self.summarize_career(ctx, &career);
+                    self.expanded.insert(career);
+                }
+                true
+            }
+            Msg::Summarized(path, modified, summary) => {
+                self.summaries.insert(path, (modified, summary));
+                true
+            }
+            Msg::Open(path) => {
+                self.save_handler.action(Action::OpenSaveAt(path));
+                ctx.props().onclose.emit(());
+                false
+            }
+            Msg::StartAction(career, action) => {
+                // `Shepard00` or `Shepard_28_Soldier_...` => `Shepard`
+                let name = career.1.trim_end_matches(|c: char| c.is_ascii_digit());
+                let name = name.split('_').next().unwrap_or_default().to_owned();
+                self.pending = Some((career, action, name));
+                self.status = None;
+                true
+            }
+            Msg::NameInput(event) => {
+                if let (Some(input), Some((_, _, name))) =
+                    (event.target_dyn_into::<HtmlInputElement>(), self.pending.as_mut())
+                {
+                    *name = input.value();
+                }
+                false
+            }
+            Msg::Confirm => {
+                if let Some((career, action, name)) = self.pending.take() {
+                    let saves = self.careers.get(&career).cloned().unwrap_or_default();
+                    // ME1LE careers are named after their id, it must not be taken
+                    let taken: HashSet<String> = self
+                        .careers
+                        .keys()
+                        .filter(|(game, _)| *game == career.0)
+                        .map(|(_, name)| name.clone())
+                        .collect();
+                    ctx.link().send_future(async move {
+                        let result = match action {
+                            CareerAction::Clone => Self::clone_career(saves, name, taken).await,
+                            CareerAction::Rename => Self::rename_career(saves, name).await,
+                            CareerAction::Delete => Self::delete_career(saves).await,
+                        };
+                        Msg::ActionDone(result.map_err(|err| format!("{:#}", err)))
+                    });
+                }
+                true
+            }
+            Msg::Cancel => {
+                self.pending = None;
+                true
+            }
+            Msg::ActionDone(result) => {
+                self.status = Some(match result {
+                    Ok(status) => status,
+                    Err(err) => err,
+                });
+                ctx.link().send_message(Msg::Refresh);
+                true
+            }
+            Msg::Close => {
+                ctx.props().onclose.emit(());
+                false
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if !ctx.props().opened {
+            return Html::default();
+        }
+
+        let link = ctx.link();
+        let status = self.status.as_ref().map(|status| html! { <p>{ format_code(status) }</p> });
+        let content = match self.error {
+            Some(ref err) => html! { <p>{ format_code(err) }</p> },
+            None if self.careers.is_empty() => html! { <p>{ "No save found" }</p> },
+            None => self
+                .careers
+                .iter()
+                .map(|(career, saves)| self.view_career(ctx, career, saves))
+                .collect(),
+        };
+
+        html! {
+            <div class={classes![
+                "absolute",
+                "top-8",
+                "left-1/2",
+                "-translate-x-1/2",
+                "w-2/3",
+                "flex",
+                "flex-col",
+                "gap-1",
+                "border",
+                "border-default-border",
+                "bg-default-bg",
+                "p-1",
+                "z-40",
+            ]}>
+                <div class="flex items-center gap-2">
+                    <span class="flex-auto">{ "Save library" }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Refresh)}>
+                        {"Refresh"}
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::Close)}>
+                        {"Close"}
+                    </button>
+                </div>
+                <hr class="border-t border-default-border" />
+                { for status }
+                <div class="flex flex-col gap-1 max-h-[70vh] overflow-y-auto">
+                    { content }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl SaveLibrary {
+    fn group_by_career(mut saves: Vec<LibrarySave>) -> IndexMap<Career, Vec<LibrarySave>> {
+        // Most recent first, so are the careers
+        saves.sort_by_key(|save| Reverse(save.modified));
+
+        let mut careers: IndexMap<Career, Vec<LibrarySave>> = IndexMap::new();
+        for save in saves {
+            careers.entry((save.game.clone(), save.career.clone())).or_default().push(save);
+        }
+        careers.sort_by(|(game_a, _), _, (game_b, _), _| game_a.cmp(game_b));
+        careers
+    }
+
+    fn summarize_career(&self, ctx: &Context<Self>, career: &Career) {
+        let saves = match self.careers.get(career) {
+            Some(saves) => saves,
+            None => return,
+        };
+
+        let outdated = saves.iter().filter(|save| {
+            !matches!(self.summaries.get(&save.path), Some((modified, _)) if *modified == save.modified)
+        });
+        for save in outdated {
+            let LibrarySave { path, modified, .. } = save.clone();
+            ctx.link().send_future(async move {
+                let summarize = async {
+                    let RpcFile { path, file } = rpc::reload_save(path.clone()).await?;
+                    let save_game = SaveHandlerProvider::deserialize(path, file.decode()?)?;
+                    anyhow::Ok(save_library::summarize(&save_game))
+                };
+                let summary = summarize.await.map_err(|err| format!("{:#}", err));
+                Msg::Summarized(path, modified, summary)
+            });
+        }
+    }
+
+    async fn load_save(path: PathBuf) -> Result<SaveGame> {
+        let RpcFile { path, file } = rpc::reload_save(path).await?;
+        SaveHandlerProvider::deserialize(path, file.decode()?)
+    }
+
+    fn career_dir(saves: &[LibrarySave]) -> Result<&Path> {
+        saves.first().and_then(|save| save.path.parent()).context("Empty career")
+    }
+
+    /// Starts a new career from its most recent save
+    async fn clone_career(
+        saves: Vec<LibrarySave>, name: String, taken: HashSet<String>,
+    ) -> Result<String> {
+        let career_dir = Self::career_dir(&saves)?;
+        let old_career = career_dir.file_name().unwrap_or_default().to_string_lossy();
+        let save_game = Self::load_save(saves[0].path.clone()).await?;
+        career::rename(&save_game, &name)?;
+
+        let mut attempts = 0;
+        let dir_name = loop {
+            let seed = random_seed();
+            let dir_name = career::reidentify(&save_game, seed)?;
+            if !taken.contains(&dir_name) {
+                break dir_name;
+            }
+            attempts += 1;
+            if attempts == 10 {
+                bail!("Failed to find a free career id");
+            }
+        };
+
+        let file_name = career::save_file_name(&save_game, &old_career, &saves[0].path);
+        let rpc_file = SaveHandlerProvider::serialize(file_name, Rc::new(save_game))?;
+        let path = career_dir.with_file_name(&dir_name);
+        rpc::create_career(CareerFiles { path, saves: vec![rpc_file] })
+            .await
+            .context("Failed to clone the career")?;
+        Ok(format!("Career `{}` created", dir_name))
+    }
+
+    async fn rename_career(saves: Vec<LibrarySave>, name: String) -> Result<String> {
+        let career_dir = Self::career_dir(&saves)?;
+        let old_career = career_dir.file_name().unwrap_or_default().to_string_lossy();
+
+        let mut dir_name = None;
+        let mut files = Vec::new();
+        for save in &saves {
+            let save_game = Self::load_save(save.path.clone()).await?;
+            let old_name = career::rename(&save_game, &name)?;
+            if dir_name.is_none() {
+                dir_name =
+                    Some(career::renamed_career_dir_name(&save_game, &old_name, &old_career)?);
+            }
+            let file_name = career::save_file_name(&save_game, &old_career, &save.path);
+            files.push(SaveHandlerProvider::serialize(file_name, Rc::new(save_game))?);
+        }
+
+        let dir_name = dir_name.context("Empty career")?;
+        let params = RenameCareerParams {
+            from: career_dir.to_owned(),
+            to: CareerFiles { path: career_dir.with_file_name(&dir_name), saves: files },
+        };
+        let trash = rpc::rename_career(params).await.context("Failed to rename the career")?;
+        Ok(format!("Career renamed to `{}`, the old one is in `{}`", dir_name, trash.display()))
+    }
+
+    async fn delete_career(saves: Vec<LibrarySave>) -> Result<String> {
+        let career_dir = Self::career_dir(&saves)?;
+        let trash = rpc::delete_career(career_dir.to_owned())
+            .await
+            .context("Failed to delete the career")?;
+        Ok(format!("Career moved to `{}`", trash.display()))
+    }
+
+    fn view_career(&self, ctx: &Context<Self>, career: &Career, saves: &[LibrarySave]) -> Html {
+        let (game, name) = career;
+        let opened = self.expanded.contains(career);
+        let onclick = ctx.link().callback({
+            let career = career.clone();
+            move |_| Msg::Toggle(career.clone())
+        });
+
+        // A career folder is named after its character
+        let title = format!("{} - {} ({} saves)", game, name, saves.len());
+        let rows = opened.then(|| {
+            let rows = saves.iter().map(|save| self.view_save(ctx, save));
+            html! {
+                <div class="flex flex-col pl-4">
+                    { for rows }
+                </div>
+            }
+        });
+
+        let link = ctx.link();
+        let action = |action: CareerAction, label: &'static str| {
+            let career = career.clone();
+            html! {
+                <button class="button" onclick={link.callback(move |_| Msg::StartAction(career.clone(), action))}>
+                    { label }
+                </button>
+            }
+        };
+        let pending = self
+            .pending
+            .as_ref()
+            .filter(|(pending, _, _)| pending == career)
+            .map(|(_, action, name)| self.view_pending(ctx, *action, name));
+
+        html! {
+            <div class="flex flex-col">
+                <div class="flex items-center gap-1">
+                    <a class="flex-auto px-1 hover:bg-theme-hover active:bg-theme-active cursor-pointer truncate" {onclick}>
+                        { if opened { "▾ " } else { "▸ " } }
+                        { title }
+                    </a>
+                    { action(CareerAction::Clone, "Clone") }
+                    { action(CareerAction::Rename, "Rename") }
+                    { action(CareerAction::Delete, "Delete") }
+                </div>
+                { for pending }
+                { for rows }
+            </div>
+        }
+    }
+
+    fn view_pending(&self, ctx: &Context<Self>, action: CareerAction, name: &str) -> Html {
+        let link = ctx.link();
+        let label = match action {
+            CareerAction::Clone => "New career with a fresh id, named",
+            CareerAction::Rename => "Rename the character to",
+            CareerAction::Delete => "Move this career to the trash ?",
+        };
+        let input = (action != CareerAction::Delete).then(|| {
+            html! {
+                <input type="text" class="input w-48"
+                    value={name.to_owned()}
+                    oninput={link.callback(Msg::NameInput)}
+                />
+            }
+        });
+
+        html! {
+            <div class="flex items-center gap-2 pl-4">
+                <span>{ label }</span>
+                { for input }
+                <button class="button" onclick={link.callback(|_| Msg::Confirm)}>
+                    {"Confirm"}
+                </button>
+                <button class="button" onclick={link.callback(|_| Msg::Cancel)}>
+                    {"Cancel"}
+                </button>
+            </div>
+        }
+    }
+
+    fn view_save(&self, ctx: &Context<Self>, save: &LibrarySave) -> Html {
+        let file_name = save.path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let summary = match self.summaries.get(&save.path) {
+            Some((_, Ok(summary))) => {
+                let SaveSummary { name, class, level, timestamp, location, .. } = summary;
+                let character = [name.as_str(), class.as_str()]
+                    .iter()
+                    .filter(|text| !text.is_empty())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let level = level.map(|level| format!("level {}", level));
+                let columns =
+                    [Some(character), level, Some(timestamp.clone()), Some(location.clone())];
+                columns
+                    .into_iter()
+                    .flatten()
+                    .filter(|column| !column.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" - ")
+            }
+            Some((_, Err(err))) => err.clone(),
+            None => String::from("Loading..."),
+        };
+
+        let path = save.path.clone();
+        html! {
+            <div class="flex items-center gap-2 px-1 hover:bg-theme-hover">
+                <button class="button" onclick={ctx.link().callback(move |_| Msg::Open(path.clone()))}>
+                    {"Open"}
+                </button>
+                <span class="w-48 truncate" title={file_name.clone()}>{ file_name }</span>
+                <span class="truncate">{ summary }</span>
+            </div>
+        }
+    }
+}
+
+// 128 random bits for the new career ids
+fn random_seed() -> u128 {
+    (0..4).fold(0, |seed, _| seed << 32 | (Math::random() * u32::MAX as f64) as u128)
+}
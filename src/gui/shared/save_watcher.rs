@@ -0,0 +1,219 @@
+use std::rc::{Rc, Weak};
+
+use anyhow::{Context as AnyhowContext, Error};
+use gloo::{events::EventListener, utils};
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::format_code,
+    save_data::shared::plot::PlotPreset,
+    services::{
+        rpc::{self, RpcFile},
+        save_handler::{Action, SaveGame, SaveHandler, SaveHandlerProvider},
+    },
+};
+
+use super::{save_plot_snapshot, set_save_preset_values};
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    FileChanged,
+    Reload,
+    Merge,
+    Merged(SaveGame, Box<PlotPreset>),
+    KeepMine,
+    Error(Error),
+    Noop,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub onerror: Callback<Error>,
+}
+
+/// Warns when the game overwrites the open save and offers to reload it or merge the plot edits
+pub struct SaveWatcher {
+    _save_handle: ContextHandle<SaveHandler>,
+    _file_listener: EventListener,
+    save_handler: SaveHandler,
+    // The plots of each open save as they were on disk, to find what has been edited since
+    originals: Vec<(Weak<SaveGame>, PlotPreset)>,
+    merged_original: Option<PlotPreset>,
+    changed: bool,
+}
+
+impl Component for SaveWatcher {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        let _file_listener = {
+            let link = ctx.link().clone();
+            EventListener::new(&utils::document(), "tse_file_changed", move |_| {
+                link.send_message(Msg::FileChanged)
+            })
+        };
+
+        Self::watch(ctx, &save_handler);
+        let originals = save_handler
+            .save_game
+            .iter()
+            .map(|save_game| (Rc::downgrade(save_game), save_plot_snapshot(save_game)))
+            .collect();
+        SaveWatcher {
+            _save_handle,
+            _file_listener,
+            originals,
+            save_handler,
+            merged_original: None,
+            changed: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_loaded(ctx, save_handler);
+                true
+            }
+            Msg::FileChanged => {
+                self.changed = self.save_handler.save_game.is_some();
+                true
+            }
+            Msg::Reload => {
+                self.save_handler.action(Action::ReloadSave);
+                false
+            }
+            Msg::Merge => {
+                if let Some(save_game) = self.save_handler.save_game.clone() {
+                    let original = match self.original(&save_game) {
+                        Some(original) => original,
+                        None => return false,
+                    };
+                    let edits = save_plot_snapshot(&save_game).diff(original);
+                    let path = save_game.file_path().to_owned();
+                    ctx.link().send_future(async move {
+                        let merge = async {
+                            let RpcFile { path, file } = rpc::reload_save(path).await?;
+                            let on_disk = SaveHandlerProvider::deserialize(path, file.decode()?)?;
+                            let original = save_plot_snapshot(&on_disk);
+                            set_save_preset_values(&on_disk, &edits)?;
+                            Ok::<_, Error>((on_disk, original))
+                        };
+                        match merge.await.context("Failed to merge the save") {
+                            Ok((save_game, original)) => Msg::Merged(save_game, Box::new(original)),
+                            Err(err) => Msg::Error(err),
+                        }
+                    });
+                }
+                false
+            }
+            Msg::Merged(save_game, original) => {
+                self.merged_original = Some(*original);
+                self.save_handler.action(Action::LoadSaveGame(save_game));
+                false
+            }
+            Msg::KeepMine => {
+                self.changed = false;
+                Self::watch(ctx, &self.save_handler);
+                true
+            }
+            Msg::Error(err) => {
+                ctx.props().onerror.emit(err);
+                false
+            }
+            Msg::Noop => false,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if !self.changed {
+            return Html::default();
+        }
+
+        let link = ctx.link();
+        html! {
+            <div class={classes![
+                "absolute",
+                "top-8",
+                "left-1/2",
+                "-translate-x-1/2",
+                "flex",
+                "flex-col",
+                "gap-1",
+                "border",
+                "border-default-border",
+                "bg-default-bg",
+                "p-1",
+                "z-40",
+            ]}>
+                <p>{ "The save has been modified by the game since it was opened." }</p>
+                <p>{ format_code("`Merge` reloads it and applies your plot edits, other edits are lost.") }</p>
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(|_| Msg::Reload)}>
+                        {"Reload"}
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::Merge)}>
+                        {"Merge"}
+                    </button>
+                    <button class="button" onclick={link.callback(|_| Msg::KeepMine)}>
+                        {"Keep my version"}
+                    </button>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl SaveWatcher {
+    fn save_loaded(&mut self, ctx: &Context<Self>, save_handler: SaveHandler) {
+        let is_new_save = match (&self.save_handler.save_game, &save_handler.save_game) {
+            (Some(current), Some(new)) => !Rc::ptr_eq(current, new),
+            (None, Some(_)) => true,
+            _ => false,
+        };
+        self.save_handler = save_handler;
+
+        if is_new_save {
+            let merged_original = self.merged_original.take();
+            // Forget the closed saves
+            self.originals.retain(|(save_game, _)| save_game.strong_count() > 0);
+            if let Some(ref save_game) = self.save_handler.save_game {
+                // A merged save starts from the plots on disk, not from the merged ones
+                if self.original(save_game).is_none() {
+                    let original = merged_original.unwrap_or_else(|| save_plot_snapshot(save_game));
+                    self.originals.push((Rc::downgrade(save_game), original));
+                }
+            }
+            self.changed = false;
+            Self::watch(ctx, &self.save_handler);
+        }
+    }
+
+    fn original(&self, save_game: &Rc<SaveGame>) -> Option<&PlotPreset> {
+        self.originals.iter().find_map(|(other, original)| {
+            Weak::ptr_eq(other, &Rc::downgrade(save_game)).then(|| original)
+        })
+    }
+
+    fn watch(ctx: &Context<Self>, save_handler: &SaveHandler) {
+        if let Some(ref save_game) = save_handler.save_game {
+            let path = save_game.file_path().to_owned();
+            ctx.link().send_future(async move {
+                // Dropped saves only have a file name
+                if !path.to_string_lossy().contains(['/', '\\']) {
+                    return Msg::Noop;
+                }
+                match rpc::watch_file(path).await.context("Failed to watch the save") {
+                    Ok(_) => Msg::Noop,
+                    Err(err) => Msg::Error(err),
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::{cell::RefCell, rc::Rc};
+
+use web_sys::HtmlTextAreaElement;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{components::Table, format_code},
+    services::{
+        save_handler::{Action, SaveHandler},
+        script,
+    },
+};
+
+const REFERENCE: &[&str] = &[
+    "`save.game` - name of the game",
+    "`save.plot` / `save.me1_plot` - plot tables, `me1_plot` is ME2 only",
+    "`plot.get_bool(id)` / `plot.set_bool(id, value)` - also `get_int`, `set_int`, `get_float`, `set_float`",
+    "`save.player` - `first_name`, `is_female`, `level`, `current_xp`, `talent_points`, `credits`, `medigel`",
+    "`save.player` (ME1LE) - `omnigel`, `talents`, `inventory`, `add_item(item_id, manufacturer_id, item_level)`",
+    "`save.player` (ME2) - `eezo`, `iridium`, `palladium`, `platinum`, `probes`, `current_fuel`, `powers`",
+    "`save.player` (ME3) - `current_fuel`, `powers`",
+    "`save.squad` - henchmen with `tag`, `level`, `talent_points`, `powers` (ME2 / ME3), `talents` (ME1LE)",
+    "`power` - `name`, `rank` / `talent` - `talent_id`, `current_rank`, `max_rank`",
+    "`item` - `item_id`, `manufacturer_id`, `item_level`",
+    "`save.kismet_records` (ME2 / ME3) - records with `guid` and `value`",
+    "`save.raw()` - read-only copy of the whole save",
+    "`print(value)` - writes to the output",
+];
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Input(InputEvent),
+    Run,
+    Open,
+    Opened(String),
+    Save,
+    ClearOutput,
+}
+
+pub struct ScriptConsole {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    script: String,
+    output: Vec<String>,
+    error: Option<String>,
+}
+
+impl Component for ScriptConsole {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        ScriptConsole {
+            _save_handle,
+            save_handler,
+            script: String::new(),
+            output: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                false
+            }
+            Msg::Input(event) => {
+                if let Some(textarea) = event.target_dyn_into::<HtmlTextAreaElement>() {
+                    self.script = textarea.value();
+                }
+                false
+            }
+            Msg::Run => {
+                if let Some(ref save_game) = self.save_handler.save_game {
+                    let output = Rc::new(RefCell::new(Vec::new()));
+                    let result = {
+                        let output = Rc::clone(&output);
+                        script::run(save_game, &self.script, move |line| {
+                            output.borrow_mut().push(line)
+                        })
+                    };
+                    self.output = output.take();
+                    self.error = result.err().map(|err| err.to_string());
+                }
+                true
+            }
+            Msg::Open => {
+                self.save_handler.action(Action::OpenScript(ctx.link().callback(Msg::Opened)));
+                false
+            }
+            Msg::Opened(script) => {
+                self.script = script;
+                true
+            }
+            Msg::Save => {
+                self.save_handler.action(Action::SaveScript(self.script.clone()));
+                false
+            }
+            Msg::ClearOutput => {
+                self.output.clear();
+                self.error = None;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let output = self.output.iter().map(|line| html! { <p>{ line }</p> });
+        let error = self.error.as_ref().map(|error| html! { <p>{ format_code(error) }</p> });
+        let reference = REFERENCE.iter().map(|&line| html! { <p>{ format_code(line) }</p> });
+
+        html! {
+            <div class="flex-auto flex flex-col gap-1">
+                <div class="flex items-center gap-2">
+                    <button class="button" onclick={link.callback(|_| Msg::Run)}>
+                        {"Run"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Open)}>
+                        {"Open"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Save)}>
+                        {"Save"}
+                    </button>
+                    <span>{"-"}</span>
+                    <button class="button" onclick={link.callback(|_| Msg::ClearOutput)}>
+                        {"Clear output"}
+                    </button>
+                </div>
+                <hr class="border-t border-default-border" />
+                <textarea
+                    class="input flex-auto min-h-[150px] font-mono resize-none"
+                    spellcheck="false"
+                    placeholder="for henchman in save.squad { ... }"
+                    value={self.script.clone()}
+                    oninput={link.callback(Msg::Input)}
+                />
+                <Table title="Output">
+                    { for output }
+                    { for error }
+                </Table>
+                <Table title="Reference" opened={false}
+                    helper="Scripts are written in Rhai, see https://rhai.rs/book/ for the language itself"
+                >
+                    { for reference }
+                </Table>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::path::PathBuf;
+
+use anyhow::Error;
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{
+        components::Select,
+        i18n::{tr, tr_format},
+    },
+    services::{
+        rpc::{self, Language, SavePlatform, Settings},
+        save_handler::{Action, SaveHandler},
+    },
+};
+
+// In the order of `Language::ALL`
+const LANGUAGES: &[&str] = &["English", "Français"];
+const PLATFORMS: &[&str] = &["PC", "XBOX 360"];
+// The games with localized ids to resolve, ME1 has none.
+// The ME1LE talk table is exported from its packages with Legendary Explorer.
+const TLK_GAMES: &[&str] =
+    &["Mass Effect Legendary", "Mass Effect 2", "Mass Effect 2 Legendary", "Mass Effect 3"];
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Language(usize),
+    Platform(usize),
+    ClearRecentFiles,
+    BrowseTlk(&'static str),
+    TlkPicked(&'static str, PathBuf),
+    ClearTlk(&'static str),
+    Close,
+    Error(Error),
+    Noop,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub opened: bool,
+    pub onclose: Callback<()>,
+}
+
+/// The preferences kept between runs, the backup policy is set in the `Backups` panel
+pub struct SettingsPanel {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+}
+
+impl Component for SettingsPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        SettingsPanel { _save_handle, save_handler }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let mut settings = Settings::clone(&self.save_handler.settings);
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                self.save_handler = save_handler;
+                return true;
+            }
+            Msg::Language(idx) => settings.language = Language::ALL[idx],
+            Msg::Platform(idx) => {
+                settings.default_platform =
+                    if idx == 0 { SavePlatform::Pc } else { SavePlatform::Xbox360 }
+            }
+            Msg::ClearRecentFiles => settings.recent_files.clear(),
+            Msg::BrowseTlk(game) => {
+                ctx.link().send_future(async move {
+                    match rpc::open_tlk_dialog().await {
+                        Ok(Some(path)) => Msg::TlkPicked(game, path),
+                        Ok(None) => Msg::Noop,
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                return false;
+            }
+            Msg::TlkPicked(game, path) => {
+                settings.tlk_files.insert(game.to_owned(), path);
+            }
+            Msg::ClearTlk(game) => {
+                settings.tlk_files.remove(game);
+            }
+            Msg::Close => {
+                ctx.props().onclose.emit(());
+                return false;
+            }
+            Msg::Error(err) => {
+                self.save_handler.action(Action::Error(err));
+                return false;
+            }
+            Msg::Noop => return false,
+        }
+        self.save_handler.action(Action::SetSettings(settings));
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if !ctx.props().opened {
+            return Html::default();
+        }
+
+        let link = ctx.link();
+        let settings = &self.save_handler.settings;
+        let language_idx = Language::ALL.iter().position(|language| *language == settings.language);
+        let platform_idx = match settings.default_platform {
+            SavePlatform::Pc => 0,
+            SavePlatform::Xbox360 => 1,
+        };
+
+        let tlk_files = TLK_GAMES.iter().map(|&game| {
+            let path = settings.tlk_files.get(game);
+            let clear = path.is_some().then(|| {
+                html! {
+                    <button class="button" onclick={link.callback(move |_| Msg::ClearTlk(game))}>
+                        { tr("Clear") }
+                    </button>
+                }
+            });
+            let path = path.map(|path| path.display().to_string()).unwrap_or_default();
+            html! {
+                <div class="flex items-center gap-2">
+                    <span class="w-44 shrink-0">{ game }</span>
+                    <span class="flex-auto truncate" title={path.clone()}>{ path }</span>
+                    <button class="button" onclick={link.callback(move |_| Msg::BrowseTlk(game))}>
+                        { tr("Browse") }
+                    </button>
+                    { for clear }
+                </div>
+            }
+        });
+
+        html! {
+            <div class={classes![
+                "absolute",
+                "top-8",
+                "left-1/2",
+                "-translate-x-1/2",
+                "w-1/2",
+                "flex",
+                "flex-col",
+                "gap-1",
+                "border",
+                "border-default-border",
+                "bg-default-bg",
+                "p-1",
+                "z-40",
+            ]}>
+                <div class="flex items-center gap-2">
+                    <span class="flex-auto">{ tr("Settings") }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::Close)}>
+                        { tr("Close") }
+                    </button>
+                </div>
+                <hr class="border-t border-default-border" />
+                <div class="flex items-center gap-1 cursor-default">
+                    <Select
+                        options={LANGUAGES}
+                        current_idx={language_idx.unwrap_or_default()}
+                        onselect={link.callback(Msg::Language)}
+                    />
+                    { tr("Language") }
+                </div>
+                <div class="flex items-center gap-1 cursor-default">
+                    <Select
+                        options={PLATFORMS}
+                        current_idx={platform_idx}
+                        onselect={link.callback(Msg::Platform)}
+                    />
+                    { tr("Default save platform") }
+                </div>
+                <div class="flex items-center gap-2">
+                    <span>{ tr_format("{} recent file(s)", &[&settings.recent_files.len()]) }</span>
+                    <button class="button" onclick={link.callback(|_| Msg::ClearRecentFiles)}>
+                        { tr("Clear") }
+                    </button>
+                </div>
+                <hr class="border-t border-default-border" />
+                <span>{ tr("TLK files, to show the localized names") }</span>
+                { for tlk_files }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::marker::PhantomData;
+
+use anyhow::anyhow;
+use serde::{de::DeserializeOwned, Serialize};
+use yew::{context::ContextHandle, prelude::*};
+
+use crate::{
+    gui::{components::Table, i18n::tr},
+    save_data::{
+        mass_effect_1_le::squad::Henchman as Me1LeHenchman,
+        mass_effect_2::squad::Henchman as Me2Henchman,
+        mass_effect_3::squad::Henchman as Me3Henchman, RcRef,
+    },
+    services::save_handler::{Action, SaveHandler},
+};
+
+/// A henchman that can be copied from one save to another
+pub trait SquadMember: Serialize + DeserializeOwned + 'static {
+    fn tag(&self) -> String;
+}
+
+macro_rules! impl_squad_member {
+    ($($henchman:ty),*) => {
+        $(
+            impl SquadMember for $henchman {
+                fn tag(&self) -> String {
+                    self.tag().clone()
+                }
+            }
+        )*
+    };
+}
+
+impl_squad_member!(Me1LeHenchman, Me2Henchman, Me3Henchman);
+
+pub enum Msg {
+    SaveHandler(SaveHandler),
+    Copy(usize),
+    Paste(usize),
+}
+
+#[derive(Properties)]
+pub struct Props<H: SquadMember> {
+    pub squad: RcRef<Vec<RcRef<H>>>,
+}
+
+impl<H: SquadMember> PartialEq for Props<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.squad == other.squad
+    }
+}
+
+/// Copies a henchman to paste it over the same henchman, in this save or another open one
+pub struct SquadClipboard<H: SquadMember> {
+    _save_handle: ContextHandle<SaveHandler>,
+    save_handler: SaveHandler,
+    _henchman: PhantomData<H>,
+}
+
+impl<H: SquadMember> Component for SquadClipboard<H> {
+    type Message = Msg;
+    type Properties = Props<H>;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        SquadClipboard { _save_handle, save_handler, _henchman: PhantomData }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SaveHandler(save_handler) => {
+                // Only the clipboard is shown
+                let can_paste = save_handler.can_paste::<H>();
+                let changed = can_paste != self.save_handler.can_paste::<H>();
+                self.save_handler = save_handler;
+                changed
+            }
+            Msg::Copy(idx) => {
+                if let Some(henchman) = ctx.props().squad.borrow().get(idx) {
+                    let henchman = henchman.borrow();
+                    self.save_handler.copy(henchman.tag(), &*henchman);
+                }
+                false
+            }
+            Msg::Paste(idx) => {
+                let squad = ctx.props().squad.borrow();
+                match (squad.get(idx), self.save_handler.paste::<H>()) {
+                    (Some(henchman), Some(pasted)) => {
+                        // A henchman's tag is what the game spawns, so it only replaces itself
+                        let mut henchman = henchman.borrow_mut();
+                        if pasted.tag() != henchman.tag() {
+                            self.save_handler.action(Action::Error(anyhow!(
+                                "Cannot paste `{}` over `{}`, only the same henchman can be pasted",
+                                pasted.tag(),
+                                henchman.tag()
+                            )));
+                            return false;
+                        }
+                        *henchman = pasted;
+                        true
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let can_paste = self.save_handler.can_paste::<H>();
+        let squad = ctx.props().squad.borrow();
+        let henchmen = squad.iter().enumerate().map(|(idx, henchman)| {
+            let paste = can_paste.then(|| {
+                html! {
+                    <>
+                        <span>{"-"}</span>
+                        <button class="button" onclick={link.callback(move |_| Msg::Paste(idx))}>
+                            { tr("Paste") }
+                        </button>
+                    </>
+                }
+            });
+            html! {
+                <div class="flex items-center gap-2 px-1">
+                    <span class="w-40">{ henchman.borrow().tag() }</span>
+                    <button class="button" onclick={link.callback(move |_| Msg::Copy(idx))}>
+                        { tr("Copy") }
+                    </button>
+                    { for paste }
+                </div>
+            }
+        });
+
+        html! {
+            <Table title="Squad clipboard" opened={false}
+                helper="Copy a henchman to paste it over the same henchman, in this save or another one."
+            >
+                { for henchmen }
+            </Table>
+        }
+    }
+}
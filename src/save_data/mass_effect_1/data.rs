@@ -27,6 +27,15 @@ impl Data {
         Ok(Self { _osef, properties })
     }
 
+    /// A new object data with the same header as `self`
+    pub fn with_properties(&self, properties: List<RcRef<Property>>) -> Self {
+        Data { _osef: self._osef.clone(), properties }
+    }
+
+    pub fn update_sizes(&self) -> Result<()> {
+        self.properties.update_sizes()
+    }
+
     pub fn size(&self) -> Result<usize> {
         let mut size = 4;
         for property in self.properties.iter() {
@@ -41,6 +50,28 @@ fn get_name(names: &[Name], id: u32) -> String {
 }
 
 impl List<RcRef<Property>> {
+    /// Updates the sizes of the properties, the nested ones first
+    pub fn update_sizes(&self) -> Result<()> {
+        for property in self.iter() {
+            let mut property = property.borrow_mut();
+            match *property {
+                Property::Array { ref array, .. } => {
+                    for item in array {
+                        if let ArrayType::Properties(properties) = item {
+                            properties.update_sizes()?;
+                        }
+                    }
+                }
+                Property::Struct {
+                    struct_type: StructType::Properties(ref properties), ..
+                } => properties.update_sizes()?,
+                _ => (),
+            }
+            property.update_size()?;
+        }
+        Ok(())
+    }
+
     pub fn visit_seq<'de, A>(names: &[Name], seq: &mut A) -> Result<Self, A::Error>
     where
         A: de::SeqAccess<'de>,
@@ -161,9 +192,185 @@ pub enum Property {
         name_id: u32,
         _osef: Dummy<4>,
     },
+    // Kept as is to not lose data
+    Unknown {
+        name_id: u32,
+        _osef1: Dummy<4>,
+        type_id: u32,
+        _osef2: Dummy<4>,
+        size: u32,
+        _osef3: Dummy<4>,
+        bytes: List<u8>,
+    },
+}
+
+#[derive(Clone, Copy, RawUi)]
+pub enum PropertyType {
+    Int,
+    Float,
+    Bool,
+    Str,
+    Struct,
+    Array,
+}
+
+impl PropertyType {
+    pub fn type_name(self) -> &'static str {
+        match self {
+            PropertyType::Int => "IntProperty",
+            PropertyType::Float => "FloatProperty",
+            PropertyType::Bool => "BoolProperty",
+            PropertyType::Str => "StrProperty",
+            PropertyType::Struct => "StructProperty",
+            PropertyType::Array => "ArrayProperty",
+        }
+    }
+}
+
+/// Where a list of properties is stored
+#[derive(Clone, PartialEq)]
+pub enum PropertiesOwner {
+    Object(i32),
+    Struct(RcRef<Property>),
+    ArrayItem(RcRef<Property>, usize),
 }
 
 impl Property {
+    pub fn new_int(name_id: u32, type_id: u32, value: i32) -> Self {
+        Property::Int {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 4,
+            _osef3: Default::default(),
+            value: value.into(),
+        }
+    }
+
+    pub fn new_float(name_id: u32, type_id: u32, value: f32) -> Self {
+        Property::Float {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 4,
+            _osef3: Default::default(),
+            value: value.into(),
+        }
+    }
+
+    pub fn new_bool(name_id: u32, type_id: u32, value: bool) -> Self {
+        // The bools are always written with a size of 0
+        Property::Bool {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 0,
+            _osef3: Default::default(),
+            value: value.into(),
+        }
+    }
+
+    pub fn new_byte(name_id: u32, type_id: u32, value: u8) -> Self {
+        Property::Byte {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 1,
+            _osef3: Default::default(),
+            value: value.into(),
+        }
+    }
+
+    pub fn new_str(name_id: u32, type_id: u32, string: String) -> Result<Self> {
+        let mut property = Property::Str {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 0,
+            _osef3: Default::default(),
+            string: string.into(),
+        };
+        property.update_size()?;
+        Ok(property)
+    }
+
+    pub fn new_object(name_id: u32, type_id: u32, object_id: i32) -> Self {
+        Property::Object {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 4,
+            _osef3: Default::default(),
+            object_id,
+        }
+    }
+
+    pub fn new_struct(name_id: u32, type_id: u32, struct_name_id: u32, none_id: u32) -> Self {
+        let properties = vec![RcRef::new(Property::new_none(none_id))];
+        Property::Struct {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 8,
+            _osef3: Default::default(),
+            struct_name_id,
+            _osef4: Default::default(),
+            struct_type: StructType::Properties(properties.into()),
+        }
+    }
+
+    pub fn new_array(name_id: u32, type_id: u32) -> Self {
+        Property::Array {
+            name_id,
+            _osef1: Default::default(),
+            type_id,
+            _osef2: Default::default(),
+            size: 4,
+            _osef3: Default::default(),
+            array: Vec::new(),
+        }
+    }
+
+    pub fn new_none(name_id: u32) -> Self {
+        Property::None { name_id, _osef: Default::default() }
+    }
+
+    pub fn name_id(&self) -> u32 {
+        match *self {
+            Property::Array { name_id, .. }
+            | Property::Bool { name_id, .. }
+            | Property::Byte { name_id, .. }
+            | Property::Float { name_id, .. }
+            | Property::Int { name_id, .. }
+            | Property::Name { name_id, .. }
+            | Property::Object { name_id, .. }
+            | Property::Str { name_id, .. }
+            | Property::StringRef { name_id, .. }
+            | Property::Struct { name_id, .. }
+            | Property::None { name_id, .. }
+            | Property::Unknown { name_id, .. } => name_id,
+        }
+    }
+
+    /// Updates the size written in the header of a variable length property after its content changed
+    pub fn update_size(&mut self) -> Result<()> {
+        let new_size = self.size()? as u32;
+        match self {
+            Property::Array { size, .. } | Property::Str { size, .. } => *size = new_size - 24,
+            // Without the struct name
+            Property::Struct { size, .. } => *size = new_size - 24 - 8,
+            _ => (),
+        }
+        Ok(())
+    }
+
     pub fn visit_seq<'de, A>(names: &[Name], seq: &mut A) -> Result<Self, A::Error>
     where
         A: de::SeqAccess<'de>,
@@ -201,7 +408,8 @@ impl Property {
                     | "m_aXMod"
                     | "m_aEquipped"
                     | "m_QuickSlotArray"
-                    | "m_savedBuybackItems" => {
+                    | "m_savedBuybackItems"
+                    | "m_LevelObjects" => {
                         for _ in 0..len {
                             let array_object = ArrayType::Object(seq.next_element()?.unwrap());
                             array.push(array_object);
@@ -213,7 +421,7 @@ impl Property {
                             array.push(array_vector);
                         }
                     }
-                    "m_DependentPackages" => {
+                    "m_DependentPackages" | "m_lstDestroyedObjects" => {
                         for _ in 0..len {
                             let array_string = ArrayType::String(seq.next_element()?.unwrap());
                             array.push(array_string);
@@ -310,7 +518,21 @@ impl Property {
                     struct_type,
                 }
             }
-            _ => unimplemented!(),
+            _ => {
+                let mut bytes = Vec::new();
+                for _ in 0..size {
+                    bytes.push(seq.next_element()?.unwrap());
+                }
+                Property::Unknown {
+                    name_id,
+                    _osef1,
+                    type_id,
+                    _osef2,
+                    size,
+                    _osef3,
+                    bytes: bytes.into(),
+                }
+            }
         };
         Ok(property)
     }
@@ -338,6 +560,7 @@ impl Property {
             Property::StringRef { .. } => size + 4,
             Property::Struct { struct_type, .. } => size + struct_type.size()? + 8,
             Property::None { .. } => 8,
+            Property::Unknown { bytes, .. } => size + bytes.len(),
         })
     }
 }
@@ -0,0 +1,314 @@
+use anyhow::{anyhow, bail, Result};
+
+use super::{
+    data::{ArrayType, Data, Property},
+    player::Player,
+};
+use crate::save_data::{
+    mass_effect_1_le::{item_db::DbItem, player::ItemLevel},
+    RcCell, RcRef,
+};
+
+/// A `BioItemXModdableSaveObject` or a `BioItemXModSaveObject`
+#[derive(Clone, PartialEq)]
+pub struct Item {
+    pub object_id: i32,
+    pub item_id: RcCell<i32>,
+    pub manufacturer_id: RcCell<i32>,
+    pub item_level: RcCell<u8>,
+}
+
+impl Item {
+    pub fn db_item(&self) -> DbItem {
+        DbItem { item_id: self.item_id.get(), manufacturer_id: self.manufacturer_id.get() }
+    }
+
+    pub fn set_db_item(&self, db_item: DbItem) {
+        self.item_id.set(db_item.item_id);
+        self.manufacturer_id.set(db_item.manufacturer_id);
+    }
+}
+
+/// An `ArrayProperty` of item object ids, `0` is an empty slot
+#[derive(Clone, PartialEq)]
+pub struct ItemArray(RcRef<Property>);
+
+impl ItemArray {
+    pub fn object_ids(&self) -> Vec<i32> {
+        match *self.0.borrow() {
+            Property::Array { ref array, .. } => array
+                .iter()
+                .filter_map(|item| match item {
+                    ArrayType::Object(object_id) => Some(*object_id),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Only the reference is removed, the item object stays in the save
+    pub fn remove(&self, idx: usize) -> Result<()> {
+        let mut property = self.0.borrow_mut();
+        if let Property::Array { ref mut array, .. } = *property {
+            if idx >= array.len() {
+                bail!("No item at index {}", idx);
+            }
+            array.remove(idx);
+        }
+        property.update_size()
+    }
+
+    fn set(&self, idx: usize, object_id: i32) {
+        if let Property::Array { ref mut array, .. } = *self.0.borrow_mut() {
+            if let Some(item) = array.get_mut(idx) {
+                *item = ArrayType::Object(object_id);
+            }
+        }
+    }
+
+    fn push(&self, object_id: i32) -> Result<()> {
+        let mut property = self.0.borrow_mut();
+        if let Property::Array { ref mut array, .. } = *property {
+            array.push(ArrayType::Object(object_id));
+        }
+        property.update_size()
+    }
+}
+
+pub struct Inventory {
+    pub items: ItemArray,
+    pub buyback_items: Option<ItemArray>,
+}
+
+/// Shepard or a henchman
+pub struct Character {
+    pub tag: String,
+    pub equipment: ItemArray,
+    pub quick_slots: ItemArray,
+}
+
+impl Player {
+    pub fn item(&self, object_id: i32) -> Option<Item> {
+        if object_id <= 0 {
+            return None;
+        }
+
+        let properties = &self.get_data(object_id).properties;
+        let int = |name| match *self.find_property(properties, name)?.borrow() {
+            Property::Int { ref value, .. } => Some(RcCell::clone(value)),
+            _ => None,
+        };
+        let item_level = match *self.find_property(properties, "m_eSophistication")?.borrow() {
+            Property::Byte { ref value, .. } => RcCell::clone(value),
+            _ => return None,
+        };
+
+        Some(Item {
+            object_id,
+            item_id: int("m_nID")?,
+            manufacturer_id: int("m_manufacturer")?,
+            item_level,
+        })
+    }
+
+    fn item_array(&self, data: &Data, property_name: &str) -> Option<ItemArray> {
+        let property = self.find_property(&data.properties, property_name)?;
+        let is_array = matches!(*property.borrow(), Property::Array { .. });
+        is_array.then(|| ItemArray(RcRef::clone(property)))
+    }
+
+    pub fn inventory(&self) -> Option<Inventory> {
+        let current_game = self.get_data(self.find_object("CurrentGame")?);
+        let m_player = self.get_data(self.find_object_id(&current_game.properties, "m_Player")?);
+        let m_squad = self.get_data(self.find_object_id(&m_player.properties, "m_Squad")?);
+        let m_inventory = self.get_data(self.find_object_id(&m_squad.properties, "m_Inventory")?);
+
+        Some(Inventory {
+            items: self.item_array(m_inventory, "m_aItem")?,
+            buyback_items: self.item_array(current_game, "m_savedBuybackItems"),
+        })
+    }
+
+    /// Shepard first, then the henchmen that have been met
+    pub fn squad(&self) -> Vec<Character> {
+        let current_game = match self.find_object("CurrentGame") {
+            Some(object_id) => self.get_data(object_id),
+            None => return Vec::new(),
+        };
+
+        let character = |tag: String, object_id: i32| {
+            if object_id <= 0 {
+                return None;
+            }
+            let data = self.get_data(object_id);
+            Some(Character {
+                tag,
+                equipment: self.item_array(data, "m_aEquipped")?,
+                quick_slots: self.item_array(data, "m_QuickSlotArray")?,
+            })
+        };
+
+        let shepard = self
+            .find_object_id(&current_game.properties, "m_Player")
+            .and_then(|object_id| character("Player".to_owned(), object_id));
+
+        let mut henchmen = Vec::new();
+        if let Some(m_henchmen) = self.find_property(&current_game.properties, "m_Henchmen") {
+            if let Property::Array { ref array, .. } = *m_henchmen.borrow() {
+                for henchman in array {
+                    let properties = match henchman {
+                        ArrayType::Properties(properties) => properties,
+                        _ => continue,
+                    };
+                    let tag = match self.find_property(properties, "Tag").map(|p| p.borrow()) {
+                        Some(tag) => match *tag {
+                            Property::Name { ref value_name_id, .. } => {
+                                self.get_name(value_name_id.get())
+                            }
+                            _ => continue,
+                        },
+                        None => continue,
+                    };
+                    // Shepard is also in the henchmen
+                    if tag == "Player" {
+                        continue;
+                    }
+                    let object_id = self.find_object_id(properties, "Save").unwrap_or_default();
+                    henchmen.extend(character(tag, object_id));
+                }
+            }
+        }
+
+        shepard.into_iter().chain(henchmen).collect()
+    }
+
+    /// Adds a new `BioItemXModdableSaveObject` at the end of `items`
+    pub fn add_item(&mut self, items: &ItemArray, db_item: DbItem) -> Result<i32> {
+        let object_id = self.new_item(db_item, ItemLevel::None)?;
+        items.push(object_id)?;
+        Ok(object_id)
+    }
+
+    /// Replaces the item in the `slot` of the `equipment`, the previous one is moved to the inventory
+    pub fn equip_item(
+        &mut self, equipment: &ItemArray, slot: usize, db_item: Option<DbItem>,
+        item_level: ItemLevel,
+    ) -> Result<()> {
+        let previous = match equipment.object_ids().get(slot) {
+            Some(object_id) => *object_id,
+            None => return Ok(()),
+        };
+        if previous > 0 {
+            let inventory = self.inventory().ok_or_else(|| anyhow!("No inventory in this save"))?;
+            inventory.items.push(previous)?;
+        }
+
+        let object_id = match db_item {
+            Some(db_item) => self.new_item(db_item, item_level)?,
+            None => 0,
+        };
+        equipment.set(slot, object_id);
+        Ok(())
+    }
+
+    fn new_item(&mut self, db_item: DbItem, item_level: ItemLevel) -> Result<i32> {
+        let template_id = self
+            .find_object("BioItemXModdableSaveObject")
+            .ok_or_else(|| anyhow!("No item to copy in this save"))?;
+        let name = |name: &str| self.name_id(name).ok_or_else(|| anyhow!("No `{}` name", name));
+
+        let template = &self.get_data(template_id).properties;
+        let owner_class = self.find_object_id(template, "OwnerClass").unwrap_or_default();
+        let owner_name = {
+            let class_name = match owner_class {
+                id if id < 0 => self.get_name(self.get_class(id).class_name_id),
+                _ => String::from("BioItemXModdable"),
+            };
+            let prefix = format!("Player.{}_", class_name);
+            let next_idx = (1..=self.objects.len() as i32)
+                .filter_map(|object_id| {
+                    let properties = &self.get_data(object_id).properties;
+                    let owner_name = self.find_property(properties, "OwnerName")?.borrow();
+                    match *owner_name {
+                        Property::Str { ref string, .. } => {
+                            string.borrow().strip_prefix(&prefix)?.parse::<usize>().ok()
+                        }
+                        _ => None,
+                    }
+                })
+                .max()
+                .map(|idx| idx + 1)
+                .unwrap_or_default();
+            format!("{}{}", prefix, next_idx)
+        };
+
+        let properties = vec![
+            Property::new_int(name("m_nID")?, name("IntProperty")?, db_item.item_id),
+            Property::new_byte(name("m_eSophistication")?, name("ByteProperty")?, item_level as u8),
+            Property::new_int(
+                name("m_manufacturer")?,
+                name("IntProperty")?,
+                db_item.manufacturer_id,
+            ),
+            Property::new_str(name("OwnerName")?, name("StrProperty")?, owner_name)?,
+            Property::new_object(name("OwnerClass")?, name("ObjectProperty")?, owner_class),
+            Property::new_none(name("None")?),
+        ];
+        let properties: Vec<_> = properties.into_iter().map(RcRef::new).collect();
+        let data = self.get_data(template_id).with_properties(properties.into());
+
+        Ok(self.add_object(template_id, data))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
+
+    #[test]
+    fn edit_inventory() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let (items_len, object_id) = {
+            let mut player = me1_save_game.player.borrow_mut();
+            let inventory = player.inventory().unwrap();
+            let items = inventory.items.object_ids();
+            assert!(!items.is_empty());
+            assert!(items.iter().all(|object_id| player.item(*object_id).is_some()));
+            assert!(inventory.buyback_items.is_some());
+
+            let squad = player.squad();
+            assert_eq!(squad[0].tag, "Player");
+            assert!(squad.iter().any(|character| character.tag == "hench_asari"));
+            assert!(squad.iter().all(|character| character.equipment.object_ids().len() == 5));
+
+            // Remove then add
+            assert!(inventory.items.remove(items.len()).is_err());
+            inventory.items.remove(0)?;
+            let db_item = DbItem { item_id: 285, manufacturer_id: 528 };
+            let object_id = player.add_item(&inventory.items, db_item)?;
+            (items.len(), object_id)
+        };
+
+        // Serialize, deserialize
+        let output = unreal::Serializer::to_vec(&me1_save_game)?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        let player = me1_save_game.player.borrow();
+        let items = player.inventory().unwrap().items.object_ids();
+        assert_eq!(items.len(), items_len);
+        assert_eq!(*items.last().unwrap(), object_id);
+        let item = player.item(object_id).unwrap();
+        assert_eq!(item.item_id.get(), 285);
+        assert_eq!(item.manufacturer_id.get(), 528);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 pub mod data;
+pub mod inventory;
 pub mod player;
+pub mod player_class;
+pub mod player_class_db;
 pub mod plot_db;
 pub mod state;
+pub mod world;
 
 use self::{player::*, state::*};
 
@@ -14,7 +18,7 @@ use serde::ser::SerializeTupleStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
 
-use super::{Dummy, List};
+use super::{Dummy, List, RcRef};
 use crate::unreal;
 
 #[rcize_fields]
@@ -26,7 +30,7 @@ pub struct Me1SaveGame {
     _no_mans_land: List<u8>,
     pub player: Player,
     pub state: State,
-    _world_save_package: Option<WorldSavePackage>,
+    pub world_save_package: Option<WorldSavePackage>,
 }
 
 impl Me1SaveGame {
@@ -50,7 +54,7 @@ impl Me1SaveGame {
                 Some({
                     let mut bytes = Vec::new();
                     zip.by_name("WorldSavePackage.sav")?.read_to_end(&mut bytes)?;
-                    unreal::Deserializer::from_bytes(&bytes)?
+                    WorldSavePackage::from_bytes(bytes)
                 })
             } else {
                 None
@@ -78,8 +82,8 @@ impl Me1SaveGame {
                 zipper.write_all(&state_data)?;
             }
             // WorldSavePackage
-            if let Some(ref world_save_package) = self._world_save_package {
-                let world_save_package_data = unreal::Serializer::to_vec(world_save_package)?;
+            if let Some(ref world_save_package) = *self.world_save_package() {
+                let world_save_package_data = world_save_package.borrow().to_bytes()?;
                 zipper.start_file("WorldSavePackage.sav", options)?;
                 zipper.write_all(&world_save_package_data)?;
             }
@@ -126,7 +130,7 @@ impl<'de> Deserialize<'de> for Me1SaveGame {
                     _no_mans_land: no_mans_land.into(),
                     player: player.into(),
                     state: state.into(),
-                    _world_save_package: world_save_package,
+                    world_save_package: RcRef::new(world_save_package.map(RcRef::new)),
                 })
             }
         }
@@ -147,7 +151,7 @@ impl serde::Serialize for Me1SaveGame {
             _no_mans_land,
             player: _,
             state: _,
-            _world_save_package,
+            world_save_package: _,
         } = self;
 
         let mut s = serializer.serialize_tuple_struct("Me1SaveGame", 4)?;
@@ -178,9 +182,35 @@ impl<'de> Deserialize<'de> for Me1MagicNumber {
     }
 }
 
-#[derive(Deserialize, Serialize, Clone)]
-pub struct WorldSavePackage {
-    data: List<u8>,
+/// `WorldSavePackage.sav` is a package like `player.sav`, with the state of the visited maps.
+/// It is kept as is if it does not parse, the rest of the save stays editable.
+#[derive(Clone)]
+pub enum WorldSavePackage {
+    Parsed(RcRef<Player>),
+    Raw(List<u8>),
+}
+
+impl WorldSavePackage {
+    fn from_bytes(bytes: Vec<u8>) -> Self {
+        match unreal::Deserializer::from_bytes(&bytes) {
+            Ok(player) => WorldSavePackage::Parsed(RcRef::new(player)),
+            Err(_) => WorldSavePackage::Raw(bytes.into()),
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            WorldSavePackage::Parsed(player) => Ok(unreal::Serializer::to_vec(&*player.borrow())?),
+            WorldSavePackage::Raw(bytes) => Ok(bytes.to_vec()),
+        }
+    }
+
+    pub fn parsed(&self) -> Option<&RcRef<Player>> {
+        match self {
+            WorldSavePackage::Parsed(player) => Some(player),
+            WorldSavePackage::Raw(_) => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,14 @@
 use std::fmt;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use serde::de;
 use serde::ser::SerializeTupleStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use super::{data::Data, List};
+use super::{
+    data::{ArrayType, Data, PropertiesOwner, Property, PropertyType, StructType},
+    List,
+};
 use crate::save_data::Dummy;
 use crate::save_data::RcRef;
 use crate::unreal;
@@ -19,7 +22,7 @@ pub struct Player {
     pub names: RcRef<List<Name>>,
     classes: List<Class>,
     pub objects: List<Object>,
-    _no_mans_land2: List<u8>,
+    depends: List<Vec<i32>>,
     datas: List<Data>,
 }
 
@@ -39,6 +42,207 @@ impl Player {
     pub fn get_data(&self, i: i32) -> &Data {
         &self.datas[i as usize - 1]
     }
+
+    pub fn name_id(&self, name: &str) -> Option<u32> {
+        self.names
+            .borrow()
+            .iter()
+            .position(|n| !n.is_duplicate && *n.string.borrow() == name)
+            .map(|id| id as u32)
+    }
+
+    /// The id of `name`, added at the end of the names if missing
+    pub fn add_name(&self, name: &str) -> u32 {
+        if let Some(name_id) = self.name_id(name) {
+            return name_id;
+        }
+
+        let mut names = self.names.borrow_mut();
+        let mut new_name = names[0].clone();
+        new_name.string = RcRef::new(name.to_owned());
+        new_name.is_duplicate = false;
+        names.push(new_name);
+        names.len() as u32 - 1
+    }
+
+    pub fn find_object(&self, object_name: &str) -> Option<i32> {
+        self.objects
+            .iter()
+            .position(|object| self.get_name(object.object_name_id) == object_name)
+            .map(|idx| idx as i32 + 1)
+    }
+
+    pub fn find_property<'a>(
+        &self, properties: &'a List<RcRef<Property>>, property_name: &str,
+    ) -> Option<&'a RcRef<Property>> {
+        properties
+            .iter()
+            .find(|property| self.get_name(property.borrow().name_id()) == property_name)
+    }
+
+    pub fn find_object_id(
+        &self, properties: &List<RcRef<Property>>, property_name: &str,
+    ) -> Option<i32> {
+        self.find_property(properties, property_name).and_then(|property| {
+            match *property.borrow() {
+                Property::Object { object_id, .. } => Some(object_id),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn new_property(
+        &self, name: &str, property_type: PropertyType, struct_name: &str,
+    ) -> Result<Property> {
+        if matches!(property_type, PropertyType::Struct) && struct_name.is_empty() {
+            bail!("A struct needs a struct name");
+        }
+
+        let name_id = self.add_name(name);
+        let type_id = self.add_name(property_type.type_name());
+        Ok(match property_type {
+            PropertyType::Int => Property::new_int(name_id, type_id, 0),
+            PropertyType::Float => Property::new_float(name_id, type_id, 0.0),
+            PropertyType::Bool => Property::new_bool(name_id, type_id, false),
+            PropertyType::Str => Property::new_str(name_id, type_id, String::new())?,
+            PropertyType::Struct => {
+                let struct_name_id = self.add_name(struct_name);
+                Property::new_struct(name_id, type_id, struct_name_id, self.add_name("None"))
+            }
+            PropertyType::Array => Property::new_array(name_id, type_id),
+        })
+    }
+
+    pub fn properties(&self, owner: &PropertiesOwner) -> Vec<RcRef<Property>> {
+        match owner {
+            PropertiesOwner::Object(object_id) => self.get_data(*object_id).properties.to_vec(),
+            PropertiesOwner::Struct(property) => match *property.borrow() {
+                Property::Struct {
+                    struct_type: StructType::Properties(ref properties), ..
+                } => properties.to_vec(),
+                _ => Vec::new(),
+            },
+            PropertiesOwner::ArrayItem(property, idx) => match *property.borrow() {
+                Property::Array { ref array, .. } => match array.get(*idx) {
+                    Some(ArrayType::Properties(properties)) => properties.to_vec(),
+                    _ => Vec::new(),
+                },
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    fn with_properties<T>(
+        &mut self, owner: &PropertiesOwner, f: impl FnOnce(&mut List<RcRef<Property>>) -> T,
+    ) -> Option<T> {
+        match owner {
+            PropertiesOwner::Object(object_id) => {
+                self.datas.get_mut(*object_id as usize - 1).map(|data| f(&mut data.properties))
+            }
+            PropertiesOwner::Struct(property) => match *property.borrow_mut() {
+                Property::Struct {
+                    struct_type: StructType::Properties(ref mut properties),
+                    ..
+                } => Some(f(properties)),
+                _ => None,
+            },
+            PropertiesOwner::ArrayItem(property, idx) => match *property.borrow_mut() {
+                Property::Array { ref mut array, .. } => match array.get_mut(*idx) {
+                    Some(ArrayType::Properties(properties)) => Some(f(properties)),
+                    _ => None,
+                },
+                _ => None,
+            },
+        }
+    }
+
+    /// Adds a property before the `None` that ends the properties of `owner`
+    pub fn add_property(
+        &mut self, owner: &PropertiesOwner, name: &str, property_type: PropertyType,
+        struct_name: &str,
+    ) -> Result<()> {
+        if name.is_empty() || name == "None" {
+            bail!("Invalid property name `{}`", name);
+        }
+        let property = self.new_property(name, property_type, struct_name)?;
+        let name_id = property.name_id();
+
+        self.with_properties(owner, |properties| {
+            if properties.iter().any(|p| p.borrow().name_id() == name_id) {
+                bail!("`{}` already exists", name);
+            }
+            let idx = properties.len().saturating_sub(1);
+            properties.insert(idx, property.into());
+            Ok(())
+        })
+        .ok_or_else(|| anyhow!("No properties to add `{}` to", name))?
+    }
+
+    pub fn remove_property(&mut self, owner: &PropertiesOwner, idx: usize) {
+        self.with_properties(owner, |properties| {
+            // Never the `None`
+            if idx + 1 < properties.len() {
+                properties.remove(idx);
+            }
+        });
+    }
+
+    /// Adds an empty struct at the end of an array of structs, the other arrays are refused.
+    /// The items of an empty array have no type to check, `item_type` must then be given.
+    pub fn add_array_item(
+        &self, property: &RcRef<Property>, item_type: Option<PropertyType>,
+    ) -> Result<()> {
+        let none_id = self.add_name("None");
+        match *property.borrow_mut() {
+            Property::Array { ref mut array, .. } => {
+                let is_struct_array = if array.is_empty() {
+                    let item_type = item_type
+                        .ok_or_else(|| anyhow!("The item type of an empty array is unknown"))?;
+                    matches!(item_type, PropertyType::Struct)
+                } else {
+                    array.iter().all(|item| matches!(item, ArrayType::Properties(_)))
+                };
+                if !is_struct_array {
+                    bail!("Only structs can be added to an array");
+                }
+
+                let properties = vec![RcRef::new(Property::new_none(none_id))];
+                array.push(ArrayType::Properties(properties.into()));
+                Ok(())
+            }
+            _ => bail!("Not an array"),
+        }
+    }
+
+    pub fn remove_array_item(&self, property: &RcRef<Property>, idx: usize) {
+        if let Property::Array { ref mut array, .. } = *property.borrow_mut() {
+            if idx < array.len() {
+                array.remove(idx);
+            }
+        }
+    }
+
+    /// Adds an object of the same class as `template_id`, returns the id of the new object
+    pub fn add_object(&mut self, template_id: i32, data: Data) -> i32 {
+        let idx = template_id as usize - 1;
+        let mut object = self.objects[idx].clone();
+        // An export is named by its name and number, the new one takes the next free number
+        object.name_number = self
+            .objects
+            .iter()
+            .filter(|other| other.object_name_id == object.object_name_id)
+            .map(|other| other.name_number)
+            .max()
+            .unwrap_or_default()
+            + 1;
+        // Only the classes, the other objects it depends on are not shared with the new one
+        let depends = self.depends[idx].iter().copied().filter(|id| *id < 0).collect();
+
+        self.objects.push(object);
+        self.depends.push(depends);
+        self.datas.push(data);
+        self.objects.len() as i32
+    }
 }
 
 impl<'de> Deserialize<'de> for Player {
@@ -87,10 +291,10 @@ impl<'de> Deserialize<'de> for Player {
                     objects.push(seq.next_element()?.unwrap());
                 }
 
-                // No man's land 2
-                let mut no_mans_land2 = Vec::new();
-                for _ in 0..(header.data_offset - header.no_mans_land_offset) {
-                    no_mans_land2.push(seq.next_element()?.unwrap());
+                // Depends
+                let mut depends = Vec::new();
+                for _ in objects.iter() {
+                    depends.push(seq.next_element()?.unwrap());
                 }
 
                 // Data
@@ -108,7 +312,7 @@ impl<'de> Deserialize<'de> for Player {
                     names: RcRef::new(names.into()),
                     classes: classes.into(),
                     objects: objects.into(),
-                    _no_mans_land2: no_mans_land2.into(),
+                    depends: depends.into(),
                     datas: datas.into(),
                 })
             }
@@ -131,11 +335,16 @@ impl serde::Serialize for Player {
             names,
             classes,
             objects,
-            _no_mans_land2,
+            depends,
             datas,
         } = self;
         let names = names.borrow();
 
+        // Tailles des propriétés
+        for data in datas.iter() {
+            data.update_sizes().map_err(Error::custom)?;
+        }
+
         // Calculs d'offsets
         let mut header = header.clone();
 
@@ -145,9 +354,16 @@ impl serde::Serialize for Player {
             header.classes_offset += name.size().map_err(Error::custom)? as u32;
         }
 
+        header.objects_len = objects.len() as u32;
         header.objects_offset = header.classes_offset + (classes.len() * 28) as u32;
-        header.no_mans_land_offset = header.objects_offset + (objects.len() * 72) as u32;
-        header.data_offset = header.no_mans_land_offset + _no_mans_land2.0.len() as u32;
+        header.depends_offset = header.objects_offset + (objects.len() * 72) as u32;
+        header.data_offset = header.depends_offset;
+        for object_depends in depends.iter() {
+            header.data_offset += (object_depends.len() * 4 + 4) as u32;
+        }
+        header.generation_names_len = header.name_len;
+        header.generation_objects_len = header.objects_len;
+        header.generation_net_objects_len = header.objects_len;
 
         let mut objects = objects.clone();
         {
@@ -169,7 +385,7 @@ impl serde::Serialize for Player {
         s.serialize_field(&*names)?;
         s.serialize_field(classes)?;
         s.serialize_field(&objects)?;
-        s.serialize_field(_no_mans_land2)?;
+        s.serialize_field(depends)?;
         s.serialize_field(datas)?;
         s.end()
     }
@@ -188,8 +404,13 @@ struct Header {
     objects_offset: u32,
     classes_len: u32,
     classes_offset: u32,
-    no_mans_land_offset: u32,
-    _osef1: Dummy<68>,
+    depends_offset: u32,
+    _guid: Dummy<16>,
+    _generations_len: u32,
+    generation_objects_len: u32,
+    generation_names_len: u32,
+    generation_net_objects_len: u32,
+    _osef1: Dummy<36>,
     _compression: u32,
     _osef2: Dummy<12>,
 }
@@ -226,7 +447,7 @@ pub struct Object {
     class_parent_id: u32,
     link_id: u32,
     pub object_name_id: u32,
-    value_id: u32,
+    name_number: u32,
     archtype_id: u32,
     flag: u64,
     pub data_size: u32,
@@ -243,7 +464,7 @@ mod test {
     use zip::ZipArchive;
 
     use super::*;
-    use crate::unreal;
+    use crate::{save_data::mass_effect_1::Me1SaveGame, unreal};
 
     #[test]
     fn deserialize_serialize() -> Result<()> {
@@ -279,4 +500,106 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn add_object() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let mut player = me1_save_game.player.borrow_mut();
+
+        let template_id = player.find_object("CurrentGame").unwrap();
+        let data = player.get_data(template_id).clone();
+        let object_id = player.add_object(template_id, data.clone());
+        let object_id_2 = player.add_object(template_id, data);
+
+        let names: Vec<_> = player
+            .objects
+            .iter()
+            .map(|object| (object.object_name_id, object.name_number))
+            .collect();
+        for object_id in [object_id, object_id_2] {
+            let name = names[object_id as usize - 1];
+            assert_eq!(names.iter().filter(|other| **other == name).count(), 1);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_remove_properties() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let names_len = {
+            let mut player = me1_save_game.player.borrow_mut();
+            let names_len = player.names.borrow().len();
+            let current_game = PropertiesOwner::Object(player.find_object("CurrentGame").unwrap());
+            let properties_len = player.properties(&current_game).len();
+
+            player.add_property(&current_game, "m_nNewInt", PropertyType::Int, "")?;
+            player.add_property(&current_game, "m_NewStruct", PropertyType::Struct, "NewStruct")?;
+            player.add_property(&current_game, "m_aNewArray", PropertyType::Array, "")?;
+            player.add_property(&current_game, "m_fTemp", PropertyType::Float, "")?;
+            assert!(player
+                .add_property(&current_game, "m_nNewInt", PropertyType::Int, "")
+                .is_err());
+            assert!(player
+                .add_property(&current_game, "m_Struct", PropertyType::Struct, "")
+                .is_err());
+
+            let properties = player.properties(&current_game);
+            assert_eq!(properties.len(), properties_len + 4);
+            // Always ends with `None`
+            assert!(matches!(*properties.last().unwrap().borrow(), Property::None { .. }));
+
+            // Nested
+            let new_struct =
+                player.find_property(&properties.into(), "m_NewStruct").cloned().unwrap();
+            let new_struct = PropertiesOwner::Struct(new_struct);
+            player.add_property(&new_struct, "m_bNewBool", PropertyType::Bool, "")?;
+            let new_array = player
+                .find_property(&player.properties(&current_game).into(), "m_aNewArray")
+                .cloned()
+                .unwrap();
+            assert!(player.add_array_item(&new_array, None).is_err());
+            assert!(player.add_array_item(&new_array, Some(PropertyType::Int)).is_err());
+            player.add_array_item(&new_array, Some(PropertyType::Struct))?;
+            player.add_property(
+                &PropertiesOwner::ArrayItem(new_array, 0),
+                "m_sNewStr",
+                PropertyType::Str,
+                "",
+            )?;
+
+            // Remove
+            let idx = player.properties(&current_game).len() - 2;
+            player.remove_property(&current_game, idx);
+
+            names_len
+        };
+
+        // Serialize, deserialize
+        let output = unreal::Serializer::to_vec(&me1_save_game)?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        let player = me1_save_game.player.borrow();
+        // m_nNewInt, m_NewStruct, NewStruct, m_aNewArray, m_fTemp, m_bNewBool, m_sNewStr
+        assert_eq!(player.names.borrow().len(), names_len + 7);
+
+        let current_game: List<_> = player
+            .properties(&PropertiesOwner::Object(player.find_object("CurrentGame").unwrap()))
+            .into();
+        assert!(player.find_property(&current_game, "m_nNewInt").is_some());
+        assert!(player.find_property(&current_game, "m_fTemp").is_none());
+
+        let new_struct = player.find_property(&current_game, "m_NewStruct").cloned().unwrap();
+        let new_struct: List<_> = player.properties(&PropertiesOwner::Struct(new_struct)).into();
+        assert!(player.find_property(&new_struct, "m_bNewBool").is_some());
+
+        let new_array = player.find_property(&current_game, "m_aNewArray").cloned().unwrap();
+        let item: List<_> = player.properties(&PropertiesOwner::ArrayItem(new_array, 0)).into();
+        assert!(player.find_property(&item, "m_sNewStr").is_some());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,450 @@
+use anyhow::{anyhow, Result};
+
+use super::{
+    data::{ArrayType, PropertiesOwner, Property, PropertyType},
+    player::Player,
+    player_class_db::Me1PlayerClass,
+};
+use crate::save_data::{
+    mass_effect_1_le::player::{ComplexTalent, ItemLevel, SimpleTalent},
+    List, RcCell, RcRef,
+};
+
+// New Game + things
+const IGNORED_SIMPLES: &[i32] = &[262, 263, 266, 265, 267, 264];
+const IGNORED_COMPLEXES: &[i32] = &[
+    108, // Charm
+    109, // Intimidate
+    259, // Spectre
+];
+
+impl Player {
+    /// The `BioPawnBehaviorSaveObject` of Shepard
+    pub fn shepard_id(&self) -> Option<i32> {
+        let current_game = self.get_data(self.find_object("CurrentGame")?);
+        self.find_object_id(&current_game.properties, "m_Player").filter(|id| *id > 0)
+    }
+
+    /// The `EBioPartyMemberClassBase` of Shepard
+    pub fn class_base(&self) -> Option<String> {
+        let properties = &self.get_data(self.shepard_id()?).properties;
+        match *self.find_property(properties, "m_ClassBase")?.borrow() {
+            Property::Name { ref value_name_id, .. } => Some(self.get_name(value_name_id.get())),
+            _ => None,
+        }
+    }
+
+    /// `m_Gender` is only saved for a female Shepard
+    pub fn is_female(&self) -> bool {
+        let properties = match self.shepard_id() {
+            Some(shepard_id) => &self.get_data(shepard_id).properties,
+            None => return false,
+        };
+        match self.find_property(properties, "m_Gender").map(|property| property.borrow()) {
+            Some(property) => match *property {
+                Property::Name { ref value_name_id, .. } => {
+                    self.get_name(value_name_id.get()) == "BIO_ATTRIBUTE_PAWN_GENDER_FEMALE"
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    /// The level of Shepard
+    pub fn xp_level(&self) -> Option<i32> {
+        self.shepard_int("m_XPLevel").map(|value| value.get())
+    }
+
+    pub fn specialization_bonus_id(&self) -> Option<i32> {
+        self.shepard_int("m_SpecializationBonusID").map(|value| value.get())
+    }
+
+    fn shepard_int(&self, name: &str) -> Option<RcCell<i32>> {
+        let properties = &self.get_data(self.shepard_id()?).properties;
+        match *self.find_property(properties, name)?.borrow() {
+            Property::Int { ref value, .. } | Property::StringRef { ref value, .. } => {
+                Some(RcCell::clone(value))
+            }
+            _ => None,
+        }
+    }
+
+    /// The properties with their default value are not saved, they are added back if needed
+    fn set_shepard_int(&mut self, name: &str, value: i32) -> Result<()> {
+        if self.shepard_int(name).is_none() {
+            let shepard_id = self.shepard_id().ok_or_else(|| anyhow!("No Shepard in this save"))?;
+            self.add_property(&PropertiesOwner::Object(shepard_id), name, PropertyType::Int, "")?;
+        }
+        if let Some(cell) = self.shepard_int(name) {
+            cell.set(value);
+        }
+        Ok(())
+    }
+
+    fn refund_talent_points(&mut self, refunded: i32) -> Result<()> {
+        if refunded > 0 {
+            let talent_points = self.shepard_int("m_TalentPoints").map(|tp| tp.get());
+            self.set_shepard_int("m_TalentPoints", talent_points.unwrap_or_default() + refunded)?;
+        }
+        Ok(())
+    }
+
+    fn shepard_talents(&self, name: &str) -> Option<RcRef<Property>> {
+        let properties = &self.get_data(self.shepard_id()?).properties;
+        self.find_property(properties, name).cloned()
+    }
+
+    fn talent_int(&self, talent: &List<RcRef<Property>>, name: &str) -> i32 {
+        match self.find_property(talent, name).map(|property| property.borrow()) {
+            Some(property) => match *property {
+                Property::Int { ref value, .. } => value.get(),
+                _ => 0,
+            },
+            None => 0,
+        }
+    }
+
+    fn new_talent(
+        &self, ints: &[(&str, i32)], arrays: &[(&str, &[RcCell<i32>])],
+    ) -> Result<ArrayType> {
+        let int_type_id = self.add_name("IntProperty");
+        let array_type_id = self.add_name("ArrayProperty");
+
+        let mut properties: Vec<_> = ints
+            .iter()
+            .map(|&(name, value)| Property::new_int(self.add_name(name), int_type_id, value))
+            .collect();
+        for &(name, values) in arrays {
+            let mut property = Property::new_array(self.add_name(name), array_type_id);
+            if let Property::Array { ref mut array, .. } = property {
+                array.extend(values.iter().map(|value| ArrayType::Int(value.get().into())));
+            }
+            property.update_size()?;
+            properties.push(property);
+        }
+        properties.push(Property::new_none(self.add_name("None")));
+
+        let properties: Vec<_> = properties.into_iter().map(RcRef::new).collect();
+        Ok(ArrayType::Properties(properties.into()))
+    }
+
+    fn new_simple_talent(&self, talent: &SimpleTalent) -> Result<ArrayType> {
+        self.new_talent(
+            &[("m_TalentID", talent.talent_id()), ("m_Ranks", talent.current_rank())],
+            &[],
+        )
+    }
+
+    fn new_complex_talent(&self, talent: &ComplexTalent) -> Result<ArrayType> {
+        self.new_talent(
+            &[
+                ("m_TalentID", talent.talent_id()),
+                ("m_Ranks", talent.current_rank()),
+                ("m_MaxRank", talent.max_rank()),
+                ("m_LevelOffset", talent.level_offset()),
+                ("m_LevelsPerRank", talent.levels_per_rank()),
+                ("m_VisualOrder", talent.visual_order()),
+            ],
+            &[
+                ("m_PrereqTalentIDArray", &talent.prereq_talent_ids()),
+                ("m_PrereqTalentRankArray", &talent.prereq_talent_ranks()),
+            ],
+        )
+    }
+
+    /// Replaces the talents of an array but the ones in `keep`.
+    /// Returns the ranks spent in the removed talents.
+    fn replace_talents(
+        &self, talents: &RcRef<Property>, keep: &[i32], new_talents: Vec<ArrayType>,
+    ) -> Result<i32> {
+        let mut spent_ranks = 0;
+        let mut property = talents.borrow_mut();
+        if let Property::Array { ref mut array, .. } = *property {
+            array.retain(|talent| match talent {
+                ArrayType::Properties(talent) => {
+                    let is_kept = keep.contains(&self.talent_int(talent, "m_TalentID"));
+                    if !is_kept {
+                        spent_ranks += self.talent_int(talent, "m_Ranks");
+                    }
+                    is_kept
+                }
+                _ => true,
+            });
+            array.extend(new_talents);
+        }
+        property.update_size()?;
+        Ok(spent_ranks)
+    }
+
+    /// Rewrites the class, the talents, the auto level-up template and the gear of Shepard.
+    /// The talent points spent are refunded and the specialization is reset.
+    pub fn change_class(&mut self, player_class: &Me1PlayerClass) -> Result<()> {
+        let Me1PlayerClass {
+            class_base,
+            localized_class_name,
+            auto_levelup_template_id,
+            simple_talents,
+            complex_talents,
+            armor,
+            omni_tool,
+            bio_amp,
+            ..
+        } = player_class;
+        let shepard_id = self.shepard_id().ok_or_else(|| anyhow!("No Shepard in this save"))?;
+
+        // Class
+        {
+            let properties = &self.get_data(shepard_id).properties;
+            let class = self
+                .find_property(properties, "m_ClassBase")
+                .ok_or_else(|| anyhow!("No `m_ClassBase` in this save"))?;
+            if let Property::Name { ref value_name_id, .. } = *class.borrow() {
+                value_name_id.set(self.add_name(class_base));
+            }
+        }
+        // A `StringRefProperty`, always saved
+        let class_name = self
+            .shepard_int("m_LocalizedClassName")
+            .ok_or_else(|| anyhow!("No `m_LocalizedClassName` in this save"))?;
+        class_name.set(localized_class_name.0);
+        self.set_shepard_int("m_AutoLevelUpTemplateID", *auto_levelup_template_id)?;
+        self.set_shepard_int("m_SpecializationBonusID", -1)?;
+
+        // Talents
+        if let Some(talents) = self.shepard_talents("m_aSimpleTalents") {
+            let new_talents = simple_talents
+                .iter()
+                .map(|talent| self.new_simple_talent(talent))
+                .collect::<Result<_>>()?;
+            // Given by the class, they cost no points
+            self.replace_talents(&talents, IGNORED_SIMPLES, new_talents)?;
+        }
+        if let Some(talents) = self.shepard_talents("m_aComplexTalents") {
+            let new_talents = complex_talents
+                .iter()
+                .map(|talent| self.new_complex_talent(talent))
+                .collect::<Result<_>>()?;
+            let spent_ranks = self.replace_talents(&talents, IGNORED_COMPLEXES, new_talents)?;
+            self.refund_talent_points(spent_ranks)?;
+        }
+
+        // Gear
+        if let Some(shepard) = self.squad().into_iter().next() {
+            self.equip_item(&shepard.equipment, 1, *armor, ItemLevel::I)?;
+            self.equip_item(&shepard.equipment, 3, *omni_tool, ItemLevel::I)?;
+            self.equip_item(&shepard.equipment, 4, *bio_amp, ItemLevel::I)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the specialization talent by the one at `spec_idx` in `spec_ids`, its ranks are refunded.
+    /// `spec_ids` starts with the class talent of `player_class`, which is no specialization.
+    pub fn set_specialization(
+        &mut self, player_class: &Me1PlayerClass, spec_ids: &[i32], spec_idx: usize,
+    ) -> Result<()> {
+        let new_spec = match spec_ids.get(spec_idx) {
+            Some(new_spec) => *new_spec,
+            None => return Ok(()),
+        };
+        let max_rank = if spec_idx == 0 {
+            player_class
+                .complex_talents
+                .iter()
+                .find(|talent| talent.talent_id() == new_spec)
+                .map(|talent| talent.max_rank())
+                .ok_or_else(|| anyhow!("Talent {} is not a class talent", new_spec))?
+        } else {
+            player_class.specialization_max_rank
+        };
+        let talents = self
+            .shepard_talents("m_aComplexTalents")
+            .ok_or_else(|| anyhow!("No `m_aComplexTalents` in this save"))?;
+
+        let mut refunded = None;
+        if let Property::Array { ref array, .. } = *talents.borrow() {
+            let current_spec = array.iter().find_map(|talent| match talent {
+                ArrayType::Properties(talent)
+                    if spec_ids.contains(&self.talent_int(talent, "m_TalentID")) =>
+                {
+                    Some(talent)
+                }
+                _ => None,
+            });
+            if let Some(current_spec) = current_spec {
+                refunded = Some(self.talent_int(current_spec, "m_Ranks"));
+
+                for (name, value) in
+                    [("m_TalentID", new_spec), ("m_Ranks", 0), ("m_MaxRank", max_rank)]
+                {
+                    if let Some(property) = self.find_property(current_spec, name) {
+                        if let Property::Int { value: ref cell, .. } = *property.borrow() {
+                            cell.set(value);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(refunded) = refunded {
+            self.refund_talent_points(refunded)?;
+            self.set_shepard_int(
+                "m_SpecializationBonusID",
+                if spec_idx == 0 { -1 } else { new_spec },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_1::{player_class_db::Me1PlayerClassDb, Me1SaveGame},
+            mass_effect_1_le::player::Me1LeClass,
+        },
+        unreal,
+    };
+
+    fn complex_talent_ids(player: &Player) -> Vec<i32> {
+        match *player.shepard_talents("m_aComplexTalents").unwrap().borrow() {
+            Property::Array { ref array, .. } => array
+                .iter()
+                .filter_map(|talent| match talent {
+                    ArrayType::Properties(talent) => Some(player.talent_int(talent, "m_TalentID")),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn complex_talent(player: &Player, talent_id: i32) -> Option<List<RcRef<Property>>> {
+        match *player.shepard_talents("m_aComplexTalents")?.borrow() {
+            Property::Array { ref array, .. } => array.iter().find_map(|talent| match talent {
+                ArrayType::Properties(talent)
+                    if player.talent_int(talent, "m_TalentID") == talent_id =>
+                {
+                    Some(talent.clone())
+                }
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    fn talent_ints(player: &Player, talent: &List<RcRef<Property>>, name: &str) -> Vec<i32> {
+        match player.find_property(talent, name).map(|property| property.borrow()) {
+            Some(property) => match *property {
+                Property::Array { ref array, .. } => array
+                    .iter()
+                    .filter_map(|item| match item {
+                        ArrayType::Int(value) => Some(value.get()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+            None => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn adept_matches_save() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_player_class_db.ron")?;
+        let player_class_db: Me1PlayerClassDb = ron::from_str(&input)?;
+        let adept = player_class_db.iter().find(|class| class.player_class == Me1LeClass::Adept);
+        let adept = adept.unwrap();
+
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let player = me1_save_game.player.borrow();
+
+        assert_eq!(player.class_base().unwrap(), adept.class_base);
+        let localized_class_name = player.shepard_int("m_LocalizedClassName").unwrap().get();
+        assert_eq!(localized_class_name, adept.localized_class_name.0);
+        let auto_levelup_template_id = player.shepard_int("m_AutoLevelUpTemplateID").unwrap().get();
+        assert_eq!(auto_levelup_template_id, adept.auto_levelup_template_id);
+
+        for talent in &adept.complex_talents {
+            // This Adept is a Bastion, the specialization replaces the class talent
+            let (talent_id, max_rank) = match talent.talent_id() {
+                126 => (157, adept.specialization_max_rank),
+                talent_id => (talent_id, talent.max_rank()),
+            };
+            let saved = complex_talent(&player, talent_id).unwrap();
+            assert_eq!(player.talent_int(&saved, "m_MaxRank"), max_rank);
+            assert_eq!(player.talent_int(&saved, "m_LevelOffset"), talent.level_offset());
+            assert_eq!(player.talent_int(&saved, "m_LevelsPerRank"), talent.levels_per_rank());
+            assert_eq!(player.talent_int(&saved, "m_VisualOrder"), talent.visual_order());
+            let prereq_ids: Vec<_> = talent.prereq_talent_ids().iter().map(|id| id.get()).collect();
+            assert_eq!(talent_ints(&player, &saved, "m_PrereqTalentIDArray"), prereq_ids);
+            let prereq_ranks: Vec<_> =
+                talent.prereq_talent_ranks().iter().map(|rank| rank.get()).collect();
+            assert_eq!(talent_ints(&player, &saved, "m_PrereqTalentRankArray"), prereq_ranks);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn change_class() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_player_class_db.ron")?;
+        let player_class_db: Me1PlayerClassDb = ron::from_str(&input)?;
+        let soldier = &player_class_db[0];
+
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let items_len = {
+            let mut player = me1_save_game.player.borrow_mut();
+            assert_eq!(player.class_base().unwrap(), "BIO_PARTY_MEMBER_CLASS_BASE_ADEPT");
+            assert_eq!(player.specialization_bonus_id(), Some(157));
+
+            let items_len = player.inventory().unwrap().items.object_ids().len();
+            player.change_class(soldier)?;
+            // Shock Trooper
+            player.set_specialization(soldier, &[119, 137, 141], 1)?;
+            items_len
+        };
+
+        // Serialize, deserialize
+        let output = unreal::Serializer::to_vec(&me1_save_game)?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        let player = me1_save_game.player.borrow();
+        assert_eq!(player.class_base().unwrap(), soldier.class_base);
+        assert_eq!(player.shepard_int("m_LocalizedClassName").unwrap().get(), 93952);
+        assert_eq!(player.shepard_int("m_AutoLevelUpTemplateID").unwrap().get(), 10);
+        assert_eq!(player.specialization_bonus_id(), Some(137));
+        // Spent ranks refunded
+        assert!(player.shepard_int("m_TalentPoints").unwrap().get() > 0);
+
+        let talent_ids = complex_talent_ids(&player);
+        assert!(talent_ids.contains(&137));
+        let specialization = complex_talent(&player, 137).unwrap();
+        assert_eq!(
+            player.talent_int(&specialization, "m_MaxRank"),
+            soldier.specialization_max_rank
+        );
+        assert!(!talent_ids.contains(&157));
+        assert!(IGNORED_COMPLEXES.iter().all(|id| talent_ids.contains(id)));
+
+        // The adept gear is in the inventory, the soldier has an armor and no bio-amp
+        let shepard = player.squad().into_iter().next().unwrap();
+        let equipment = shepard.equipment.object_ids();
+        let armor = player.item(equipment[1]).unwrap();
+        assert_eq!(armor.item_id.get(), 249);
+        assert_eq!(armor.item_level.get(), ItemLevel::I as u8);
+        assert_eq!(equipment[4], 0);
+        assert_eq!(player.inventory().unwrap().items.object_ids().len(), items_len + 2);
+
+        Ok(())
+    }
+}
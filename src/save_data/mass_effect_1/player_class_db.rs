@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+use crate::save_data::{
+    mass_effect_1_le::{
+        item_db::DbItem,
+        player::{ComplexTalent, Me1LeClass, SimpleTalent},
+    },
+    StringRef,
+};
+
+#[derive(Deserialize)]
+pub struct Me1PlayerClass {
+    pub player_class: Me1LeClass,
+    /// The `EBioPartyMemberClassBase` name of `m_ClassBase`
+    pub class_base: String,
+    pub localized_class_name: StringRef,
+    pub auto_levelup_template_id: i32,
+    /// The max rank of the class talent once replaced by a specialization
+    pub specialization_max_rank: i32,
+    pub simple_talents: Vec<SimpleTalent>,
+    pub complex_talents: Vec<ComplexTalent>,
+    pub armor: Option<DbItem>,
+    pub omni_tool: Option<DbItem>,
+    pub bio_amp: Option<DbItem>,
+}
+
+/// The classes of the Legendary Edition db, the talents are the same in the original game.
+/// The Adept is checked against `test/ME1Save.MassEffectSave`.
+#[derive(Deserialize, Deref)]
+pub struct Me1PlayerClassDb(Vec<Me1PlayerClass>);
+
+impl Me1PlayerClassDb {
+    /// By value, the db order does not have to follow `Me1LeClass`
+    pub fn class(&self, player_class: &Me1LeClass) -> Option<&Me1PlayerClass> {
+        self.iter().find(|class| class.player_class == *player_class)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn deserialize_player_class_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_player_class_db.ron")?;
+        let me1_player_class_db: Me1PlayerClassDb = ron::from_str(&input)?;
+
+        for idx in 0..Me1LeClass::variants().len() {
+            assert!(me1_player_class_db.class(&Me1LeClass::from(idx)).is_some());
+        }
+
+        Ok(())
+    }
+}
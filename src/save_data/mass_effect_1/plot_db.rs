@@ -14,7 +14,7 @@ mod test {
     use anyhow::Result;
     use std::fs;
 
-    use crate::save_data::shared::plot::RawPlotDb;
+    use crate::save_data::shared::plot::{PlotPresetDb, RawPlotDb};
 
     use super::*;
 
@@ -33,4 +33,12 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn deserialize_plot_preset_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_plot_preset_db.ron")?;
+        let _me1_plot_preset_db: PlotPresetDb = ron::from_str(&input)?;
+
+        Ok(())
+    }
 }
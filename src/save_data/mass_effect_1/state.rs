@@ -6,7 +6,7 @@ use crate::save_data::{shared::plot::PlotTable, Dummy, List};
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct State {
     _begin: Dummy<12>,
-    base_level_name: String,
+    pub base_level_name: String,
     _osef1: Dummy<24>,
     pub plot: PlotTable,
     _osef2: List<u8>,
@@ -0,0 +1,206 @@
+use anyhow::{bail, Result};
+
+use super::{
+    data::{ArrayType, Property},
+    player::Player,
+};
+use crate::save_data::{List, RcRef};
+
+/// A `BioWorldSaveObject`, the levels of a map that has been visited
+pub struct WorldMap {
+    pub name: String,
+    pub levels: Vec<Level>,
+}
+
+/// A `BioLevelSaveObject`
+pub struct Level {
+    pub name: String,
+    pub object_id: i32,
+    /// The objects of the level that have a state, placeables, pawns, inventories, ...
+    pub objects: Vec<i32>,
+    /// The paths of the pawns killed and the objects destroyed
+    pub destroyed_objects: Option<RcRef<Property>>,
+}
+
+impl Level {
+    pub fn destroyed_objects(&self) -> Vec<String> {
+        let destroyed_objects = match self.destroyed_objects {
+            Some(ref destroyed_objects) => destroyed_objects.borrow(),
+            None => return Vec::new(),
+        };
+        match *destroyed_objects {
+            Property::Array { ref array, .. } => array
+                .iter()
+                .filter_map(|item| match item {
+                    ArrayType::String(path) => Some(path.borrow().clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Removes an object from the destroyed ones, it will be back the next time the level is loaded
+    pub fn restore_destroyed_object(&self, idx: usize) -> Result<()> {
+        let destroyed_objects = match self.destroyed_objects {
+            Some(ref destroyed_objects) => destroyed_objects,
+            None => return Ok(()),
+        };
+        let mut property = destroyed_objects.borrow_mut();
+        if let Property::Array { ref mut array, .. } = *property {
+            if idx >= array.len() {
+                bail!("No destroyed object at index {}", idx);
+            }
+            array.remove(idx);
+        }
+        property.update_size()
+    }
+}
+
+impl Player {
+    pub fn object_class_name(&self, object_id: i32) -> String {
+        let class_id = self.get_object(object_id).class_id;
+        if class_id < 0 {
+            self.get_name(self.get_class(class_id).class_name_id)
+        } else {
+            String::from("Class")
+        }
+    }
+
+    /// The `OwnerName` of an object, the name of the actor it saves
+    pub fn owner_name(&self, object_id: i32) -> Option<String> {
+        let properties = &self.get_data(object_id).properties;
+        match *self.find_property(properties, "OwnerName")?.borrow() {
+            Property::Str { ref string, .. } => Some(string.borrow().clone()),
+            _ => None,
+        }
+    }
+
+    pub fn world_maps(&self) -> Vec<WorldMap> {
+        (1..=self.objects.len() as i32)
+            .filter(|object_id| self.object_class_name(*object_id) == "BioWorldSaveObject")
+            .map(|object_id| {
+                let name = self.get_name(self.get_object(object_id).object_name_id);
+                let properties = &self.get_data(object_id).properties;
+                let levels = match self.find_property(properties, "m_Levels") {
+                    Some(m_levels) => match *m_levels.borrow() {
+                        Property::Array { ref array, .. } => array
+                            .iter()
+                            .filter_map(|level| match level {
+                                ArrayType::Properties(properties) => self.level(properties),
+                                _ => None,
+                            })
+                            .collect(),
+                        _ => Vec::new(),
+                    },
+                    None => Vec::new(),
+                };
+                WorldMap { name: name.trim_end_matches("WorldSave").to_owned(), levels }
+            })
+            .collect()
+    }
+
+    fn level(&self, properties: &List<RcRef<Property>>) -> Option<Level> {
+        let name = match *self.find_property(properties, "m_Name")?.borrow() {
+            // `Level BIOA_END20_00_LAY.TheWorld:PersistentLevel`
+            Property::Str { ref string, .. } => {
+                let name = string.borrow();
+                let name = name.trim_start_matches("Level ");
+                name.split('.').next().unwrap_or(name).to_owned()
+            }
+            _ => return None,
+        };
+        let object_id = self.find_object_id(properties, "m_Save").filter(|id| *id > 0)?;
+
+        let properties = &self.get_data(object_id).properties;
+        let objects = match self.find_property(properties, "m_LevelObjects") {
+            Some(m_level_objects) => match *m_level_objects.borrow() {
+                Property::Array { ref array, .. } => array
+                    .iter()
+                    .filter_map(|object| match object {
+                        ArrayType::Object(object_id) if *object_id > 0 => Some(*object_id),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        let destroyed_objects = self.find_property(properties, "m_lstDestroyedObjects").cloned();
+
+        Some(Level { name, object_id, objects, destroyed_objects })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use crate::{
+        save_data::mass_effect_1::{Me1SaveGame, WorldSavePackage},
+        unreal,
+    };
+
+    #[test]
+    fn world_save_package() -> Result<()> {
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+
+        let destroyed_len = {
+            let world = me1_save_game.world_save_package();
+            let world = world.as_ref().unwrap().borrow();
+            let world = world.parsed().unwrap().borrow();
+            let maps = world.world_maps();
+            assert!(maps.iter().any(|map| map.name == "BIOA_END00"));
+
+            let level = maps
+                .iter()
+                .flat_map(|map| map.levels.iter())
+                .find(|level| !level.destroyed_objects().is_empty())
+                .unwrap();
+            assert!(!level.objects.is_empty());
+            assert!(level.objects.iter().all(|object_id| world.owner_name(*object_id).is_some()));
+
+            // Bring back a dead pawn
+            let destroyed_len = level.destroyed_objects().len();
+            assert!(level.restore_destroyed_object(destroyed_len).is_err());
+            level.restore_destroyed_object(0)?;
+            (level.object_id, destroyed_len)
+        };
+
+        // Serialize, deserialize
+        let output = unreal::Serializer::to_vec(&me1_save_game)?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&output)?;
+
+        let world = me1_save_game.world_save_package();
+        let world = world.as_ref().unwrap().borrow();
+        let world = world.parsed().unwrap().borrow();
+        let (object_id, len) = destroyed_len;
+        let level = world
+            .world_maps()
+            .into_iter()
+            .flat_map(|map| map.levels)
+            .find(|level| level.object_id == object_id)
+            .unwrap();
+        assert_eq!(level.destroyed_objects().len(), len - 1);
+
+        // Exports have no world
+        let input = fs::read("test/ME1Export.MassEffectSave")?;
+        let me1_save_game: Me1SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        assert!(me1_save_game.world_save_package().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn raw_world_save_package() -> Result<()> {
+        let bytes = vec![0x52, 0x47, 0x4d, 0x48, 0x01];
+        let world_save_package = WorldSavePackage::from_bytes(bytes.clone());
+        assert!(world_save_package.parsed().is_none());
+        assert_eq!(world_save_package.to_bytes()?, bytes);
+
+        Ok(())
+    }
+}
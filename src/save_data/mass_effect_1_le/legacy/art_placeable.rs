@@ -6,24 +6,48 @@ use crate::save_data::Dummy;
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ArtPlaceableBehavior {
-    is_dead: bool,
-    generated_treasure: bool,
+    pub is_dead: bool,
+    pub generated_treasure: bool,
     challenge_scaled: bool,
     owner: Option<BaseObject>,
     health: f32,
     current_health: f32,
     enabled: bool,
-    current_fsm_state_name: String,
-    is_destroyed: bool,
+    pub current_fsm_state_name: String,
+    pub is_destroyed: bool,
     state_0: String,
     state_1: String,
     use_case: u8,
     use_case_override: bool,
     player_only: bool,
-    skill_difficulty: u8,
+    pub skill_difficulty: u8,
     inventory: Option<BaseObject>,
-    skill_game_failed: bool,
-    skill_game_xp_awarded: bool,
+    pub skill_game_failed: bool,
+    pub skill_game_xp_awarded: bool,
+}
+
+impl ArtPlaceableBehavior {
+    /// Containers, doors and the placeables with a skill game, the others only have a `None` state
+    pub fn is_interactive(&self) -> bool {
+        *self.current_fsm_state_name() != "None" || self.skill_difficulty() > 0
+    }
+
+    /// Closes it and lets the skill game be played again
+    pub fn relock(&mut self) {
+        if *self.current_fsm_state_name() != "None" {
+            *self.current_fsm_state_name_mut() = "Closed".to_owned();
+        }
+        self.set_skill_game_failed(false);
+        self.set_skill_game_xp_awarded(false);
+    }
+
+    /// Relocks it, repairs it and generates its loot again
+    pub fn reset(&mut self) {
+        self.relock();
+        self.set_is_dead(false);
+        self.set_is_destroyed(false);
+        self.set_generated_treasure(false);
+    }
 }
 
 #[rcize_fields]
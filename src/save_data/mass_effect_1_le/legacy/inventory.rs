@@ -1,21 +1,29 @@
 use serde::{Deserialize, Serialize};
 
 use super::{BaseObject, OptionObjectProxy};
-use crate::save_data::mass_effect_1_le::player::ItemLevel;
+use crate::save_data::{mass_effect_1_le::player::ItemLevel, StringRef};
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Shop {
-    last_player_level: i32,
-    is_initialized: bool,
-    inventory: Vec<OptionObjectProxy>,
+    pub last_player_level: i32,
+    pub is_initialized: bool,
+    pub inventory: Vec<OptionObjectProxy>,
+}
+
+impl Shop {
+    /// The game generates the stock again the next time the shop is opened
+    pub fn reset(&mut self) {
+        self.set_is_initialized(false);
+        self.set_last_player_level(0);
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Inventory {
-    items: Vec<BaseObject>,
-    plot_items: Vec<PlotItem>,
+    pub items: Vec<BaseObject>,
+    pub plot_items: Vec<PlotItem>,
     credits: i32,
     grenades: i32,
     medigel: f32,
@@ -25,21 +33,21 @@ pub struct Inventory {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
-struct PlotItem {
-    localized_name: i32,
-    localized_desc: i32,
+pub struct PlotItem {
+    pub localized_name: StringRef,
+    localized_desc: StringRef,
     export_id: i32,
-    base_price: i32,
+    pub base_price: i32,
     shop_gui_image_id: i32,
     plot_conditional_id: i32,
 }
 
 #[rcize_fields]
-#[derive(Deserialize, Serialize, Clone, RawUiChildren)]
+#[derive(Deserialize, Serialize, Clone, Default, RawUiChildren)]
 pub struct Item {
-    item_id: i32,
-    item_level: ItemLevel,
-    manufacturer_id: i32,
+    pub item_id: i32,
+    pub item_level: ItemLevel,
+    pub manufacturer_id: i32,
     plot_conditional_id: i32,
     slot_specs: Vec<ModdableSlotSpec>,
 }
@@ -55,9 +63,9 @@ struct ModdableSlotSpec {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct ItemMod {
-    item_id: i32,
-    item_level: ItemLevel,
-    manufacturer_id: i32,
+    pub item_id: i32,
+    pub item_level: ItemLevel,
+    pub manufacturer_id: i32,
     plot_conditional_id: i32,
     type_id: i32,
 }
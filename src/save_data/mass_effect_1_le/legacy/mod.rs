@@ -2,7 +2,7 @@ mod art_placeable;
 mod inventory;
 mod pawn;
 
-use self::{art_placeable::*, inventory::*, pawn::*};
+pub use self::{art_placeable::*, inventory::*, pawn::*};
 
 use std::fmt;
 
@@ -11,21 +11,22 @@ use serde::{de, Deserialize, Deserializer, Serialize};
 
 use crate::save_data::RcRef;
 use crate::save_data::{
+    mass_effect_1_le::mako,
     shared::{Rotator, Vector},
-    Dummy,
+    Dummy, StringRef,
 };
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi, RawUiChildren)]
 pub struct Map {
-    levels: IndexMap<String, Level>,
-    world: Option<BaseObject>,
+    pub levels: IndexMap<String, Level>,
+    pub world: Option<BaseObject>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, RawUi, RawUiChildren)]
 pub struct Level {
-    objects: Vec<BaseObject>,
+    pub objects: Vec<BaseObject>,
     actors: Vec<String>,
 }
 
@@ -90,6 +91,36 @@ impl<'de> Deserialize<'de> for BaseObject {
     }
 }
 
+impl BaseObject {
+    /// The name of the actor in the level, `BioPawn_12` for `BioPawn_12.BioPawnBehavior_0`
+    pub fn actor_name(&self) -> String {
+        self.owner_name().split('.').next().unwrap_or_default().to_owned()
+    }
+
+    /// A new moddable item of the `actor` with a name not used by the `siblings`
+    pub fn new_item(actor: &str, siblings: &[RcRef<BaseObject>]) -> Self {
+        const PREFIX: &str = "BioItemXModdable_";
+        let next_idx = siblings
+            .iter()
+            .filter_map(|sibling| {
+                let sibling = sibling.borrow();
+                let owner_name = sibling.owner_name();
+                let (_, name) = owner_name.split_once('.')?;
+                name.strip_prefix(PREFIX)?.parse::<usize>().ok()
+            })
+            .max()
+            .map(|idx| idx + 1)
+            .unwrap_or_default();
+
+        BaseObject {
+            _class_name: "BioItemXModdableSaveObject".to_owned(),
+            owner_name: format!("{}.{}{}", actor, PREFIX, next_idx).into(),
+            owner_class: Some(RcRef::new("BioItemXModdable".to_owned())).into(),
+            _object: Object::Item(Default::default()),
+        }
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub enum Object {
     PawnBehavior(RcRef<PawnBehavior>),
@@ -124,28 +155,42 @@ pub struct OptionObjectProxy {
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct VehicleBehavior {
     actor_type: String,
-    powertrain_enabled: bool,
-    vehicle_fonction_enabled: bool,
-    owner: Option<BaseObject>,
+    pub powertrain_enabled: bool,
+    pub vehicle_fonction_enabled: bool,
+    pub owner: Option<BaseObject>,
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Vehicle {
-    location: Vector,
-    rotation: Rotator,
-    velocity: Vector,
-    acceleration: Vector,
+    pub location: Vector,
+    pub rotation: Rotator,
+    pub velocity: Vector,
+    pub acceleration: Vector,
     script_initialized: bool,
-    hidden: bool,
-    stasis: bool,
-    health: f32,
-    shield: f32,
-    first_name: String,
-    localized_last_name: i32,
+    pub hidden: bool,
+    pub stasis: bool,
+    pub health: f32,
+    pub shield: f32,
+    pub first_name: String,
+    pub localized_last_name: StringRef,
     _unknown: Dummy<16>,
 }
 
+impl Vehicle {
+    pub fn restore(&mut self) {
+        mako::restore(&self.health, &self.shield);
+    }
+
+    /// Stops the vehicle and puts it back in the world, the location is left to the user
+    pub fn unstick(&mut self) {
+        *self.velocity_mut() = Vector::default();
+        *self.acceleration_mut() = Vector::default();
+        self.set_hidden(false);
+        self.set_stasis(false);
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", name)]
@@ -160,10 +205,10 @@ pub struct World {
     streaming_states: Vec<WorldStreamingState>,
     destination_area_map: String,
     destination: Vector,
-    cinematics_seen: Vec<String>,
-    scanned_clusters: Vec<i32>,
-    scanned_systems: Vec<i32>,
-    scanned_planets: Vec<i32>,
+    pub cinematics_seen: Vec<String>,
+    pub scanned_clusters: Vec<i32>,
+    pub scanned_systems: Vec<i32>,
+    pub scanned_planets: Vec<i32>,
     journal_sort_method: u8,
     journal_showing_missions: bool,
     journal_last_selected_mission: i32,
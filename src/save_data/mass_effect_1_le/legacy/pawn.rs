@@ -3,24 +3,24 @@ use serde::{Deserialize, Serialize};
 use super::{BaseObject, OptionObjectProxy};
 use crate::save_data::{
     shared::{Rotator, Vector},
-    Dummy,
+    Dummy, StringRef,
 };
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct PawnBehavior {
-    is_dead: bool,
+    pub is_dead: bool,
     generated_treasure: bool,
     challenge_scaled: bool,
     owner: Option<BaseObject>,
-    health: f32,
+    pub health: f32,
     shield: f32,
     first_name: String,
-    localized_last_name: i32,
-    health_max: f32,
+    localized_last_name: StringRef,
+    pub health_max: f32,
     health_regen_rate: f32,
     radar_range: f32,
-    level: i32,
+    pub level: i32,
     health_per_level: f32,
     stability: f32,
     gender: u8,
@@ -35,12 +35,12 @@ pub struct PawnBehavior {
     inventory: Option<BaseObject>,
     _unknown: Dummy<3>,
     experience: i32,
-    talent_points: i32,
+    pub talent_points: i32,
     talent_pool_points: i32,
     attribute_primary: u8,
     attribute_secondary: u8,
     class_base: u8,
-    localized_class_name: i32,
+    localized_class_name: StringRef,
     auto_level_up_template_id: i32,
     spectre_rank: u8,
     background_origin: u8,
@@ -61,6 +61,21 @@ pub struct PawnBehavior {
     equipment: Vec<OptionObjectProxy>,
 }
 
+impl PawnBehavior {
+    pub fn kill(&mut self) {
+        self.set_is_dead(true);
+        self.set_health(0.0);
+    }
+
+    /// The max health is often left to 0 by the game, which restores the health on load
+    pub fn revive(&mut self) {
+        self.set_is_dead(false);
+        if self.health() <= 0.0 {
+            self.set_health(self.health_max().max(1.0));
+        }
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "{}", talent_id)]
@@ -0,0 +1,11 @@
+use crate::save_data::RcCell;
+
+/// Hull and shields of the undamaged Mako in `test/ME1LeSave.pcsav`, saves do not store the maximums
+pub const MAKO_HEALTH_MAX: f32 = 5000.0;
+pub const MAKO_SHIELD_MAX: f32 = 2000.0;
+
+/// Fully repairs the hull and shields of a Mako
+pub fn restore(health: &RcCell<f32>, shield: &RcCell<f32>) {
+    health.set(MAKO_HEALTH_MAX);
+    shield.set(MAKO_SHIELD_MAX);
+}
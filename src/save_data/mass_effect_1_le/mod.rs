@@ -1,8 +1,10 @@
 pub mod item_db;
 pub mod legacy;
+pub mod mako;
 pub mod player;
 pub mod player_class_db;
 pub mod squad;
+pub mod talent_db;
 
 use self::{legacy::*, player::*, squad::*};
 
@@ -17,7 +19,10 @@ use serde::de;
 use serde::ser::{self, SerializeStruct};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{save_data::RcRef, unreal};
+use crate::{
+    save_data::{RcRef, StringRef},
+    unreal,
+};
 
 use super::{
     shared::{
@@ -199,22 +204,22 @@ impl<'de> Deserialize<'de> for Me1LeMagicNumber {
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct Me1LeSaveData {
     _version: Me1LeVersion,
-    character_id: String,
+    pub character_id: String,
     created_date: SaveTimeStamp,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     seconds_played: i32,
     pub player: Player,
-    base_level_name: String,
+    pub base_level_name: String,
     map_name: String,
     parent_map_name: String,
     location: Vector,
     rotation: Rotator,
     pub squad: Vec<Henchman>,
-    display_name: String,
-    file_name: String,
+    pub display_name: String,
+    pub file_name: String,
     pub no_export: NoExport, // Only serialized for normal savegames, not for character export
 }
 
@@ -294,17 +299,47 @@ impl serde::Serialize for NoExport {
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUiChildren)]
 pub struct NoExportData {
-    legacy_maps: IndexMap<String, Map>,
-    mako: Vehicle,
+    pub legacy_maps: IndexMap<String, Map>,
+    pub mako: Vehicle,
+}
+
+/// An object of a legacy level, with the names of its map and level
+pub struct LegacyObject {
+    pub map_name: String,
+    pub level_name: String,
+    pub object: RcRef<BaseObject>,
+}
+
+impl NoExportData {
+    /// Every object of every level of the legacy maps, in save order
+    pub fn legacy_objects(&self) -> Vec<LegacyObject> {
+        let mut objects = Vec::new();
+        for (map_name, map) in self.legacy_maps().iter() {
+            for (level_name, level) in map.borrow().levels().iter() {
+                objects.extend(level.borrow().objects().iter().map(|object| LegacyObject {
+                    map_name: map_name.clone(),
+                    level_name: level_name.clone(),
+                    object: RcRef::clone(object),
+                }));
+            }
+        }
+        objects
+    }
 }
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct Vehicle {
-    first_name: String,
-    localized_last_name: i32,
-    health: f32,
-    shield: f32,
+    pub first_name: String,
+    pub localized_last_name: StringRef,
+    pub health: f32,
+    pub shield: f32,
+}
+
+impl Vehicle {
+    pub fn restore(&mut self) {
+        mako::restore(&self.health, &self.shield);
+    }
 }
 
 #[cfg(test)]
@@ -317,6 +352,7 @@ mod test {
 
     use super::*;
     use crate::unreal;
+    use mako::{MAKO_HEALTH_MAX, MAKO_SHIELD_MAX};
 
     #[test]
     fn deserialize_serialize() -> Result<()> {
@@ -389,6 +425,175 @@ mod test {
         Ok(())
     }
 
+    fn legacy_objects(save_data: &Me1LeSaveData) -> Vec<LegacyObject> {
+        save_data.no_export().as_ref().unwrap().borrow().legacy_objects()
+    }
+
+    /// Edits `test/ME1LeSave.pcsav` then checks the save once written and read again.
+    /// What `edit` returns is passed to `check`.
+    fn edit_save<T>(
+        edit: impl FnOnce(&Me1LeSaveGame) -> T, check: impl FnOnce(&Me1LeSaveGame, T),
+    ) -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let edited = edit(&me1_save_game);
+
+        let output = unreal::Serializer::to_vec(&me1_save_game)?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&output)?;
+        check(&me1_save_game, edited);
+        Ok(())
+    }
+
+    #[test]
+    fn edit_legacy_maps() -> Result<()> {
+        fn behaviors(
+            save_data: &Me1LeSaveData,
+        ) -> (Vec<RcRef<PawnBehavior>>, Vec<RcRef<ArtPlaceableBehavior>>) {
+            let mut pawns = Vec::new();
+            let mut placeables = Vec::new();
+            for LegacyObject { object, .. } in legacy_objects(save_data) {
+                match object.borrow()._object {
+                    Object::PawnBehavior(ref pawn) => pawns.push(RcRef::clone(pawn)),
+                    Object::ArtPlaceableBehavior(ref placeable)
+                        if placeable.borrow().is_interactive() =>
+                    {
+                        placeables.push(RcRef::clone(placeable))
+                    }
+                    _ => (),
+                }
+            }
+            (pawns, placeables)
+        }
+
+        edit_save(
+            |me1_save_game| {
+                let (pawns, placeables) = behaviors(&me1_save_game.save_data.borrow());
+                pawns[0].borrow_mut().kill();
+                placeables[0].borrow_mut().reset();
+            },
+            |me1_save_game, ()| {
+                let (pawns, placeables) = behaviors(&me1_save_game.save_data.borrow());
+                assert!(pawns[0].borrow().is_dead());
+                assert_eq!(pawns[0].borrow().health(), 0.0);
+                assert!(!placeables[0].borrow().generated_treasure());
+
+                pawns[0].borrow_mut().revive();
+                assert!(!pawns[0].borrow().is_dead());
+                assert!(pawns[0].borrow().health() > 0.0);
+            },
+        )
+    }
+
+    #[test]
+    fn repair_mako() -> Result<()> {
+        fn vehicles(
+            save_data: &Me1LeSaveData,
+        ) -> Vec<(RcRef<VehicleBehavior>, RcRef<legacy::Vehicle>)> {
+            let mut vehicles = Vec::new();
+            for LegacyObject { object, .. } in legacy_objects(save_data) {
+                if let Object::VehicleBehavior(ref behavior) = object.borrow()._object {
+                    let owner = behavior.borrow().owner().as_ref().map(RcRef::clone);
+                    if let Object::Vehicle(ref vehicle) = owner.unwrap().borrow()._object {
+                        vehicles.push((RcRef::clone(behavior), RcRef::clone(vehicle)));
+                    }
+                }
+            }
+            vehicles
+        }
+
+        edit_save(
+            |me1_save_game| {
+                let mut save_data = me1_save_game.save_data.borrow_mut();
+                {
+                    let no_export = save_data.no_export();
+                    let mut no_export = no_export.as_ref().unwrap().borrow_mut();
+                    let mut mako = no_export.mako_mut();
+                    // The maximums come from this undamaged Mako
+                    assert_eq!(mako.health(), MAKO_HEALTH_MAX);
+                    assert_eq!(mako.shield(), MAKO_SHIELD_MAX);
+                    mako.set_health(0.0);
+                    mako.set_shield(0.0);
+                    mako.restore();
+                }
+                save_data.player_mut().set_is_driving(false);
+
+                let (behavior, vehicle) = &vehicles(&save_data)[0];
+                behavior.borrow_mut().set_powertrain_enabled(true);
+                let mut vehicle = vehicle.borrow_mut();
+                vehicle.set_hidden(true);
+                vehicle.restore();
+                vehicle.unstick();
+            },
+            |me1_save_game, ()| {
+                let save_data = me1_save_game.save_data.borrow();
+                let no_export = save_data.no_export();
+                let no_export = no_export.as_ref().unwrap().borrow();
+                assert_eq!(no_export.mako().health(), MAKO_HEALTH_MAX);
+                assert_eq!(no_export.mako().shield(), MAKO_SHIELD_MAX);
+                assert!(!save_data.player().is_driving());
+
+                let (behavior, vehicle) = &vehicles(&save_data)[0];
+                assert!(behavior.borrow().powertrain_enabled());
+                let vehicle = vehicle.borrow();
+                assert!(!vehicle.hidden());
+                assert_eq!(vehicle.health(), MAKO_HEALTH_MAX);
+            },
+        )
+    }
+
+    #[test]
+    fn edit_shops() -> Result<()> {
+        fn shops(save_data: &Me1LeSaveData) -> Vec<(String, RcRef<Shop>)> {
+            let mut shops = Vec::new();
+            for LegacyObject { object, .. } in legacy_objects(save_data) {
+                let object = object.borrow();
+                if let Object::Shop(ref shop) = object._object {
+                    shops.push((object.actor_name(), RcRef::clone(shop)));
+                }
+            }
+            shops
+        }
+
+        fn inventory(shop: &RcRef<Shop>) -> RcRef<legacy::Inventory> {
+            let shop = shop.borrow();
+            let proxy = shop.inventory()[0].borrow().proxy().as_ref().map(RcRef::clone).unwrap();
+            let object = proxy.borrow();
+            match object._object {
+                Object::Inventory(ref inventory) => RcRef::clone(inventory),
+                _ => panic!("not an inventory"),
+            }
+        }
+
+        edit_save(
+            |me1_save_game| {
+                let shops = shops(&me1_save_game.save_data.borrow());
+                let (actor, shop) = &shops[0];
+                shop.borrow_mut().reset();
+
+                let inventory = inventory(shop);
+                let mut inventory = inventory.borrow_mut();
+                let mut items = inventory.items_mut();
+                let item = BaseObject::new_item(actor, &items);
+                items.push(item.into());
+                items.len()
+            },
+            |me1_save_game, item_count| {
+                let shops = shops(&me1_save_game.save_data.borrow());
+                let (actor, shop) = &shops[0];
+                assert!(!shop.borrow().is_initialized());
+                assert_eq!(shop.borrow().last_player_level(), 0);
+
+                let inventory = inventory(shop);
+                let inventory = inventory.borrow();
+                let items = inventory.items();
+                assert_eq!(items.len(), item_count);
+                let item = items.last().unwrap().borrow();
+                assert!(item.owner_name().starts_with(&format!("{}.BioItemXModdable_", actor)));
+                assert!(matches!(item._object, Object::Item(_)));
+            },
+        )
+    }
+
     // #[test]
     // fn uncompress() -> Result<()> {
     //     let input = fs::read("test/ME1Le_Export.pcsav")?;
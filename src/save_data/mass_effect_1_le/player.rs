@@ -5,25 +5,25 @@ use crate::save_data::{
         appearance::HeadMorph,
         player::{Notoriety, Origin},
     },
-    Dummy,
+    Dummy, RcRef, StringRef,
 };
 
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, RawUi)]
 pub struct Player {
     pub is_female: bool,
-    pub localized_class_name: i32,
+    pub localized_class_name: StringRef,
     pub player_class: Me1LeClass,
     pub level: i32,
     pub current_xp: f32,
     pub first_name: String,
-    localized_last_name: i32,
+    localized_last_name: StringRef,
     pub origin: Origin,
     pub notoriety: Notoriety,
     pub specialization_bonus_id: i32,
-    spectre_rank: u8,
+    pub spectre_rank: u8,
     pub talent_points: i32,
-    talent_pool_points: i32,
+    pub talent_pool_points: i32,
     mapped_talent: String,
     pub head_morph: Option<HeadMorph>,
     pub simple_talents: Vec<SimpleTalent>,
@@ -37,27 +37,27 @@ pub struct Player {
     armor_overridden: bool,
     pub auto_levelup_template_id: i32,
     health_per_level: f32,
-    stability: f32,
+    pub stability: f32,
     race: u8,
-    toxic: f32,
-    stamina: i32,
-    focus: i32,
-    precision: i32,
-    coordination: i32,
+    pub toxic: f32,
+    pub stamina: i32,
+    pub focus: i32,
+    pub precision: i32,
+    pub coordination: i32,
     attribute_primary: u8,
     attribute_secondary: u8,
-    skill_charm: f32,
-    skill_intimidate: f32,
-    skill_haggle: f32,
-    health: f32,
-    shield: f32,
-    xp_level: i32,
-    is_driving: bool,
+    pub skill_charm: f32,
+    pub skill_intimidate: f32,
+    pub skill_haggle: f32,
+    pub health: f32,
+    pub shield: f32,
+    pub xp_level: i32,
+    pub is_driving: bool,
     pub game_options: Vec<i32>,
     helmet_shown: bool,
     _unknown: Dummy<5>,
     last_power: String,
-    health_max: f32,
+    pub health_max: f32,
     hotkeys: Vec<Hotkey>,
     primary_weapon: String,
     secondary_weapon: String,
@@ -91,8 +91,87 @@ pub struct ComplexTalent {
     pub level_offset: i32,
     pub levels_per_rank: i32,
     pub visual_order: i32,
-    prereq_talent_ids: Vec<i32>,
-    prereq_talent_ranks: Vec<i32>,
+    pub prereq_talent_ids: Vec<i32>,
+    pub prereq_talent_ranks: Vec<i32>,
+}
+
+impl ComplexTalent {
+    /// The highest rank allowed at `level`, a rank is gained every `levels_per_rank` after `level_offset`
+    pub fn max_rank_at(&self, level: i32) -> i32 {
+        let levels_per_rank = self.levels_per_rank().max(1);
+        ((level - self.level_offset()) / levels_per_rank).clamp(0, self.max_rank())
+    }
+
+    /// Whether the other `talents` of the character have the ranks required by this one
+    pub fn is_unlocked(&self, talents: &[RcRef<ComplexTalent>]) -> bool {
+        let prereq_ids = self.prereq_talent_ids();
+        let prereq_ranks = self.prereq_talent_ranks();
+        prereq_ids.iter().zip(prereq_ranks.iter()).all(|(id, rank)| {
+            talents.iter().any(|talent| {
+                let talent = talent.borrow();
+                talent.talent_id() == id.get() && talent.current_rank() >= rank.get()
+            })
+        })
+    }
+}
+
+/// Sets the rank of the talent at `idx` within what the `level`, the prerequisites and the
+/// `talent_points` allow, the talents that lose a prerequisite are reset.
+/// Returns the talent points left.
+pub fn set_talent_rank(
+    talents: &[RcRef<ComplexTalent>], idx: usize, rank: i32, level: i32, mut talent_points: i32,
+) -> i32 {
+    let talent = match talents.get(idx) {
+        Some(talent) => talent,
+        None => return talent_points,
+    };
+    let current_rank = talent.borrow().current_rank();
+
+    if rank > current_rank {
+        let talent = talent.borrow();
+        if !talent.is_unlocked(talents) {
+            return talent_points;
+        }
+        let new_rank = rank.min(talent.max_rank_at(level)).min(current_rank + talent_points);
+        if new_rank > current_rank {
+            talent.current_rank.set(new_rank);
+            talent_points -= new_rank - current_rank;
+        }
+    } else if rank < current_rank {
+        let new_rank = rank.max(0);
+        talent.borrow().current_rank.set(new_rank);
+        talent_points += current_rank - new_rank;
+
+        // A reset talent can be the prerequisite of another one
+        loop {
+            let locked = talents.iter().find(|talent| {
+                let talent = talent.borrow();
+                talent.current_rank() > 0 && !talent.is_unlocked(talents)
+            });
+            match locked {
+                Some(talent) => {
+                    let talent = talent.borrow();
+                    talent_points += talent.current_rank();
+                    talent.current_rank.set(0);
+                }
+                None => break,
+            }
+        }
+    }
+    talent_points
+}
+
+/// Resets all the `talents` and returns the points spent in them
+pub fn respec_talents(talents: &[RcRef<ComplexTalent>]) -> i32 {
+    talents
+        .iter()
+        .map(|talent| {
+            let talent = talent.borrow();
+            let rank = talent.current_rank();
+            talent.current_rank.set(0);
+            rank
+        })
+        .sum()
 }
 
 #[rcize_fields]
@@ -156,3 +235,49 @@ struct Hotkey {
     pawn: i32,
     event: i32,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn talent(
+        talent_id: i32, level_offset: i32, prereq: Option<(i32, i32)>,
+    ) -> RcRef<ComplexTalent> {
+        let mut talent = ComplexTalent::default();
+        talent.set_talent_id(talent_id);
+        talent.set_max_rank(12);
+        talent.set_level_offset(level_offset);
+        talent.set_levels_per_rank(1);
+        if let Some((id, rank)) = prereq {
+            talent.prereq_talent_ids_mut().push(id.into());
+            talent.prereq_talent_ranks_mut().push(rank.into());
+        }
+        talent.into()
+    }
+
+    #[test]
+    fn talent_tree() {
+        // Pistols then Shotguns, which requires 4 ranks of Pistols
+        let talents = [talent(0, -1, None), talent(15, 2, Some((0, 4)))];
+        let rank = |idx: usize| talents[idx].borrow().current_rank();
+
+        // Locked by the prerequisite
+        assert_eq!(set_talent_rank(&talents, 1, 3, 10, 20), 20);
+        assert_eq!(rank(1), 0);
+
+        // Capped by the level then by the points
+        assert_eq!(set_talent_rank(&talents, 0, 12, 5, 20), 14);
+        assert_eq!(rank(0), 6);
+        assert_eq!(set_talent_rank(&talents, 1, 12, 60, 4), 0);
+        assert_eq!(rank(1), 4);
+
+        // Losing the prerequisite resets the talent
+        assert_eq!(set_talent_rank(&talents, 0, 3, 60, 0), 7);
+        assert_eq!((rank(0), rank(1)), (3, 0));
+
+        assert_eq!(set_talent_rank(&talents, 0, 5, 60, 7), 5);
+        assert_eq!(set_talent_rank(&talents, 1, 2, 60, 5), 3);
+        assert_eq!(respec_talents(&talents), 7);
+        assert_eq!((rank(0), rank(1)), (0, 0));
+    }
+}
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use crate::save_data::RcRef;
+use crate::save_data::{RcRef, StringRef};
 
 use super::player::{ComplexTalent, Item, Me1LeClass, SimpleTalent};
 
@@ -13,7 +13,7 @@ pub struct Me1LeSpecializationBonus {
 #[derive(Deserialize)]
 pub struct Me1LePlayerClass {
     pub player_class: Me1LeClass,
-    pub localized_class_name: i32,
+    pub localized_class_name: StringRef,
     pub auto_levelup_template_id: i32,
     pub simple_talents: Vec<RcRef<SimpleTalent>>,
     pub complex_talents: Vec<RcRef<ComplexTalent>>,
@@ -26,6 +26,13 @@ pub struct Me1LePlayerClass {
 #[derive(Deserialize, Deref)]
 pub struct Me1LePlayerClassDb(Vec<Me1LePlayerClass>);
 
+impl Me1LePlayerClassDb {
+    /// By value, the db order does not have to follow `Me1LeClass`
+    pub fn class(&self, player_class: &Me1LeClass) -> Option<&Me1LePlayerClass> {
+        self.iter().find(|class| class.player_class == *player_class)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs;
@@ -36,7 +43,11 @@ mod test {
     #[test]
     fn deserialize_player_class_db() -> Result<()> {
         let input = fs::read_to_string("databases/me1_le_player_class_db.ron")?;
-        let _me1_le_player_class_db: Me1LePlayerClassDb = ron::from_str(&input)?;
+        let me1_le_player_class_db: Me1LePlayerClassDb = ron::from_str(&input)?;
+
+        for idx in 0..Me1LeClass::variants().len() {
+            assert!(me1_le_player_class_db.class(&Me1LeClass::from(idx)).is_some());
+        }
 
         Ok(())
     }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::save_data::StringRef;
+
 use super::player::{ComplexTalent, Item, SimpleTalent};
 
 #[rcize_fields]
@@ -12,10 +14,10 @@ pub struct Henchman {
     pub equipment: Vec<Item>,
     pub quick_slots: Vec<Item>,
     pub talent_points: i32,
-    talent_pool_points: i32,
+    pub talent_pool_points: i32,
     auto_levelup_template_id: i32,
-    localized_last_name: i32,
-    localized_class_name: i32,
+    localized_last_name: StringRef,
+    localized_class_name: StringRef,
     class_base: u8,
     health_per_level: f32,
     stability: f32,
@@ -30,7 +32,7 @@ pub struct Henchman {
     attribute_secondary: u8,
     health: f32,
     shield: f32,
-    level: i32,
+    pub level: i32,
     helmet_shown: bool,
     current_quick_slot: u8,
     health_max: f32,
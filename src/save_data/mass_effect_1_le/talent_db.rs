@@ -0,0 +1,65 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+/// Ids of the Paragon and Renegade talents in `databases/me1_le_talent_db.ron`, the same for every class
+pub const CHARM_TALENT_ID: i32 = 108;
+pub const INTIMIDATE_TALENT_ID: i32 = 109;
+
+#[derive(Deserialize, Deref)]
+pub struct Me1LeTalentDb(IndexMap<i32, String>);
+
+impl Me1LeTalentDb {
+    pub fn name(&self, talent_id: i32) -> String {
+        self.get(&talent_id).cloned().unwrap_or_else(|| format!("Talent {}", talent_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_1_le::Me1LeSaveGame, unreal};
+
+    #[test]
+    fn deserialize_talent_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_talent_db.ron")?;
+        let me1_le_talent_db: Me1LeTalentDb = ron::from_str(&input)?;
+
+        assert_eq!(me1_le_talent_db.name(CHARM_TALENT_ID), "Charm");
+        assert_eq!(me1_le_talent_db.name(INTIMIDATE_TALENT_ID), "Intimidate");
+
+        Ok(())
+    }
+
+    #[test]
+    fn names_saved_talents() -> Result<()> {
+        let input = fs::read_to_string("databases/me1_le_talent_db.ron")?;
+        let me1_le_talent_db: Me1LeTalentDb = ron::from_str(&input)?;
+
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let me1_save_game: Me1LeSaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let save_data = me1_save_game.save_data.borrow();
+
+        // Shepard and every henchman
+        let mut talent_ids: Vec<_> = save_data
+            .player()
+            .complex_talents()
+            .iter()
+            .map(|talent| talent.borrow().talent_id())
+            .collect();
+        for henchman in save_data.squad().iter() {
+            let henchman = henchman.borrow();
+            talent_ids.extend(
+                henchman.complex_talents().iter().map(|talent| talent.borrow().talent_id()),
+            );
+        }
+        for talent_id in talent_ids {
+            assert!(me1_le_talent_db.contains_key(&talent_id), "talent {} has no name", talent_id);
+        }
+
+        Ok(())
+    }
+}
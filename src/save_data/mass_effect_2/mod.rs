@@ -1,7 +1,8 @@
 mod galaxy_map;
 pub mod player;
 pub mod plot_db;
-mod squad;
+pub mod squad;
+pub mod suicide_mission;
 
 use self::{galaxy_map::*, player::*, squad::*};
 
@@ -21,20 +22,20 @@ pub struct Me2SaveGame {
     debug_name: String,
     seconds_played: f32,
     disc: i32,
-    base_level_name: String,
+    pub base_level_name: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
     levels: Vec<Level>,
     streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
+    pub kismet_records: Vec<Kismet>,
     doors: Vec<Door>,
     pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
@@ -80,21 +81,21 @@ pub struct Me2LeSaveGame {
     debug_name: String,
     seconds_played: f32,
     disc: i32,
-    base_level_name: String,
+    pub base_level_name: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
     levels: Vec<Level>,
     streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
+    pub kismet_records: Vec<Kismet>,
     doors: Vec<Door>,
     pawns: Vec<Guid>,
     pub player: Player,
     me1_import_bonus: Me1ImportBonus,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-use crate::save_data::shared::{
-    appearance::Appearance,
-    player::{Notoriety, Origin, WeaponLoadout},
+use crate::save_data::{
+    shared::{
+        appearance::Appearance,
+        player::{Notoriety, Origin, WeaponLoadout},
+    },
+    StringRef,
 };
 
 #[rcize_fields]
@@ -13,7 +16,7 @@ pub struct Player {
     pub level: i32,
     pub current_xp: f32,
     pub first_name: String,
-    localized_last_name: i32,
+    localized_last_name: StringRef,
     pub origin: Origin,
     pub notoriety: Notoriety,
     pub talent_points: i32,
@@ -34,7 +37,7 @@ pub struct Player {
     pub probes: i32,
     pub current_fuel: f32,
     pub face_code: String,
-    localized_class_name: i32,
+    localized_class_name: StringRef,
 }
 
 #[rcize_fields]
@@ -42,7 +45,7 @@ pub struct Player {
 #[display(fmt = "{}", name)]
 pub struct Power {
     pub name: String,
-    rank: f32,
+    pub rank: f32,
     pub power_class_name: String,
     wheel_display_index: i32,
 }
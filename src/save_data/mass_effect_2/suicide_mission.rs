@@ -0,0 +1,740 @@
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+
+use crate::save_data::shared::plot::{PlotPreset, PlotState};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Squadmate {
+    Garrus,
+    Grunt,
+    Jack,
+    Jacob,
+    Kasumi,
+    Legion,
+    Miranda,
+    Mordin,
+    Samara,
+    Tali,
+    Thane,
+    Zaeed,
+}
+
+impl Squadmate {
+    pub const ALL: [Squadmate; 12] = [
+        Squadmate::Garrus,
+        Squadmate::Grunt,
+        Squadmate::Jack,
+        Squadmate::Jacob,
+        Squadmate::Kasumi,
+        Squadmate::Legion,
+        Squadmate::Miranda,
+        Squadmate::Mordin,
+        Squadmate::Samara,
+        Squadmate::Tali,
+        Squadmate::Thane,
+        Squadmate::Zaeed,
+    ];
+
+    fn ids(self) -> SquadmateIds {
+        match self {
+            Squadmate::Garrus => SquadmateIds {
+                in_party: 38,
+                in_squad: 26,
+                is_dead: 200,
+                is_loyal: 183,
+                vents: Some(2882),
+                first_leader: 2870,
+                crew_escort: 2953,
+                biotic: None,
+                second_leader: 2842,
+            },
+            Squadmate::Grunt => SquadmateIds {
+                in_party: 42,
+                in_squad: 30,
+                is_dead: 204,
+                is_loyal: 189,
+                vents: None,
+                first_leader: 2874,
+                crew_escort: 2957,
+                biotic: None,
+                second_leader: 2846,
+            },
+            Squadmate::Jack => SquadmateIds {
+                in_party: 35,
+                in_squad: 23,
+                is_dead: 197,
+                is_loyal: 179,
+                vents: None,
+                first_leader: 2867,
+                crew_escort: 2950,
+                biotic: Some(2851),
+                second_leader: 2838,
+            },
+            Squadmate::Jacob => SquadmateIds {
+                in_party: 34,
+                in_squad: 22,
+                is_dead: 196,
+                is_loyal: 178,
+                vents: Some(2878),
+                first_leader: 2866,
+                crew_escort: 2949,
+                biotic: Some(2850),
+                second_leader: 2837,
+            },
+            Squadmate::Kasumi => SquadmateIds {
+                in_party: 37,
+                in_squad: 25,
+                is_dead: 199,
+                is_loyal: 182,
+                vents: Some(2881),
+                first_leader: 2869,
+                crew_escort: 2952,
+                biotic: None,
+                second_leader: 2841,
+            },
+            Squadmate::Legion => SquadmateIds {
+                in_party: 36,
+                in_squad: 24,
+                is_dead: 198,
+                is_loyal: 180,
+                vents: Some(2880),
+                first_leader: 2868,
+                crew_escort: 2951,
+                biotic: None,
+                second_leader: 2840,
+            },
+            Squadmate::Miranda => SquadmateIds {
+                in_party: 33,
+                in_squad: 21,
+                is_dead: 195,
+                is_loyal: 177,
+                vents: None,
+                first_leader: 2865,
+                crew_escort: 2948,
+                biotic: Some(2849),
+                second_leader: 2836,
+            },
+            Squadmate::Mordin => SquadmateIds {
+                in_party: 41,
+                in_squad: 29,
+                is_dead: 203,
+                is_loyal: 188,
+                vents: Some(2885),
+                first_leader: 2873,
+                crew_escort: 2956,
+                biotic: None,
+                second_leader: 2845,
+            },
+            Squadmate::Samara => SquadmateIds {
+                in_party: 43,
+                in_squad: 31,
+                is_dead: 205,
+                is_loyal: 191,
+                vents: None,
+                first_leader: 2875,
+                crew_escort: 2958,
+                biotic: Some(2859),
+                second_leader: 2847,
+            },
+            Squadmate::Tali => SquadmateIds {
+                in_party: 40,
+                in_squad: 28,
+                is_dead: 202,
+                is_loyal: 186,
+                vents: Some(2884),
+                first_leader: 2872,
+                crew_escort: 2955,
+                biotic: None,
+                second_leader: 2844,
+            },
+            Squadmate::Thane => SquadmateIds {
+                in_party: 39,
+                in_squad: 27,
+                is_dead: 201,
+                is_loyal: 185,
+                vents: Some(2883),
+                first_leader: 2871,
+                crew_escort: 2954,
+                biotic: Some(2855),
+                second_leader: 2843,
+            },
+            Squadmate::Zaeed => SquadmateIds {
+                in_party: 44,
+                in_squad: 32,
+                is_dead: 206,
+                is_loyal: 192,
+                vents: None,
+                first_leader: 2876,
+                crew_escort: 2959,
+                biotic: None,
+                second_leader: 2848,
+            },
+        }
+    }
+
+    // Morinth takes Samara's place but has her own role flags
+    fn morinth_ids() -> SquadmateIds {
+        SquadmateIds {
+            first_leader: 3317,
+            crew_escort: 3318,
+            biotic: Some(3319),
+            second_leader: 3320,
+            ..Squadmate::Samara.ids()
+        }
+    }
+
+    fn is_tech_expert(self) -> bool {
+        matches!(self, Squadmate::Kasumi | Squadmate::Legion | Squadmate::Tali)
+    }
+
+    fn is_biotic_expert(self) -> bool {
+        matches!(self, Squadmate::Jack | Squadmate::Samara)
+    }
+
+    fn is_leader(self) -> bool {
+        matches!(self, Squadmate::Garrus | Squadmate::Jacob | Squadmate::Miranda)
+    }
+
+    fn defense(self) -> i32 {
+        match self {
+            Squadmate::Garrus | Squadmate::Grunt | Squadmate::Zaeed => 3,
+            Squadmate::Jacob
+            | Squadmate::Legion
+            | Squadmate::Miranda
+            | Squadmate::Samara
+            | Squadmate::Thane => 1,
+            Squadmate::Jack | Squadmate::Kasumi | Squadmate::Mordin | Squadmate::Tali => 0,
+        }
+    }
+}
+
+struct SquadmateIds {
+    in_party: usize,
+    in_squad: usize,
+    is_dead: usize,
+    is_loyal: usize,
+    vents: Option<usize>,
+    first_leader: usize,
+    crew_escort: usize,
+    biotic: Option<usize>,
+    second_leader: usize,
+}
+
+// Who dies first when the Normandy isn't upgraded
+const SHIELDING_VICTIMS: [Squadmate; 8] = [
+    Squadmate::Kasumi,
+    Squadmate::Legion,
+    Squadmate::Tali,
+    Squadmate::Thane,
+    Squadmate::Garrus,
+    Squadmate::Zaeed,
+    Squadmate::Grunt,
+    Squadmate::Samara,
+];
+const CANNON_VICTIMS: [Squadmate; 6] = [
+    Squadmate::Thane,
+    Squadmate::Garrus,
+    Squadmate::Zaeed,
+    Squadmate::Grunt,
+    Squadmate::Jack,
+    Squadmate::Samara,
+];
+// Who dies first while holding the line, disloyal squadmates go first
+const HOLD_THE_LINE_VICTIMS: [Squadmate; 12] = [
+    Squadmate::Mordin,
+    Squadmate::Tali,
+    Squadmate::Kasumi,
+    Squadmate::Jack,
+    Squadmate::Miranda,
+    Squadmate::Jacob,
+    Squadmate::Garrus,
+    Squadmate::Samara,
+    Squadmate::Legion,
+    Squadmate::Thane,
+    Squadmate::Zaeed,
+    Squadmate::Grunt,
+];
+
+// Plot ids of the crew
+const CREW_ESCORT_NONE: usize = 3710;
+const CREW_SURVIVED: usize = 3351;
+const CREW_DIED: usize = 3349;
+const KELLY_IS_DEAD: usize = 3629;
+const CREW_IS_DEAD: [usize; 3] = [3515, 3631, 3632];
+const CREW_DELAY: usize = 23;
+
+#[derive(Clone, Copy)]
+pub struct SquadmateState {
+    pub squadmate: Squadmate,
+    pub is_loyal: bool,
+    pub in_squad: bool,
+}
+
+/// The state of the save before going through the Omega 4 Relay
+#[derive(Clone)]
+pub struct SuicideMission {
+    pub squadmates: Vec<SquadmateState>,
+    pub is_morinth: bool,
+    pub heavy_ship_armor: bool,
+    pub multicore_shielding: bool,
+    pub thanix_cannon: bool,
+    pub crew_delay: i32,
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub struct Choices {
+    pub tech_specialist: Option<Squadmate>,
+    pub first_leader: Option<Squadmate>,
+    pub biotic_specialist: Option<Squadmate>,
+    pub second_leader: Option<Squadmate>,
+    pub crew_escort: Option<Squadmate>,
+    pub final_squad: [Option<Squadmate>; 2],
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CrewSurvivors {
+    All,
+    Half,
+    OnlyChakwas,
+}
+
+pub struct Outcome {
+    pub dead: IndexMap<Squadmate, String>,
+    pub survivors: Vec<Squadmate>,
+    pub crew: CrewSurvivors,
+    pub shepard_survives: bool,
+}
+
+impl SuicideMission {
+    pub fn from_plot(state: &impl PlotState) -> Self {
+        let squadmates = Squadmate::ALL
+            .iter()
+            .filter_map(|&squadmate| {
+                let ids = squadmate.ids();
+                (state.boolean(ids.in_party) && !state.boolean(ids.is_dead)).then(|| {
+                    SquadmateState {
+                        squadmate,
+                        is_loyal: state.boolean(ids.is_loyal),
+                        in_squad: state.boolean(ids.in_squad),
+                    }
+                })
+            })
+            .collect();
+
+        SuicideMission {
+            squadmates,
+            is_morinth: state.boolean(1664),
+            heavy_ship_armor: state.boolean(237),
+            multicore_shielding: state.boolean(236),
+            thanix_cannon: state.boolean(238),
+            crew_delay: state.integer(CREW_DELAY),
+        }
+    }
+
+    pub fn name(&self, squadmate: Squadmate) -> &'static str {
+        match squadmate {
+            Squadmate::Garrus => "Garrus",
+            Squadmate::Grunt => "Grunt",
+            Squadmate::Jack => "Jack",
+            Squadmate::Jacob => "Jacob",
+            Squadmate::Kasumi => "Kasumi",
+            Squadmate::Legion => "Legion",
+            Squadmate::Miranda => "Miranda",
+            Squadmate::Mordin => "Mordin",
+            Squadmate::Samara if self.is_morinth => "Morinth",
+            Squadmate::Samara => "Samara",
+            Squadmate::Tali => "Tali",
+            Squadmate::Thane => "Thane",
+            Squadmate::Zaeed => "Zaeed",
+        }
+    }
+
+    fn state(&self, squadmate: Squadmate) -> Option<&SquadmateState> {
+        self.squadmates.iter().find(|state| state.squadmate == squadmate)
+    }
+
+    fn is_loyal(&self, squadmate: Squadmate) -> bool {
+        self.state(squadmate).map(|state| state.is_loyal).unwrap_or_default()
+    }
+
+    fn ids(&self, squadmate: Squadmate) -> SquadmateIds {
+        match squadmate {
+            Squadmate::Samara if self.is_morinth => Squadmate::morinth_ids(),
+            _ => squadmate.ids(),
+        }
+    }
+
+    pub fn simulate(&self, choices: &Choices) -> Result<Outcome> {
+        if self.squadmates.len() < 8 {
+            bail!("At least 8 squadmates must be recruited to go through the Omega 4 Relay");
+        }
+
+        let mut dead = IndexMap::new();
+        let is_alive = |dead: &IndexMap<Squadmate, String>, squadmate: Squadmate| {
+            self.state(squadmate).is_some() && !dead.contains_key(&squadmate)
+        };
+        let first_victim = |dead: &IndexMap<Squadmate, String>, victims: &[Squadmate]| {
+            victims.iter().copied().find(|&squadmate| {
+                is_alive(dead, squadmate)
+                    && !self.state(squadmate).map(|state| state.in_squad).unwrap_or_default()
+            })
+        };
+
+        // Omega 4 Relay
+        if !self.heavy_ship_armor && is_alive(&dead, Squadmate::Jack) {
+            dead.insert(Squadmate::Jack, "Killed by the Oculus, no Heavy Ship Armor".to_owned());
+        }
+        if !self.multicore_shielding {
+            if let Some(victim) = first_victim(&dead, &SHIELDING_VICTIMS) {
+                dead.insert(victim, "Killed in the cargo hold, no Multicore Shielding".to_owned());
+            }
+        }
+        if !self.thanix_cannon {
+            if let Some(victim) = first_victim(&dead, &CANNON_VICTIMS) {
+                dead.insert(victim, "Killed by the Collector ship, no Thanix Cannon".to_owned());
+            }
+        }
+
+        // Infiltration
+        let tech = self.choose(&dead, choices.tech_specialist, "tech specialist")?;
+        let first_leader = self.choose(&dead, choices.first_leader, "first fireteam leader")?;
+        if tech == first_leader {
+            bail!("The tech specialist can't also lead the fireteam");
+        }
+        if self.ids(tech).vents.is_none() {
+            bail!("{} can't be the tech specialist", self.name(tech));
+        }
+
+        if !tech.is_tech_expert() || !self.is_loyal(tech) {
+            dead.insert(tech, "Killed in the vents, not a loyal tech expert".to_owned());
+        } else if !first_leader.is_leader() || !self.is_loyal(first_leader) {
+            dead.insert(tech, "Shot in the vents, the fireteam leader failed".to_owned());
+        }
+
+        // The Long Walk
+        let biotic = self.choose(&dead, choices.biotic_specialist, "biotic specialist")?;
+        let second_leader = self.choose(&dead, choices.second_leader, "second fireteam leader")?;
+        if biotic == second_leader {
+            bail!("The biotic specialist can't also lead the fireteam");
+        }
+        if self.ids(biotic).biotic.is_none() {
+            bail!("{} can't be the biotic specialist", self.name(biotic));
+        }
+
+        let crew_escort = match choices.crew_escort {
+            Some(escort) => {
+                let escort = self.choose(&dead, Some(escort), "crew escort")?;
+                if escort == biotic || escort == second_leader {
+                    bail!("The crew escort already has a role during the Long Walk");
+                }
+                Some(escort)
+            }
+            None => None,
+        };
+
+        let final_squad = [
+            self.choose(&dead, choices.final_squad[0], "first squadmate")?,
+            self.choose(&dead, choices.final_squad[1], "second squadmate")?,
+        ];
+        if final_squad[0] == final_squad[1] {
+            bail!("The final squad needs two different squadmates");
+        }
+        if final_squad.iter().any(|&squadmate| Some(squadmate) == crew_escort) {
+            bail!("The crew escort can't be in the final squad");
+        }
+
+        if !biotic.is_biotic_expert() || !self.is_loyal(biotic) {
+            // Shepard's own squad pays for a weak biotic shield
+            let victim = final_squad[0];
+            dead.insert(victim, "Taken by the seekers swarms, the biotic shield failed".to_owned());
+        }
+        if !second_leader.is_leader() || !self.is_loyal(second_leader) {
+            dead.insert(second_leader, "Killed leading the second fireteam".to_owned());
+        }
+
+        let crew = match crew_escort {
+            Some(escort) if is_alive(&dead, escort) && self.crew_delay == 0 => CrewSurvivors::All,
+            Some(escort) if is_alive(&dead, escort) && self.crew_delay < 4 => CrewSurvivors::Half,
+            _ => CrewSurvivors::OnlyChakwas,
+        };
+        if let Some(escort) = crew_escort {
+            if is_alive(&dead, escort) && !self.is_loyal(escort) {
+                dead.insert(escort, "Killed escorting the crew, not loyal".to_owned());
+            }
+        }
+
+        // Final battle
+        for squadmate in final_squad {
+            if is_alive(&dead, squadmate) && !self.is_loyal(squadmate) {
+                dead.insert(squadmate, "Killed in the final battle, not loyal".to_owned());
+            }
+        }
+
+        let mut defenders: Vec<Squadmate> = HOLD_THE_LINE_VICTIMS
+            .iter()
+            .copied()
+            .filter(|&squadmate| {
+                is_alive(&dead, squadmate)
+                    && !final_squad.contains(&squadmate)
+                    && Some(squadmate) != crew_escort
+            })
+            .collect();
+        let casualties = Self::hold_the_line_casualties(
+            defenders.len(),
+            defenders
+                .iter()
+                .map(|&squadmate| squadmate.defense() + self.is_loyal(squadmate) as i32)
+                .sum(),
+        );
+        defenders.sort_by_key(|&squadmate| self.is_loyal(squadmate));
+        for &squadmate in defenders.iter().take(casualties) {
+            dead.insert(squadmate, "Killed holding the line".to_owned());
+        }
+
+        let survivors: Vec<Squadmate> = self
+            .squadmates
+            .iter()
+            .map(|state| state.squadmate)
+            .filter(|squadmate| !dead.contains_key(squadmate))
+            .collect();
+        let shepard_survives = survivors.len() >= 2;
+
+        Ok(Outcome { dead, survivors, crew, shepard_survives })
+    }
+
+    fn choose(
+        &self, dead: &IndexMap<Squadmate, String>, choice: Option<Squadmate>, role: &str,
+    ) -> Result<Squadmate> {
+        match choice {
+            Some(squadmate) if self.state(squadmate).is_none() => {
+                bail!("{} isn't recruited and can't be the {}", self.name(squadmate), role)
+            }
+            Some(squadmate) if dead.contains_key(&squadmate) => {
+                bail!("{} is already dead and can't be the {}", self.name(squadmate), role)
+            }
+            Some(squadmate) => Ok(squadmate),
+            None => bail!("Choose the {}", role),
+        }
+    }
+
+    // `score` is the total, the game compares the average defense of the team
+    fn hold_the_line_casualties(count: usize, score: i32) -> usize {
+        if count == 0 {
+            return 0;
+        }
+        let average = score as f32 / count as f32;
+        let casualties = match count {
+            1 if average >= 2.0 => 0,
+            1 => 1,
+            2 | 3 if average >= 2.0 => 0,
+            2 | 3 if average >= 1.0 => 1,
+            2 | 3 => 2,
+            4 if average >= 2.0 => 0,
+            4 if average >= 1.0 => 1,
+            4 if average >= 0.5 => 2,
+            4 => 3,
+            _ if average >= 2.0 => 0,
+            _ if average >= 1.5 => 1,
+            _ if average >= 0.5 => 2,
+            _ => 3,
+        };
+        casualties.min(count)
+    }
+
+    /// The plots of a save made after the mission
+    pub fn outcome_preset(&self, choices: &Choices, outcome: &Outcome) -> PlotPreset {
+        let mut booleans = IndexMap::new();
+
+        for (&squadmate, _) in &outcome.dead {
+            let ids = self.ids(squadmate);
+            booleans.insert(ids.is_dead, true);
+            booleans.insert(ids.in_party, false);
+            booleans.insert(ids.in_squad, false);
+        }
+
+        if let Some(vents) = choices.tech_specialist.and_then(|tech| self.ids(tech).vents) {
+            booleans.insert(vents, true);
+        }
+        if let Some(leader) = choices.first_leader {
+            booleans.insert(self.ids(leader).first_leader, true);
+        }
+        if let Some(biotic) = choices.biotic_specialist.and_then(|biotic| self.ids(biotic).biotic) {
+            booleans.insert(biotic, true);
+        }
+        if let Some(leader) = choices.second_leader {
+            booleans.insert(self.ids(leader).second_leader, true);
+        }
+        match choices.crew_escort {
+            Some(escort) => {
+                booleans.insert(self.ids(escort).crew_escort, true);
+            }
+            None => {
+                booleans.insert(CREW_ESCORT_NONE, true);
+            }
+        }
+
+        let crew_survived = outcome.crew == CrewSurvivors::All;
+        booleans.insert(CREW_SURVIVED, crew_survived);
+        booleans.insert(CREW_DIED, !crew_survived);
+        booleans.insert(KELLY_IS_DEAD, !crew_survived);
+        for id in CREW_IS_DEAD {
+            booleans.insert(id, outcome.crew == CrewSurvivors::OnlyChakwas);
+        }
+
+        PlotPreset { name: "Suicide Mission outcome".to_owned(), booleans, ..Default::default() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::save_data::{mass_effect_2::Me2SaveGame, shared::plot::BitVec, RcCell};
+    use crate::unreal;
+
+    struct SavePlot<'a> {
+        booleans: &'a BitVec,
+        integers: Vec<i32>,
+    }
+
+    impl PlotState for SavePlot<'_> {
+        fn boolean(&self, id: usize) -> bool {
+            self.booleans.get(id).map(|plot| *plot).unwrap_or_default()
+        }
+
+        fn integer(&self, id: usize) -> i32 {
+            self.integers.get(id).copied().unwrap_or_default()
+        }
+
+        fn is_female(&self) -> bool {
+            false
+        }
+
+        fn level(&self) -> i32 {
+            1
+        }
+    }
+
+    fn everyone_loyal() -> SuicideMission {
+        SuicideMission {
+            squadmates: Squadmate::ALL
+                .iter()
+                .map(|&squadmate| SquadmateState { squadmate, is_loyal: true, in_squad: false })
+                .collect(),
+            is_morinth: false,
+            heavy_ship_armor: true,
+            multicore_shielding: true,
+            thanix_cannon: true,
+            crew_delay: 0,
+        }
+    }
+
+    fn best_choices() -> Choices {
+        Choices {
+            tech_specialist: Some(Squadmate::Tali),
+            first_leader: Some(Squadmate::Garrus),
+            biotic_specialist: Some(Squadmate::Samara),
+            second_leader: Some(Squadmate::Miranda),
+            crew_escort: Some(Squadmate::Mordin),
+            final_squad: [Some(Squadmate::Jack), Some(Squadmate::Legion)],
+        }
+    }
+
+    #[test]
+    fn everyone_survives() -> Result<()> {
+        let mission = everyone_loyal();
+        let outcome = mission.simulate(&best_choices())?;
+
+        assert!(outcome.dead.is_empty());
+        assert_eq!(outcome.survivors.len(), 12);
+        assert!(outcome.crew == CrewSurvivors::All);
+        assert!(outcome.shepard_survives);
+
+        let preset = mission.outcome_preset(&best_choices(), &outcome);
+        assert_eq!(preset.booleans.get(&2884), Some(&true));
+        assert_eq!(preset.booleans.get(&CREW_SURVIVED), Some(&true));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bad_choices() -> Result<()> {
+        let mut mission = everyone_loyal();
+        mission.multicore_shielding = false;
+        mission.squadmates.iter_mut().for_each(|state| {
+            state.is_loyal = state.squadmate != Squadmate::Jacob;
+        });
+
+        let choices = Choices {
+            tech_specialist: Some(Squadmate::Mordin),
+            second_leader: Some(Squadmate::Jacob),
+            crew_escort: Some(Squadmate::Zaeed),
+            ..best_choices()
+        };
+        let outcome = mission.simulate(&choices)?;
+
+        assert!(outcome.dead.contains_key(&Squadmate::Kasumi));
+        assert!(outcome.dead.contains_key(&Squadmate::Mordin));
+        assert!(outcome.dead.contains_key(&Squadmate::Jacob));
+        assert!(outcome.shepard_survives);
+
+        let preset = mission.outcome_preset(&choices, &outcome);
+        assert_eq!(preset.booleans.get(&199), Some(&true));
+        assert_eq!(preset.booleans.get(&37), Some(&false));
+
+        // Kasumi is dead before the Infiltration
+        let choices = Choices { tech_specialist: Some(Squadmate::Kasumi), ..best_choices() };
+        assert!(mission.simulate(&choices).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn simulate_save() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: Me2SaveGame = unreal::Deserializer::from_bytes(&input)?;
+        let plot = me2_save_game.plot.borrow();
+        let state = SavePlot {
+            booleans: &plot.booleans.borrow(),
+            integers: plot.integers.borrow().iter().map(RcCell::get).collect(),
+        };
+
+        // Everyone but Thane is recruited and loyal
+        let mission = SuicideMission::from_plot(&state);
+        assert!(mission.state(Squadmate::Thane).is_none());
+        assert!(mission.squadmates.iter().all(|state| state.is_loyal));
+
+        let choices = Choices {
+            tech_specialist: Some(Squadmate::Legion),
+            first_leader: Some(Squadmate::Tali),
+            final_squad: [Some(Squadmate::Jack), Some(Squadmate::Grunt)],
+            ..best_choices()
+        };
+        let outcome = mission.simulate(&choices)?;
+        let preset = mission.outcome_preset(&choices, &outcome);
+        let written: Vec<_> = preset.booleans.into_iter().collect();
+        let expected = [
+            // Legion dies in the vents, Tali is no leader
+            (198, true),
+            (36, false),
+            (24, false),
+            // The roles
+            (2880, true),
+            (2872, true),
+            (2859, true),
+            (2836, true),
+            (2956, true),
+            // The crew dies
+            (CREW_SURVIVED, false),
+            (CREW_DIED, true),
+            (KELLY_IS_DEAD, true),
+            (3515, false),
+            (3631, false),
+            (3632, false),
+        ];
+        assert_eq!(written, expected);
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ mod galaxy_map;
 pub mod player;
 pub mod plot;
 pub mod plot_db;
-mod squad;
+pub mod squad;
 
 use self::{galaxy_map::*, player::*, plot::*, squad::*};
 
@@ -23,22 +23,22 @@ pub struct Me3SaveGame {
     debug_name: String,
     seconds_played: f32,
     disc: i32,
-    base_level_name: String,
+    pub base_level_name: String,
     base_level_name_display_override: String,
     pub difficulty: Difficulty,
     pub end_game_state: EndGameState,
-    timestamp: SaveTimeStamp,
+    pub timestamp: SaveTimeStamp,
     location: Vector,
     rotation: Rotator,
     current_loading_tip: i32,
     levels: Vec<Level>,
     streaming_states: Vec<StreamingState>,
-    kismet_records: Vec<Kismet>,
+    pub kismet_records: Vec<Kismet>,
     doors: Vec<Door>,
     placeables: Vec<Placeable>,
     pawns: Vec<Guid>,
     pub player: Player,
-    squad: Vec<Henchman>,
+    pub squad: Vec<Henchman>,
     pub plot: PlotTable,
     journal: Journal,
     codex: Codex,
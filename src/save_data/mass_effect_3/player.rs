@@ -6,7 +6,7 @@ use crate::save_data::{
         appearance::Appearance,
         player::{Notoriety, Origin, WeaponLoadout},
     },
-    Guid,
+    Guid, StringRef,
 };
 
 #[rcize_fields]
@@ -20,7 +20,7 @@ pub struct Player {
     pub level: i32,
     pub current_xp: f32,
     pub first_name: String,
-    localized_last_name: i32,
+    localized_last_name: StringRef,
     pub origin: Origin,
     pub notoriety: Notoriety,
     pub talent_points: i32,
@@ -49,8 +49,8 @@ pub struct Player {
     pub current_fuel: f32,
     pub grenades: i32,
     pub face_code: String,
-    localized_class_name: i32,
-    character_guid: Guid,
+    localized_class_name: StringRef,
+    pub character_guid: Guid,
 }
 
 #[rcize_fields]
@@ -58,7 +58,7 @@ pub struct Player {
 #[display(fmt = "{}", name)]
 pub struct Power {
     pub name: String,
-    rank: f32,
+    pub rank: f32,
     evolved_choice_0: i32,
     evolved_choice_1: i32,
     evolved_choice_2: i32,
@@ -29,7 +29,7 @@ mod test {
     use anyhow::Result;
 
     use super::*;
-    use crate::save_data::shared::plot::RawPlotDb;
+    use crate::save_data::shared::plot::{PlotPresetDb, PlotRuleDb, RawPlotDb};
 
     #[test]
     fn deserialize_plot_db() -> Result<()> {
@@ -46,4 +46,20 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn deserialize_plot_preset_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_plot_preset_db.ron")?;
+        let _me3_plot_preset_db: PlotPresetDb = ron::from_str(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_plot_rule_db() -> Result<()> {
+        let input = fs::read_to_string("databases/me3_plot_rule_db.ron")?;
+        let _me3_plot_rule_db: PlotRuleDb = ron::from_str(&input)?;
+
+        Ok(())
+    }
 }
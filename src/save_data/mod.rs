@@ -236,6 +236,11 @@ where
     }
 }
 
+/// An id in the TLK string table of the game, shown with its string once a TLK file is picked
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, From, Display)]
+#[serde(transparent)]
+pub struct StringRef(pub i32);
+
 #[derive(Clone, From, Display, Default)]
 #[display(fmt = "")]
 pub struct Guid(Uuid);
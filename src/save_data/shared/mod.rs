@@ -1,6 +1,9 @@
 pub mod appearance;
 pub mod player;
 pub mod plot;
+pub mod tlk;
+
+use std::fmt;
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -49,6 +52,21 @@ pub struct SaveTimeStamp {
     year: i32,
 }
 
+impl fmt::Display for SaveTimeStamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.seconds_since_midnight.get();
+        write!(
+            f,
+            "{}-{:02}-{:02} {:02}:{:02}",
+            self.year.get(),
+            self.month.get(),
+            self.day.get(),
+            seconds / 3600,
+            seconds % 3600 / 60
+        )
+    }
+}
+
 #[rcize_fields]
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
@@ -95,8 +113,8 @@ pub struct StreamingState {
 #[derive(Deserialize, Serialize, Clone, Default, Display, RawUi)]
 #[display(fmt = "")]
 pub struct Kismet {
-    guid: Guid,
-    value: bool,
+    pub guid: Guid,
+    pub value: bool,
 }
 
 #[rcize_fields]
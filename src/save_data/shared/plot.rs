@@ -1,4 +1,6 @@
-use anyhow::Result;
+use std::ops::RangeInclusive;
+
+use anyhow::{anyhow, bail, Context, Result};
 use bitvec::prelude::*;
 use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -74,15 +76,324 @@ pub struct PlotCodexPage {
     is_new: bool,
 }
 
+/// How many plots can be added past the end of a plot table, so a typo in an id
+/// does not grow the table by millions of plots
+pub const PLOT_GROWTH_MAX: usize = 10_000;
+
+/// How many plots a list of plot ids can select
+pub const PLOT_IDS_MAX: usize = 1_000_000;
+
+/// Checks that the plot `id` can be set in a plot table of `len` plots
+pub fn check_plot_id(id: usize, len: usize) -> Result<()> {
+    if id >= len.saturating_add(PLOT_GROWTH_MAX) {
+        bail!("Plot id {} is out of range, the table has {} plots", id, len);
+    }
+    Ok(())
+}
+
+/// The key of the plot `id` in a ME3 plot table
+pub fn plot_key(id: usize) -> Result<i32> {
+    i32::try_from(id).map_err(|_| anyhow!("Plot id {} is out of range", id))
+}
+
 #[derive(Deserialize, Clone, PartialEq, Eq)]
 pub struct PlotCategory {
     pub booleans: IndexMap<usize, String>,
     pub integers: IndexMap<usize, String>,
 }
 
-#[derive(Deserialize, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, Default)]
 pub struct RawPlotDb {
+    #[serde(default)]
     pub booleans: IndexMap<usize, String>,
+    #[serde(default)]
     pub integers: IndexMap<usize, String>,
+    #[serde(default)]
     pub floats: IndexMap<usize, String>,
 }
+
+impl RawPlotDb {
+    pub fn labels(&self, kind: PlotKind) -> &IndexMap<usize, String> {
+        match kind {
+            PlotKind::Boolean => &self.booleans,
+            PlotKind::Integer => &self.integers,
+            PlotKind::Float => &self.floats,
+        }
+    }
+
+    pub fn labels_mut(&mut self, kind: PlotKind) -> &mut IndexMap<usize, String> {
+        match kind {
+            PlotKind::Boolean => &mut self.booleans,
+            PlotKind::Integer => &mut self.integers,
+            PlotKind::Float => &mut self.floats,
+        }
+    }
+
+    /// Adds the labels of `overlay`, replacing the existing ones with the same id
+    pub fn merge(&mut self, overlay: &RawPlotDb) {
+        for kind in [PlotKind::Boolean, PlotKind::Integer, PlotKind::Float] {
+            let labels = self.labels_mut(kind);
+            for (&id, label) in overlay.labels(kind) {
+                labels.insert(id, label.clone());
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PlotKind {
+    Boolean,
+    Integer,
+    Float,
+}
+
+/// Parses a list of plot ids like `1, 10-20, 42=3`, separated by commas, spaces or new lines.
+/// An id or a range can have its own value after a `=`.
+pub fn parse_plot_ids(input: &str) -> Result<Vec<(RangeInclusive<usize>, Option<&str>)>> {
+    let ids = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            let (ids, value) = match token.split_once('=') {
+                Some((ids, value)) => (ids, Some(value)),
+                None => (token, None),
+            };
+
+            let parse_id = |id: &str| {
+                id.parse::<usize>().with_context(|| format!("Invalid plot id `{}`", token))
+            };
+            let range = match ids.split_once('-') {
+                Some((start, end)) => parse_id(start)?..=parse_id(end)?,
+                None => {
+                    let id = parse_id(ids)?;
+                    id..=id
+                }
+            };
+
+            if range.is_empty() {
+                bail!("Invalid plot range `{}`", token);
+            }
+            Ok((range, value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let count = ids.iter().fold(0_usize, |count, (range, _)| {
+        count.saturating_add(range.end() - range.start()).saturating_add(1)
+    });
+    if count > PLOT_IDS_MAX {
+        bail!("Too many plots, the maximum is {}", PLOT_IDS_MAX);
+    }
+    Ok(ids)
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct PlotPresetDb {
+    pub presets: Vec<PlotPreset>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct PlotPreset {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub conditions: Vec<PlotCondition>,
+    #[serde(default)]
+    pub booleans: IndexMap<usize, bool>,
+    #[serde(default)]
+    pub integers: IndexMap<usize, i32>,
+    #[serde(default)]
+    pub floats: IndexMap<usize, f32>,
+}
+
+impl PlotPreset {
+    pub fn is_empty(&self) -> bool {
+        self.booleans.is_empty() && self.integers.is_empty() && self.floats.is_empty()
+    }
+
+    /// The plot values of `self` that differ from `other`, as a preset to apply on `other`
+    pub fn diff(&self, other: &PlotPreset) -> PlotPreset {
+        PlotPreset {
+            booleans: diff(&self.booleans, &other.booleans),
+            integers: diff(&self.integers, &other.integers),
+            floats: diff(&self.floats, &other.floats),
+            ..Default::default()
+        }
+    }
+}
+
+fn diff<T>(current: &IndexMap<usize, T>, other: &IndexMap<usize, T>) -> IndexMap<usize, T>
+where
+    T: Copy + Default + PartialEq,
+{
+    let changed = current
+        .iter()
+        .filter(|(id, value)| other.get(*id).copied().unwrap_or_default() != **value)
+        .map(|(&id, &value)| (id, value));
+    let removed = other
+        .iter()
+        .filter(|(id, value)| !current.contains_key(*id) && **value != T::default())
+        .map(|(&id, _)| (id, T::default()));
+
+    let mut diff: IndexMap<usize, T> = changed.chain(removed).collect();
+    diff.sort_keys();
+    diff
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub enum PlotCondition {
+    Boolean(usize, bool),
+    IntegerEquals(usize, i32),
+    IntegerAtLeast(usize, i32),
+    IntegerAtMost(usize, i32),
+}
+
+impl PlotCondition {
+    pub fn is_met(&self, state: &impl PlotState) -> bool {
+        match *self {
+            PlotCondition::Boolean(id, value) => state.boolean(id) == value,
+            PlotCondition::IntegerEquals(id, value) => state.integer(id) == value,
+            PlotCondition::IntegerAtLeast(id, value) => state.integer(id) >= value,
+            PlotCondition::IntegerAtMost(id, value) => state.integer(id) <= value,
+        }
+    }
+}
+
+/// What the plot rules are evaluated against
+pub trait PlotState {
+    fn boolean(&self, id: usize) -> bool;
+    fn integer(&self, id: usize) -> i32;
+    fn is_female(&self) -> bool;
+    fn level(&self) -> i32;
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct PlotRuleDb {
+    pub rules: Vec<PlotRule>,
+}
+
+/// A rule is violated when all its `when` conditions are met but not all its `require` ones.
+/// Each fix is a preset applied to the save to restore a consistent state.
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct PlotRule {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub when: Vec<RuleCondition>,
+    pub require: Vec<RuleCondition>,
+    #[serde(default)]
+    pub fixes: Vec<PlotPreset>,
+}
+
+impl PlotRule {
+    pub fn is_violated(&self, state: &impl PlotState) -> bool {
+        self.when.iter().all(|condition| condition.is_met(state))
+            && !self.require.iter().all(|condition| condition.is_met(state))
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub enum RuleCondition {
+    Plot(PlotCondition),
+    Any(Vec<PlotCondition>),
+    PlayerIsFemale(bool),
+    PlayerLevelAtMost(i32),
+}
+
+impl RuleCondition {
+    pub fn is_met(&self, state: &impl PlotState) -> bool {
+        match self {
+            RuleCondition::Plot(condition) => condition.is_met(state),
+            RuleCondition::Any(conditions) => {
+                conditions.iter().any(|condition| condition.is_met(state))
+            }
+            RuleCondition::PlayerIsFemale(is_female) => state.is_female() == *is_female,
+            RuleCondition::PlayerLevelAtMost(level) => state.level() <= *level,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn parse_ids() -> Result<()> {
+        let ids = parse_plot_ids("1, 10-20\n42=3 7-8=false")?;
+        assert_eq!(
+            ids,
+            [(1..=1, None), (10..=20, None), (42..=42, Some("3")), (7..=8, Some("false"))]
+        );
+
+        assert!(parse_plot_ids("1, two").is_err());
+        assert!(parse_plot_ids("20-10").is_err());
+        assert!(parse_plot_ids("0-18446744073709551615").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_user_plot_labels() -> Result<()> {
+        let input = fs::read_to_string("databases/me2_raw_plot_db.ron")?;
+        let mut me2_raw_plot_db: RawPlotDb = ron::from_str(&input)?;
+
+        // Only booleans, the other tables are optional
+        let user_labels: RawPlotDb = ron::from_str("(booleans: { 1: \"My label\" })")?;
+        me2_raw_plot_db.merge(&user_labels);
+
+        assert_eq!(me2_raw_plot_db.booleans.get(&1).map(String::as_str), Some("My label"));
+
+        Ok(())
+    }
+
+    struct TestState {
+        booleans: Vec<usize>,
+        is_female: bool,
+    }
+
+    impl PlotState for TestState {
+        fn boolean(&self, id: usize) -> bool {
+            self.booleans.contains(&id)
+        }
+
+        fn integer(&self, _: usize) -> i32 {
+            0
+        }
+
+        fn is_female(&self) -> bool {
+            self.is_female
+        }
+
+        fn level(&self) -> i32 {
+            1
+        }
+    }
+
+    #[test]
+    fn check_plot_rules() -> Result<()> {
+        let rule: PlotRule = ron::from_str(
+            "(
+                name: \"Dead but in the party\",
+                when: [Plot(Boolean(200, true))],
+                require: [Plot(Boolean(38, false)), PlayerIsFemale(false)],
+            )",
+        )?;
+
+        let mut state = TestState { booleans: vec![38], is_female: false };
+        assert!(!rule.is_violated(&state));
+
+        state.booleans.push(200);
+        assert!(rule.is_violated(&state));
+
+        state.booleans = vec![200];
+        assert!(!rule.is_violated(&state));
+
+        state.is_female = true;
+        assert!(rule.is_violated(&state));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use std::{collections::BTreeMap, convert::TryInto};
+
+use anyhow::{bail, ensure, Context, Result};
+use bitvec::prelude::*;
+
+use crate::save_data::StringRef;
+
+// "Tlk\0"
+const MAGIC: u32 = 0x006B_6C54;
+const HEADER_LEN: usize = 28;
+
+/// A BioWare talk table, the localized strings of a game referenced by their `StringRef`
+#[derive(Default)]
+pub struct TlkFile {
+    strings: BTreeMap<i32, String>,
+}
+
+impl TlkFile {
+    /// Parses the `.tlk` files of ME2, ME3 and their Legendary Edition, or an XML export.
+    /// ME1 and ME1LE have no `.tlk` files, their talk tables are embedded in their packages and
+    /// exported to `.tlk` or `.xml` with Legendary Explorer.
+    pub fn parse(input: &[u8]) -> Result<Self> {
+        if input.get(..4) != Some(&MAGIC.to_le_bytes()) {
+            if let Ok(xml) = std::str::from_utf8(input) {
+                if xml.trim_start_matches('\u{feff}').trim_start().starts_with('<') {
+                    return Self::parse_xml(xml);
+                }
+            }
+        }
+
+        ensure!(input.len() >= HEADER_LEN, "Invalid TLK file: header too short");
+        let header: Vec<u32> = input[..HEADER_LEN]
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        ensure!(header[0] == MAGIC, "Invalid TLK file: wrong magic number");
+
+        // header[1] and header[2] are the version and the min version
+        let male_count = header[3] as usize;
+        let female_count = header[4] as usize;
+        let node_count = header[5] as usize;
+        let data_len = header[6] as usize;
+
+        let pairs = |offset: usize, count: usize| -> Result<Vec<(i32, i32)>> {
+            let bytes = count
+                .checked_mul(8)
+                .and_then(|len| offset.checked_add(len))
+                .and_then(|end| input.get(offset..end))
+                .context("Invalid TLK file: unexpected end of file")?;
+            Ok(bytes
+                .chunks_exact(8)
+                .map(|pair| {
+                    let left = i32::from_le_bytes(pair[..4].try_into().unwrap());
+                    let right = i32::from_le_bytes(pair[4..].try_into().unwrap());
+                    (left, right)
+                })
+                .collect())
+        };
+
+        // The female variants follow the male ones with the same ids, only the male ones are kept
+        let entry_count =
+            male_count.checked_add(female_count).context("Invalid TLK file: too many strings")?;
+        let entries = pairs(HEADER_LEN, entry_count)?;
+        // The entries have been read, their length cannot overflow
+        let nodes_offset = HEADER_LEN + entries.len() * 8;
+        let nodes = pairs(nodes_offset, node_count)?;
+        let data_offset = nodes_offset + nodes.len() * 8;
+        let data = data_offset
+            .checked_add(data_len)
+            .and_then(|data_end| input.get(data_offset..data_end))
+            .context("Invalid TLK file: unexpected end of file")?
+            .view_bits::<Lsb0>();
+
+        let mut strings = BTreeMap::new();
+        for &(id, bit_offset) in entries.iter().take(male_count) {
+            if bit_offset < 0 {
+                continue;
+            }
+            let string = Self::decode(&nodes, data, bit_offset as usize)
+                .with_context(|| format!("Invalid TLK file: failed to decode string {}", id))?;
+            strings.insert(id, string);
+        }
+
+        Ok(TlkFile { strings })
+    }
+
+    // `<string><id>123</id>...<data>text</data></string>`, the female variants follow the male ones
+    // with the same ids, only the male ones are kept
+    fn parse_xml(xml: &str) -> Result<Self> {
+        fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+            let start = xml.find(&format!("<{}>", name))? + name.len() + 2;
+            let len = xml[start..].find(&format!("</{}>", name))?;
+            Some(&xml[start..start + len])
+        }
+
+        let mut strings = BTreeMap::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find("<string>") {
+            let end =
+                rest[start..].find("</string>").context("Invalid TLK file: unclosed string")?;
+            let entry = &rest[start..start + end];
+            rest = &rest[start + end..];
+
+            let id = element(entry, "id").context("Invalid TLK file: string without id")?;
+            let id = id.trim().parse().context("Invalid TLK file: invalid string id")?;
+            let data = element(entry, "data").unwrap_or_default();
+            strings.entry(id).or_insert_with(|| Self::unescape_xml(data));
+        }
+        ensure!(!strings.is_empty(), "Invalid TLK file: no strings");
+
+        Ok(TlkFile { strings })
+    }
+
+    fn unescape_xml(text: &str) -> String {
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    // Walks the huffman tree from the root, a negative node is a character, `\0` ends the string
+    fn decode(
+        nodes: &[(i32, i32)], data: &BitSlice<u8, Lsb0>, bit_offset: usize,
+    ) -> Result<String> {
+        let mut string = String::new();
+        let mut node = 0;
+        for bit in data.get(bit_offset..).context("offset out of bounds")? {
+            let &(left, right) = nodes.get(node).context("node out of bounds")?;
+            let next = if *bit { right } else { left };
+            if next >= 0 {
+                node = next as usize;
+                continue;
+            }
+
+            let c = (-1 - next) as u32;
+            if c == 0 {
+                return Ok(string);
+            }
+            string.push(char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER));
+            node = 0;
+        }
+        bail!("unterminated string")
+    }
+
+    pub fn get(&self, string_ref: StringRef) -> Option<&str> {
+        self.strings.get(&string_ref.0).map(String::as_str)
+    }
+
+    /// The strings containing `text`, case insensitive
+    pub fn search<'a>(&'a self, text: &str) -> impl Iterator<Item = (StringRef, &'a str)> {
+        let text = text.to_lowercase();
+        self.strings
+            .iter()
+            .filter(move |(_, string)| string.to_lowercase().contains(&text))
+            .map(|(id, string)| (StringRef(*id), string.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // `\0` => 0, `a` => 10, `b` => 11
+    fn tlk_file() -> Vec<u8> {
+        let header = [MAGIC, 3, 2, 2, 1, 2, 2];
+        let entries = [(100, 0), (101, 5), (100, 0)];
+        let nodes = [(-1, 1), (-1 - 'a' as i32, -1 - 'b' as i32)];
+        // "ab\0" then "ba\0"
+        let data = [0b1110_1101, 0b0000_0000];
+
+        let mut input: Vec<u8> = header.iter().flat_map(|value| value.to_le_bytes()).collect();
+        for (left, right) in entries.iter().chain(&nodes) {
+            input.extend(left.to_le_bytes());
+            input.extend(right.to_le_bytes());
+        }
+        input.extend(data);
+        input
+    }
+
+    #[test]
+    fn parse_tlk() -> Result<()> {
+        let tlk = TlkFile::parse(&tlk_file())?;
+
+        assert_eq!(tlk.get(StringRef(100)), Some("ab"));
+        assert_eq!(tlk.get(StringRef(101)), Some("ba"));
+        assert_eq!(tlk.get(StringRef(102)), None);
+
+        let found: Vec<_> = tlk.search("B").map(|(id, _)| id.0).collect();
+        assert_eq!(found, [100, 101]);
+        let found: Vec<_> = tlk.search("ab").map(|(id, _)| id.0).collect();
+        assert_eq!(found, [100]);
+
+        assert!(TlkFile::parse(&[0; 8]).is_err());
+
+        // Legendary Explorer XML export
+        let xml = "<tlkFile Name=\"GlobalTlk_tlk\">\n\
+            <string><id>100</id><flags>1</flags><data>Liara T&apos;Soni</data></string>\n\
+            <string><id>101</id><flags>1</flags><data>a &amp; b</data></string>\n\
+            <string><id>100</id><flags>1</flags><data>female</data></string>\n\
+            </tlkFile>";
+        let tlk = TlkFile::parse(xml.as_bytes())?;
+        assert_eq!(tlk.get(StringRef(100)), Some("Liara T'Soni"));
+        assert_eq!(tlk.get(StringRef(101)), Some("a & b"));
+        assert!(TlkFile::parse(b"<tlkFile></tlkFile>").is_err());
+
+        // Counts that would overflow
+        let mut input = tlk_file();
+        input[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        input[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(TlkFile::parse(&input).is_err());
+        Ok(())
+    }
+}
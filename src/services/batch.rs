@@ -0,0 +1,340 @@
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    gui::shared::{save_plot_snapshot, set_save_preset_values},
+    save_data::shared::plot::{PlotPreset, PlotState},
+    services::{
+        rpc::{self, BatchReport, CommandLineBatch, CommandLineOperation, RpcFile},
+        save_handler::{SaveGame, SaveHandlerProvider},
+        script,
+    },
+};
+
+/// What a batch applies to each save
+#[derive(Clone, PartialEq)]
+pub enum BatchOperation {
+    Preset(Box<PlotPreset>),
+    Script(String),
+    Assignments(Vec<Assignment>),
+}
+
+/// `field = value`, the field is a path from the script `save` like `player.credits`,
+/// or a plot like `plot.bool[42]`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Assignment {
+    field: String,
+    value: String,
+}
+
+impl Assignment {
+    /// One assignment per line, or separated by `;`
+    pub fn parse(input: &str) -> Result<Vec<Assignment>> {
+        let assignments = input
+            .split(['\n', ';'])
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (field, value) = line
+                    .split_once('=')
+                    .with_context(|| format!("`{}` is not an assignment", line))?;
+                let (field, value) = (field.trim(), value.trim());
+
+                if plot_field(field).is_none() && !is_field_path(field) {
+                    bail!("`{}` is not a field", field);
+                }
+                if !is_literal(value) {
+                    bail!("`{}` is not a boolean, a number or a quoted string", value);
+                }
+                Ok(Assignment { field: field.to_owned(), value: value.to_owned() })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if assignments.is_empty() {
+            bail!("No field to set");
+        }
+        Ok(assignments)
+    }
+
+    fn to_script(&self) -> String {
+        match plot_field(&self.field) {
+            Some((table, kind, id)) => {
+                format!("save.{}.set_{}({}, {});", table, kind, id, self.value)
+            }
+            None => format!("save.{} = {};", self.field, self.value),
+        }
+    }
+}
+
+// `plot.bool[42]` => (`plot`, `bool`, 42)
+fn plot_field(field: &str) -> Option<(&str, &str, usize)> {
+    let (table, rest) = field.split_once('.')?;
+    let (kind, id) = rest.strip_suffix(']')?.split_once('[')?;
+    let is_valid =
+        ["plot", "me1_plot"].contains(&table) && ["bool", "int", "float"].contains(&kind);
+    is_valid.then(|| id.trim().parse().ok()).flatten().map(|id| (table, kind, id))
+}
+
+fn is_field_path(field: &str) -> bool {
+    field.split('.').all(|name| {
+        let mut chars = name.chars();
+        chars.next().map(|c| c.is_ascii_alphabetic() || c == '_').unwrap_or_default()
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn is_literal(value: &str) -> bool {
+    let is_string = value.len() >= 2
+        && value.starts_with('"')
+        && value.ends_with('"')
+        && !value[1..value.len() - 1].contains('"');
+    matches!(value, "true" | "false")
+        || value.parse::<i32>().is_ok()
+        || value.parse::<f32>().is_ok()
+        || is_string
+}
+
+// What the preset conditions read of a save
+struct PresetState {
+    plot: PlotPreset,
+    is_female: bool,
+    level: i32,
+}
+
+impl PresetState {
+    fn read(save_game: &SaveGame) -> Self {
+        let (is_female, level) = match save_game {
+            SaveGame::MassEffect1 { save_game, .. } => {
+                let save_game = save_game.borrow();
+                let player = save_game.player.borrow();
+                (player.is_female(), player.xp_level().unwrap_or_default())
+            }
+            SaveGame::MassEffect1Le { save_game, .. } => {
+                let save_game = save_game.borrow();
+                let save_data = save_game.save_data.borrow();
+                let player = save_data.player();
+                (player.is_female(), player.level())
+            }
+            SaveGame::MassEffect1LePs4 { save_game, .. } => {
+                let save_data = save_game.borrow();
+                let player = save_data.player();
+                (player.is_female(), player.level())
+            }
+            SaveGame::MassEffect2 { save_game, .. } => {
+                let save_game = save_game.borrow();
+                let player = save_game.player();
+                (player.is_female(), player.level())
+            }
+            SaveGame::MassEffect2Le { save_game, .. } => {
+                let save_game = save_game.borrow();
+                let player = save_game.player();
+                (player.is_female(), player.level())
+            }
+            SaveGame::MassEffect3 { save_game, .. } => {
+                let save_game = save_game.borrow();
+                let player = save_game.player();
+                (player.is_female(), player.level())
+            }
+        };
+        PresetState { plot: save_plot_snapshot(save_game), is_female, level }
+    }
+}
+
+impl PlotState for PresetState {
+    fn boolean(&self, id: usize) -> bool {
+        self.plot.booleans.get(&id).copied().unwrap_or_default()
+    }
+
+    fn integer(&self, id: usize) -> i32 {
+        self.plot.integers.get(&id).copied().unwrap_or_default()
+    }
+
+    fn is_female(&self) -> bool {
+        self.is_female
+    }
+
+    fn level(&self) -> i32 {
+        self.level
+    }
+}
+
+/// Applies `operation` to the save and describes what has been done
+pub fn apply(save_game: &SaveGame, operation: &BatchOperation) -> Result<String> {
+    match operation {
+        BatchOperation::Preset(preset) => {
+            let state = PresetState::read(save_game);
+            let unmet =
+                preset.conditions.iter().filter(|condition| !condition.is_met(&state)).count();
+            if unmet > 0 {
+                bail!("`{}` has not been applied, {} condition(s) not met", preset.name, unmet);
+            }
+
+            set_save_preset_values(save_game, preset)?;
+            Ok(format!("`{}` applied", preset.name))
+        }
+        BatchOperation::Script(script) => run_script(save_game, script),
+        BatchOperation::Assignments(assignments) => {
+            let script = assignments.iter().map(Assignment::to_script).collect::<Vec<_>>();
+            run_script(save_game, &script.join("\n"))?;
+            Ok(format!("{} field(s) set", assignments.len()))
+        }
+    }
+}
+
+fn run_script(save_game: &SaveGame, script: &str) -> Result<String> {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    {
+        let output = Rc::clone(&output);
+        script::run(save_game, script, move |line| output.borrow_mut().push(line))?;
+    }
+
+    let output = output.take();
+    if output.is_empty() {
+        Ok(String::from("Script run"))
+    } else {
+        Ok(output.join(" / "))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct BatchResult {
+    pub path: PathBuf,
+    pub result: Result<String, String>,
+}
+
+/// Opens, edits and saves each file one after the other, the native side backs up each of them
+pub async fn run(paths: Vec<PathBuf>, operation: &BatchOperation) -> Vec<BatchResult> {
+    let mut results = Vec::with_capacity(paths.len());
+    for path in paths {
+        let result = edit_file(path.clone(), operation).await.map_err(|err| format!("{:#}", err));
+        results.push(BatchResult { path, result });
+    }
+    results
+}
+
+async fn edit_file(path: PathBuf, operation: &BatchOperation) -> Result<String> {
+    let RpcFile { path, file } = rpc::reload_save(path).await?;
+    let save_game = SaveHandlerProvider::deserialize(path.clone(), file.decode()?)?;
+    let done = apply(&save_game, operation)?;
+
+    let rpc_file = SaveHandlerProvider::serialize(path, Rc::new(save_game))?;
+    rpc::save_file(rpc_file).await?;
+    Ok(done)
+}
+
+/// One line per save and a summary
+pub fn report(results: &[BatchResult]) -> String {
+    let mut lines: Vec<_> = results
+        .iter()
+        .map(|BatchResult { path, result }| match result {
+            Ok(done) => format!("OK     {} - {}", path.display(), done),
+            Err(err) => format!("FAILED {} - {}", path.display(), err),
+        })
+        .collect();
+
+    let failed = results.iter().filter(|result| result.result.is_err()).count();
+    lines.push(format!("{} save(s) edited, {} failed", results.len() - failed, failed));
+    lines.join("\n")
+}
+
+/// Runs the batch given on the command line, the native side exits once the report is sent
+pub async fn run_command_line(batch: CommandLineBatch) -> Result<()> {
+    let CommandLineBatch { paths, operation } = batch;
+
+    let operation = match operation {
+        CommandLineOperation::Preset(RpcFile { file, .. }) => {
+            let ron = String::from_utf8(file.decode()?)?;
+            ron::from_str(&ron)
+                .map(|preset| BatchOperation::Preset(Box::new(preset)))
+                .context("Failed to parse the plot preset")
+        }
+        CommandLineOperation::Script(RpcFile { file, .. }) => {
+            String::from_utf8(file.decode()?).map(BatchOperation::Script).map_err(Into::into)
+        }
+        CommandLineOperation::Assignments(assignments) => {
+            Assignment::parse(&assignments.join("\n")).map(BatchOperation::Assignments)
+        }
+    };
+
+    let report = match operation {
+        Ok(operation) => {
+            let results = run(paths, &operation).await;
+            let failed = results.iter().filter(|result| result.result.is_err()).count();
+            BatchReport { text: report(&results), failed }
+        }
+        Err(err) => BatchReport { text: format!("{:#}", err), failed: paths.len().max(1) },
+    };
+    rpc::finish_batch(report).await
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::save_data::shared::plot::PlotCondition;
+
+    #[test]
+    fn apply_operations() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("ME2Save.pcsav".into(), input)?;
+
+        // Assignments
+        let assignments =
+            Assignment::parse("player.credits = 123456; plot.bool[42] = true\nplot.int[7]=3")?;
+        assert_eq!(apply(&save_game, &BatchOperation::Assignments(assignments))?, "3 field(s) set");
+        let plot = save_plot_snapshot(&save_game);
+        assert_eq!(plot.booleans.get(&42), Some(&true));
+        assert_eq!(plot.integers.get(&7), Some(&3));
+        if let SaveGame::MassEffect2 { save_game, .. } = &save_game {
+            let save_game = save_game.borrow();
+            assert_eq!(save_game.player().credits.get(), 123456);
+        }
+
+        assert!(Assignment::parse("player.credits").is_err());
+        assert!(Assignment::parse("player.credits = 1 + 1").is_err());
+        assert!(Assignment::parse("plot.bool[x] = true").is_err());
+        assert!(Assignment::parse(" ; ").is_err());
+        assert_eq!(
+            Assignment::parse(r#"player.first_name = "Jane""#)?[0].to_script(),
+            r#"save.player.first_name = "Jane";"#
+        );
+
+        // Preset
+        let mut preset = PlotPreset {
+            name: String::from("Test"),
+            conditions: vec![PlotCondition::Boolean(42, false)],
+            booleans: [(43, true)].into_iter().collect(),
+            ..Default::default()
+        };
+        assert!(apply(&save_game, &BatchOperation::Preset(Box::new(preset.clone()))).is_err());
+        preset.conditions = vec![PlotCondition::IntegerEquals(7, 3)];
+        apply(&save_game, &BatchOperation::Preset(Box::new(preset)))?;
+        assert_eq!(save_plot_snapshot(&save_game).booleans.get(&43), Some(&true));
+
+        // The player is read from the save, even from the ME1 property tree
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let me1_save_game =
+            SaveHandlerProvider::deserialize("ME1Save.MassEffectSave".into(), input)?;
+        let state = PresetState::read(&me1_save_game);
+        assert!(state.is_female);
+        assert_eq!(state.level, 60);
+
+        // Script
+        let script = BatchOperation::Script(String::from("print(save.plot.get_int(7));"));
+        assert_eq!(apply(&save_game, &script)?, "3");
+
+        // Report
+        let results = vec![
+            BatchResult { path: "a.pcsav".into(), result: Ok(String::from("Script run")) },
+            BatchResult { path: "b.pcsav".into(), result: Err(String::from("Oops")) },
+        ];
+        assert_eq!(
+            report(&results),
+            "OK     a.pcsav - Script run\nFAILED b.pcsav - Oops\n1 save(s) edited, 1 failed"
+        );
+
+        Ok(())
+    }
+}
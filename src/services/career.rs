@@ -0,0 +1,266 @@
+use std::{
+    mem,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+use uuid::Uuid;
+
+use crate::{
+    save_data::{mass_effect_1_le::Me1LeSaveData, Guid},
+    services::save_handler::SaveGame,
+};
+
+/// Renames the character everywhere it appears in the save and returns the old name.
+/// For ME1LE the career id follows the name, keeping its number.
+pub fn rename(save_game: &SaveGame, name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("The name cannot be empty");
+    }
+
+    let old_name = match save_game {
+        SaveGame::MassEffect1 { .. } => bail!(NOT_SUPPORTED),
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let mut save_data = save_game.save_data.borrow_mut();
+            let old_name = save_data.player().first_name().clone();
+            let number = career_number(&save_data.character_id()).to_owned();
+            me1_le_set_identity(&mut save_data, name, &format!("{}{}", name, number));
+            old_name
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => {
+            let mut save_data = save_game.borrow_mut();
+            let old_name = save_data.player().first_name().clone();
+            let number = career_number(&save_data.character_id()).to_owned();
+            me1_le_set_identity(&mut save_data, name, &format!("{}{}", name, number));
+            old_name
+        }
+        SaveGame::MassEffect2 { save_game, .. } => mem::replace(
+            &mut *save_game.borrow_mut().player_mut().first_name_mut(),
+            name.to_owned(),
+        ),
+        SaveGame::MassEffect2Le { save_game, .. } => mem::replace(
+            &mut *save_game.borrow_mut().player_mut().first_name_mut(),
+            name.to_owned(),
+        ),
+        SaveGame::MassEffect3 { save_game, .. } => mem::replace(
+            &mut *save_game.borrow_mut().player_mut().first_name_mut(),
+            name.to_owned(),
+        ),
+    };
+    Ok(old_name)
+}
+
+/// The career directory name once the character has been renamed from `old_name`
+pub fn renamed_career_dir_name(
+    save_game: &SaveGame, old_name: &str, old_dir: &str,
+) -> Result<String> {
+    match save_game {
+        SaveGame::MassEffect2 { .. }
+        | SaveGame::MassEffect2Le { .. }
+        | SaveGame::MassEffect3 { .. } => match old_dir.strip_prefix(old_name) {
+            Some(rest) => Ok(format!("{}{}", first_name(save_game), rest)),
+            None => Ok(old_dir.to_owned()),
+        },
+        _ => career_dir_name(save_game, 0),
+    }
+}
+
+/// Gives the save a new career identity, so the game sees it as another playthrough.
+/// `seed` must be random, it is turned into the new ids.
+/// Returns the name of the folder of the new career.
+pub fn reidentify(save_game: &SaveGame, seed: u128) -> Result<String> {
+    match save_game {
+        SaveGame::MassEffect1 { .. } => bail!(NOT_SUPPORTED),
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let mut save_data = save_game.save_data.borrow_mut();
+            let name = save_data.player().first_name().clone();
+            me1_le_set_identity(&mut save_data, &name, &format!("{}{:02}", name, seed));
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => {
+            let mut save_data = save_game.borrow_mut();
+            let name = save_data.player().first_name().clone();
+            me1_le_set_identity(&mut save_data, &name, &format!("{}{:02}", name, seed));
+        }
+        // ME2 saves have no career id, the career is only identified by its folder,
+        // which is named after the seed
+        SaveGame::MassEffect2 { .. } | SaveGame::MassEffect2Le { .. } => (),
+        SaveGame::MassEffect3 { save_game, .. } => {
+            *save_game.borrow_mut().player_mut().character_guid_mut() =
+                Guid::from(Uuid::from_u128(seed));
+        }
+    }
+    career_dir_name(save_game, seed)
+}
+
+/// The name of the folder holding the saves of the career.
+/// ME1LE uses the career id, ME2 and ME3 `Name_Class_Id`.
+pub fn career_dir_name(save_game: &SaveGame, seed: u128) -> Result<String> {
+    let (name, class, id) = match save_game {
+        SaveGame::MassEffect1 { .. } => bail!(NOT_SUPPORTED),
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            return Ok(save_game.borrow().save_data.borrow().character_id().clone())
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => {
+            return Ok(save_game.borrow().character_id().clone())
+        }
+        SaveGame::MassEffect2 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let player = save_game.player();
+            let (name, class) = (player.first_name().clone(), player.class_name().clone());
+            (name, class, seed)
+        }
+        SaveGame::MassEffect2Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let player = save_game.player();
+            let (name, class) = (player.first_name().clone(), player.class_name().clone());
+            (name, class, seed)
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let player = save_game.player();
+            let guid = player.character_guid().hyphenated();
+            let id = u128::from_str_radix(&guid.replace('-', ""), 16).unwrap_or(seed);
+            let (name, class) = (player.first_name().clone(), player.class_name().clone());
+            (name, class, id)
+        }
+    };
+
+    let class = class.rsplit(['.', '_']).next().unwrap_or(&class).to_owned();
+    let class = class.strip_prefix("Player").unwrap_or(&class);
+    Ok(format!("{}_{}_{:07x}", name, class, id >> 100))
+}
+
+/// ME1LE save files start with the career id, the other games keep the same file name
+pub fn save_file_name(save_game: &SaveGame, old_career: &str, file_path: &Path) -> PathBuf {
+    let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+    let new_career = match save_game {
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            save_game.borrow().save_data.borrow().character_id().clone()
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => save_game.borrow().character_id().clone(),
+        _ => return PathBuf::from(file_name.as_ref()),
+    };
+
+    match file_name.strip_prefix(old_career) {
+        Some(rest) => PathBuf::from(format!("{}{}", new_career, rest)),
+        None => PathBuf::from(file_name.as_ref()),
+    }
+}
+
+fn first_name(save_game: &SaveGame) -> String {
+    match save_game {
+        SaveGame::MassEffect1 { .. } => String::new(),
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let save_data = save_game.save_data.borrow();
+            let name = save_data.player().first_name().clone();
+            name
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => {
+            let save_data = save_game.borrow();
+            let name = save_data.player().first_name().clone();
+            name
+        }
+        SaveGame::MassEffect2 { save_game, .. } => save_game.borrow().player().first_name().clone(),
+        SaveGame::MassEffect2Le { save_game, .. } => {
+            save_game.borrow().player().first_name().clone()
+        }
+        SaveGame::MassEffect3 { save_game, .. } => save_game.borrow().player().first_name().clone(),
+    }
+}
+
+const NOT_SUPPORTED: &str = "Careers of Mass Effect 1 saves cannot be edited";
+
+// `Shepard01` => `01`
+fn career_number(character_id: &str) -> &str {
+    let name_len = character_id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    &character_id[name_len..]
+}
+
+fn me1_le_set_identity(save_data: &mut Me1LeSaveData, name: &str, character_id: &str) {
+    let old_name = save_data.player().first_name().clone();
+    let old_id = save_data.character_id().clone();
+
+    // The display name is what the load menu shows, it contains both the name and the id
+    let display_name = save_data
+        .display_name()
+        .split(' ')
+        .map(|token| match token {
+            token if token == old_id => character_id,
+            token if token == old_name => name,
+            token => token,
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let file_name = match save_data.file_name().strip_prefix(old_id.as_str()) {
+        Some(rest) => format!("{}{}", character_id, rest),
+        None => save_data.file_name().clone(),
+    };
+
+    *save_data.display_name_mut() = display_name;
+    *save_data.file_name_mut() = file_name;
+    *save_data.character_id_mut() = character_id.to_owned();
+    *save_data.player_mut().first_name_mut() = name.to_owned();
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::services::save_handler::SaveHandlerProvider;
+
+    #[test]
+    fn rename_and_reidentify() -> Result<()> {
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("Clare00_QuickSave.pcsav".into(), input)?;
+        assert_eq!(rename(&save_game, "Jane")?, "Clare");
+        assert_eq!(renamed_career_dir_name(&save_game, "Clare", "Clare00")?, "Jane00");
+        assert_eq!(reidentify(&save_game, 42)?, "Jane42");
+
+        if let SaveGame::MassEffect1Le { save_game, .. } = &save_game {
+            let save_game = save_game.borrow();
+            let save_data = save_game.save_data.borrow();
+            assert_eq!(*save_data.character_id(), "Jane42");
+            assert_eq!(*save_data.file_name(), "Jane42_QuickSave");
+            assert_eq!(*save_data.player().first_name(), "Jane");
+            assert!(save_data.display_name().contains(" Jane "));
+            assert!(save_data.display_name().contains(" Jane42 "));
+            assert!(!save_data.display_name().contains("Clare"));
+        } else {
+            unreachable!();
+        }
+        assert_eq!(career_dir_name(&save_game, 0)?, "Jane42");
+        assert_eq!(
+            save_file_name(&save_game, "Clare00", Path::new("Clare00/Clare00_QuickSave.pcsav")),
+            PathBuf::from("Jane42_QuickSave.pcsav")
+        );
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("ME3Save.pcsav".into(), input)?;
+        let old_name = rename(&save_game, "Jane")?;
+        let renamed =
+            renamed_career_dir_name(&save_game, &old_name, &format!("{}_1_abc", old_name))?;
+        assert_eq!(renamed, "Jane_1_abc");
+        assert!(reidentify(&save_game, 0x1234567 << 100)?.ends_with("_1234567"));
+        assert!(career_dir_name(&save_game, 0)?.starts_with("Jane_"));
+        assert!(career_dir_name(&save_game, 0)?.ends_with("_1234567"));
+
+        assert!(rename(&save_game, " ").is_err());
+
+        // Full seeds, not a hundred identities
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("Clare00_QuickSave.pcsav".into(), input)?;
+        assert_eq!(reidentify(&save_game, 12345)?, "Clare12345");
+
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("ME2Save.pcsav".into(), input)?;
+        let dir_name = reidentify(&save_game, 0xabc << 100)?;
+        assert!(dir_name.ends_with("_0000abc"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use std::any;
+
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Data copied from a save to be pasted in another open save.
+/// The value is kept as RON with its type, so it can only be pasted as the same kind of data.
+#[derive(Clone, PartialEq)]
+pub struct Clipboard {
+    pub label: String,
+    type_name: &'static str,
+    ron: String,
+}
+
+impl Clipboard {
+    pub fn copy<T: Serialize>(label: impl Into<String>, value: &T) -> Result<Self> {
+        Ok(Clipboard {
+            label: label.into(),
+            type_name: any::type_name::<T>(),
+            ron: ron::to_string(value)?,
+        })
+    }
+
+    pub fn holds<T>(&self) -> bool {
+        self.type_name == any::type_name::<T>()
+    }
+
+    /// `None` if the clipboard holds another kind of data
+    pub fn paste<T: DeserializeOwned>(&self) -> Option<Result<T>> {
+        self.holds::<T>().then(|| Ok(ron::from_str(&self.ron)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::{
+        save_data::{
+            mass_effect_1_le::squad::Henchman as Me1LeHenchman,
+            mass_effect_2::squad::Henchman as Me2Henchman,
+            mass_effect_3::squad::Henchman as Me3Henchman, shared::appearance::HeadMorph,
+        },
+        services::save_handler::{SaveGame, SaveHandlerProvider},
+    };
+
+    #[test]
+    fn copy_paste() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("ME2Save.pcsav".into(), input)?;
+        if let SaveGame::MassEffect2 { save_game, .. } = save_game {
+            let save_game = save_game.borrow();
+            let henchman = save_game.squad()[0].clone();
+            let clipboard = Clipboard::copy(henchman.borrow().tag().clone(), &*henchman.borrow())?;
+            assert!(clipboard.holds::<Me2Henchman>());
+            assert!(clipboard.paste::<HeadMorph>().is_none());
+
+            let pasted: Me2Henchman = clipboard.paste().unwrap()?;
+            assert_eq!(*pasted.tag(), *henchman.borrow().tag());
+            assert_eq!(pasted.powers().len(), henchman.borrow().powers().len());
+        }
+
+        let input = fs::read("test/ME3Save.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("ME3Save.pcsav".into(), input)?;
+        if let SaveGame::MassEffect3 { save_game, .. } = save_game {
+            let save_game = save_game.borrow();
+            let henchman = save_game.squad()[0].clone();
+            let pasted: Me3Henchman = Clipboard::copy("", &*henchman.borrow())?.paste().unwrap()?;
+            assert_eq!(*pasted.tag(), *henchman.borrow().tag());
+
+            let player = save_game.player();
+            let appearance = player.appearance();
+            let head_morph = appearance.head_morph();
+            let head_morph = head_morph.as_ref().unwrap();
+            let pasted: HeadMorph = Clipboard::copy("", &*head_morph.borrow())?.paste().unwrap()?;
+            assert_eq!(*pasted.hair_mesh(), *head_morph.borrow().hair_mesh());
+        }
+
+        let input = fs::read("test/ME1LeSave.pcsav")?;
+        let save_game = SaveHandlerProvider::deserialize("ME1LeSave.pcsav".into(), input)?;
+        if let SaveGame::MassEffect1Le { save_game, .. } = save_game {
+            let save_game = save_game.borrow();
+            let save_data = save_game.save_data.borrow();
+            let henchman = save_data.squad()[0].clone();
+            let pasted: Me1LeHenchman =
+                Clipboard::copy("", &*henchman.borrow())?.paste().unwrap()?;
+            assert_eq!(*pasted.tag(), *henchman.borrow().tag());
+        }
+
+        Ok(())
+    }
+}
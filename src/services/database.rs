@@ -1,56 +1,107 @@
-use std::rc::Rc;
+use std::{path::PathBuf, rc::Rc};
 
 use anyhow::{Context as ErrorContext, Error, Result};
-use yew::{prelude::*, ContextProvider};
+use ron::ser::PrettyConfig;
+use yew::{context::ContextHandle, prelude::*, ContextProvider};
 
 use crate::{
+    gui::{i18n, Theme},
     save_data::{
-        mass_effect_1::plot_db::Me1PlotDb, mass_effect_1_le::item_db::Me1ItemDb,
-        mass_effect_1_le::player_class_db::Me1LePlayerClassDb, mass_effect_2::plot_db::Me2PlotDb,
-        mass_effect_3::plot_db::Me3PlotDb, shared::plot::RawPlotDb,
+        mass_effect_1::player_class_db::Me1PlayerClassDb,
+        mass_effect_1::plot_db::Me1PlotDb,
+        mass_effect_1_le::item_db::Me1ItemDb,
+        mass_effect_1_le::player_class_db::Me1LePlayerClassDb,
+        mass_effect_1_le::talent_db::Me1LeTalentDb,
+        mass_effect_2::plot_db::Me2PlotDb,
+        mass_effect_3::plot_db::Me3PlotDb,
+        shared::{
+            plot::{PlotKind, PlotPresetDb, PlotRuleDb, RawPlotDb},
+            tlk::TlkFile,
+        },
+    },
+    services::{
+        rpc::{self, Base64File, RpcFile},
+        save_handler::{SaveGame, SaveHandler},
     },
-    services::rpc,
 };
 
 pub enum Type {
     Me1LePlayerClasses,
+    Me1LeTalents,
     Me1Plot,
     Me1RawPlot,
+    Me1PlotPresets,
     Me1Items,
+    Me1PlayerClasses,
     Me2Plot,
     Me2RawPlot,
+    Me2PlotPresets,
+    Me2PlotRules,
     Me3Plot,
     Me3RawPlot,
+    Me3PlotPresets,
+    Me3PlotRules,
 }
 
 pub enum Database {
     Me1LePlayerClasses(Me1LePlayerClassDb),
+    Me1LeTalents(Me1LeTalentDb),
     Me1Plot(Me1PlotDb),
-    Me1RawPlot(RawPlotDb),
+    Me1RawPlot(RawPlotDb, RawPlotDb),
+    Me1PlotPresets(PlotPresetDb),
     Me1Items(Me1ItemDb),
+    Me1PlayerClasses(Me1PlayerClassDb),
     Me2Plot(Me2PlotDb),
-    Me2RawPlot(RawPlotDb),
+    Me2RawPlot(RawPlotDb, RawPlotDb),
+    Me2PlotPresets(PlotPresetDb),
+    Me2PlotRules(PlotRuleDb),
     Me3Plot(Me3PlotDb),
-    Me3RawPlot(RawPlotDb),
+    Me3RawPlot(RawPlotDb, RawPlotDb),
+    Me3PlotPresets(PlotPresetDb),
+    Me3PlotRules(PlotRuleDb),
+}
+
+pub enum UserPlotLabelsAction {
+    Set(PlotKind, usize, String),
+    Remove(PlotKind, usize),
+    Import,
+    Export,
 }
 
 pub enum Msg {
     LoadDatabase(Type),
     DatabaseLoaded(Box<Database>),
+    UserPlotLabels(Theme, UserPlotLabelsAction),
+    UserPlotLabelsImported(Theme, RawPlotDb),
+    SaveHandler(SaveHandler),
+    TlkLoaded(Option<Rc<TlkFile>>),
     Error(Error),
+    Noop,
 }
 
 #[derive(Clone, Default)]
 pub struct Databases {
     me1_le_player_classes: Option<Rc<Me1LePlayerClassDb>>,
+    me1_le_talents: Option<Rc<Me1LeTalentDb>>,
     me1_plot: Option<Rc<Me1PlotDb>>,
     me1_raw_plot: Option<Rc<RawPlotDb>>,
+    me1_user_plot_labels: Option<Rc<RawPlotDb>>,
+    me1_plot_presets: Option<Rc<PlotPresetDb>>,
     me1_item_db: Option<Rc<Me1ItemDb>>,
+    me1_player_classes: Option<Rc<Me1PlayerClassDb>>,
     me2_plot: Option<Rc<Me2PlotDb>>,
     me2_raw_plot: Option<Rc<RawPlotDb>>,
+    me2_user_plot_labels: Option<Rc<RawPlotDb>>,
+    me2_plot_presets: Option<Rc<PlotPresetDb>>,
+    me2_plot_rules: Option<Rc<PlotRuleDb>>,
     me3_plot: Option<Rc<Me3PlotDb>>,
     me3_raw_plot: Option<Rc<RawPlotDb>>,
+    me3_user_plot_labels: Option<Rc<RawPlotDb>>,
+    me3_plot_presets: Option<Rc<PlotPresetDb>>,
+    me3_plot_rules: Option<Rc<PlotRuleDb>>,
+    tlk: Option<Rc<TlkFile>>,
     load_callback: Callback<Type>,
+    user_plot_labels_callback: Callback<(Theme, UserPlotLabelsAction)>,
 }
 
 impl Databases {
@@ -61,6 +112,13 @@ impl Databases {
         self.me1_le_player_classes
     }
 
+    pub fn get_me1_le_talents(self) -> Option<Rc<Me1LeTalentDb>> {
+        if self.me1_le_talents.is_none() {
+            self.load_database(Type::Me1LeTalents);
+        }
+        self.me1_le_talents
+    }
+
     pub fn get_me1_plot(self) -> Option<Rc<Me1PlotDb>> {
         if self.me1_plot.is_none() {
             self.load_database(Type::Me1Plot);
@@ -75,6 +133,13 @@ impl Databases {
         self.me1_raw_plot
     }
 
+    pub fn get_me1_plot_presets(self) -> Option<Rc<PlotPresetDb>> {
+        if self.me1_plot_presets.is_none() {
+            self.load_database(Type::Me1PlotPresets);
+        }
+        self.me1_plot_presets
+    }
+
     pub fn get_me1_item_db(self) -> Option<Rc<Me1ItemDb>> {
         if self.me1_item_db.is_none() {
             self.load_database(Type::Me1Items);
@@ -82,6 +147,13 @@ impl Databases {
         self.me1_item_db
     }
 
+    pub fn get_me1_player_classes(self) -> Option<Rc<Me1PlayerClassDb>> {
+        if self.me1_player_classes.is_none() {
+            self.load_database(Type::Me1PlayerClasses);
+        }
+        self.me1_player_classes
+    }
+
     pub fn get_me2_plot(self) -> Option<Rc<Me2PlotDb>> {
         if self.me2_plot.is_none() {
             self.load_database(Type::Me2Plot);
@@ -96,6 +168,20 @@ impl Databases {
         self.me2_raw_plot
     }
 
+    pub fn get_me2_plot_presets(self) -> Option<Rc<PlotPresetDb>> {
+        if self.me2_plot_presets.is_none() {
+            self.load_database(Type::Me2PlotPresets);
+        }
+        self.me2_plot_presets
+    }
+
+    pub fn get_me2_plot_rules(self) -> Option<Rc<PlotRuleDb>> {
+        if self.me2_plot_rules.is_none() {
+            self.load_database(Type::Me2PlotRules);
+        }
+        self.me2_plot_rules
+    }
+
     pub fn get_me3_plot(self) -> Option<Rc<Me3PlotDb>> {
         if self.me3_plot.is_none() {
             self.load_database(Type::Me3Plot);
@@ -110,6 +196,38 @@ impl Databases {
         self.me3_raw_plot
     }
 
+    pub fn get_me3_plot_presets(self) -> Option<Rc<PlotPresetDb>> {
+        if self.me3_plot_presets.is_none() {
+            self.load_database(Type::Me3PlotPresets);
+        }
+        self.me3_plot_presets
+    }
+
+    pub fn get_me3_plot_rules(self) -> Option<Rc<PlotRuleDb>> {
+        if self.me3_plot_rules.is_none() {
+            self.load_database(Type::Me3PlotRules);
+        }
+        self.me3_plot_rules
+    }
+
+    /// The TLK file picked for the game of the active save, if any
+    pub fn get_tlk(&self) -> Option<Rc<TlkFile>> {
+        self.tlk.clone()
+    }
+
+    /// User labels merged over the raw plot db, only available once the raw plot db is loaded
+    pub fn get_user_plot_labels(&self, theme: Theme) -> Option<Rc<RawPlotDb>> {
+        match theme {
+            Theme::MassEffect1 => self.me1_user_plot_labels.clone(),
+            Theme::MassEffect2 => self.me2_user_plot_labels.clone(),
+            Theme::MassEffect3 => self.me3_user_plot_labels.clone(),
+        }
+    }
+
+    pub fn user_plot_labels_action(&self, theme: Theme, action: UserPlotLabelsAction) {
+        self.user_plot_labels_callback.emit((theme, action));
+    }
+
     fn load_database(&self, db_type: Type) {
         self.load_callback.emit(db_type);
     }
@@ -117,25 +235,59 @@ impl Databases {
 
 impl PartialEq for Databases {
     fn eq(&self, other: &Self) -> bool {
+        fn ptr_eq<T>(this: &Option<Rc<T>>, other: &Option<Rc<T>>) -> bool {
+            match (this, other) {
+                (Some(this), Some(other)) => Rc::ptr_eq(this, other),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+
         let Databases {
             me1_le_player_classes,
+            me1_le_talents,
             me1_plot,
             me1_raw_plot,
+            me1_user_plot_labels,
+            me1_plot_presets,
             me1_item_db,
+            me1_player_classes,
             me2_plot,
             me2_raw_plot,
+            me2_user_plot_labels,
+            me2_plot_presets,
+            me2_plot_rules,
             me3_plot,
             me3_raw_plot,
+            me3_user_plot_labels,
+            me3_plot_presets,
+            me3_plot_rules,
+            tlk,
             load_callback: _,
+            user_plot_labels_callback: _,
         } = self;
+        // Most databases are loaded once and never change, only their presence matters
         me1_le_player_classes.is_some() == other.me1_le_player_classes.is_some()
+            && me1_le_talents.is_some() == other.me1_le_talents.is_some()
             && me1_plot.is_some() == other.me1_plot.is_some()
-            && me1_raw_plot.is_some() == other.me1_raw_plot.is_some()
+            && me1_plot_presets.is_some() == other.me1_plot_presets.is_some()
             && me1_item_db.is_some() == other.me1_item_db.is_some()
+            && me1_player_classes.is_some() == other.me1_player_classes.is_some()
             && me2_plot.is_some() == other.me2_plot.is_some()
-            && me2_raw_plot.is_some() == other.me2_raw_plot.is_some()
+            && me2_plot_presets.is_some() == other.me2_plot_presets.is_some()
+            && me2_plot_rules.is_some() == other.me2_plot_rules.is_some()
             && me3_plot.is_some() == other.me3_plot.is_some()
-            && me3_raw_plot.is_some() == other.me3_raw_plot.is_some()
+            && me3_plot_presets.is_some() == other.me3_plot_presets.is_some()
+            && me3_plot_rules.is_some() == other.me3_plot_rules.is_some()
+            // The raw plot dbs and the user labels are replaced when a label is edited,
+            // and the TLK when another game is opened
+            && ptr_eq(me1_raw_plot, &other.me1_raw_plot)
+            && ptr_eq(me1_user_plot_labels, &other.me1_user_plot_labels)
+            && ptr_eq(me2_raw_plot, &other.me2_raw_plot)
+            && ptr_eq(me2_user_plot_labels, &other.me2_user_plot_labels)
+            && ptr_eq(me3_raw_plot, &other.me3_raw_plot)
+            && ptr_eq(me3_user_plot_labels, &other.me3_user_plot_labels)
+            && ptr_eq(tlk, &other.tlk)
     }
 }
 
@@ -146,7 +298,14 @@ pub struct Props {
 }
 
 pub struct DatabaseProvider {
+    _save_handle: ContextHandle<SaveHandler>,
     dbs: Databases,
+    // The raw plot dbs without the user labels, to merge them again when the labels change
+    me1_raw_plot_base: Option<RawPlotDb>,
+    me2_raw_plot_base: Option<RawPlotDb>,
+    me3_raw_plot_base: Option<RawPlotDb>,
+    // The game and the TLK file the loaded TLK comes from
+    tlk_source: Option<(&'static str, PathBuf)>,
 }
 
 impl Component for DatabaseProvider {
@@ -155,8 +314,25 @@ impl Component for DatabaseProvider {
 
     fn create(ctx: &Context<Self>) -> Self {
         let load_callback = ctx.link().callback(Msg::LoadDatabase);
-        let dbs = Databases { load_callback, ..Default::default() };
-        Self { dbs }
+        let user_plot_labels_callback =
+            ctx.link().callback(|(theme, action)| Msg::UserPlotLabels(theme, action));
+        let dbs = Databases { load_callback, user_plot_labels_callback, ..Default::default() };
+
+        let (save_handler, _save_handle) = ctx
+            .link()
+            .context::<SaveHandler>(ctx.link().callback(Msg::SaveHandler))
+            .expect("no save handler provider");
+
+        let mut this = Self {
+            _save_handle,
+            dbs,
+            me1_raw_plot_base: None,
+            me2_raw_plot_base: None,
+            me3_raw_plot_base: None,
+            tlk_source: None,
+        };
+        this.update_tlk(ctx, &save_handler);
+        this
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -169,38 +345,65 @@ impl Component for DatabaseProvider {
                             Ok(Database::Me1LePlayerClasses(db))
                         })
                     }
+                    Type::Me1LeTalents => {
+                        Self::load_db(ctx, "databases/me1_le_talent_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me1LeTalents(db))
+                        })
+                    }
                     Type::Me1Plot => Self::load_db(ctx, "databases/me1_plot_db.ron", |response| {
                         let db = ron::from_str(&response)?;
                         Ok(Database::Me1Plot(db))
                     }),
-                    Type::Me1RawPlot => {
-                        Self::load_db(ctx, "databases/me1_raw_plot_db.ron", |response| {
+                    Type::Me1RawPlot => Self::load_raw_plot_db(ctx, Theme::MassEffect1),
+                    Type::Me1PlotPresets => {
+                        Self::load_db(ctx, "databases/me1_plot_preset_db.ron", |response| {
                             let db = ron::from_str(&response)?;
-                            Ok(Database::Me1RawPlot(db))
+                            Ok(Database::Me1PlotPresets(db))
                         })
                     }
                     Type::Me1Items => Self::load_db(ctx, "databases/me1_item_db.ron", |response| {
                         let db = ron::from_str(&response)?;
                         Ok(Database::Me1Items(db))
                     }),
+                    Type::Me1PlayerClasses => {
+                        Self::load_db(ctx, "databases/me1_player_class_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me1PlayerClasses(db))
+                        })
+                    }
                     Type::Me2Plot => Self::load_db(ctx, "databases/me2_plot_db.ron", |response| {
                         let db = ron::from_str(&response)?;
                         Ok(Database::Me2Plot(db))
                     }),
-                    Type::Me2RawPlot => {
-                        Self::load_db(ctx, "databases/me2_raw_plot_db.ron", |response| {
+                    Type::Me2RawPlot => Self::load_raw_plot_db(ctx, Theme::MassEffect2),
+                    Type::Me2PlotPresets => {
+                        Self::load_db(ctx, "databases/me2_plot_preset_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me2PlotPresets(db))
+                        })
+                    }
+                    Type::Me2PlotRules => {
+                        Self::load_db(ctx, "databases/me2_plot_rule_db.ron", |response| {
                             let db = ron::from_str(&response)?;
-                            Ok(Database::Me2RawPlot(db))
+                            Ok(Database::Me2PlotRules(db))
                         })
                     }
                     Type::Me3Plot => Self::load_db(ctx, "databases/me3_plot_db.ron", |response| {
                         let db = ron::from_str(&response)?;
                         Ok(Database::Me3Plot(db))
                     }),
-                    Type::Me3RawPlot => {
-                        Self::load_db(ctx, "databases/me3_raw_plot_db.ron", |response| {
+                    Type::Me3RawPlot => Self::load_raw_plot_db(ctx, Theme::MassEffect3),
+                    Type::Me3PlotPresets => {
+                        Self::load_db(ctx, "databases/me3_plot_preset_db.ron", |response| {
+                            let db = ron::from_str(&response)?;
+                            Ok(Database::Me3PlotPresets(db))
+                        })
+                    }
+                    Type::Me3PlotRules => {
+                        Self::load_db(ctx, "databases/me3_plot_rule_db.ron", |response| {
                             let db = ron::from_str(&response)?;
-                            Ok(Database::Me3RawPlot(db))
+                            Ok(Database::Me3PlotRules(db))
                         })
                     }
                 }
@@ -211,34 +414,106 @@ impl Component for DatabaseProvider {
                     Database::Me1LePlayerClasses(db) => {
                         self.dbs.me1_le_player_classes = Some(db.into());
                     }
+                    Database::Me1LeTalents(db) => {
+                        self.dbs.me1_le_talents = Some(db.into());
+                    }
                     Database::Me1Plot(db) => {
                         self.dbs.me1_plot = Some(db.into());
                     }
-                    Database::Me1RawPlot(db) => {
-                        self.dbs.me1_raw_plot = Some(db.into());
+                    Database::Me1RawPlot(db, user_labels) => {
+                        self.me1_raw_plot_base = Some(db);
+                        self.set_user_plot_labels(Theme::MassEffect1, user_labels);
+                    }
+                    Database::Me1PlotPresets(db) => {
+                        self.dbs.me1_plot_presets = Some(db.into());
                     }
                     Database::Me1Items(db) => {
                         self.dbs.me1_item_db = Some(db.into());
                     }
+                    Database::Me1PlayerClasses(db) => {
+                        self.dbs.me1_player_classes = Some(db.into());
+                    }
                     Database::Me2Plot(db) => {
                         self.dbs.me2_plot = Some(db.into());
                     }
-                    Database::Me2RawPlot(db) => {
-                        self.dbs.me2_raw_plot = Some(db.into());
+                    Database::Me2RawPlot(db, user_labels) => {
+                        self.me2_raw_plot_base = Some(db);
+                        self.set_user_plot_labels(Theme::MassEffect2, user_labels);
+                    }
+                    Database::Me2PlotPresets(db) => {
+                        self.dbs.me2_plot_presets = Some(db.into());
+                    }
+                    Database::Me2PlotRules(db) => {
+                        self.dbs.me2_plot_rules = Some(db.into());
                     }
                     Database::Me3Plot(db) => {
                         self.dbs.me3_plot = Some(db.into());
                     }
-                    Database::Me3RawPlot(db) => {
-                        self.dbs.me3_raw_plot = Some(db.into());
+                    Database::Me3RawPlot(db, user_labels) => {
+                        self.me3_raw_plot_base = Some(db);
+                        self.set_user_plot_labels(Theme::MassEffect3, user_labels);
+                    }
+                    Database::Me3PlotPresets(db) => {
+                        self.dbs.me3_plot_presets = Some(db.into());
+                    }
+                    Database::Me3PlotRules(db) => {
+                        self.dbs.me3_plot_rules = Some(db.into());
+                    }
+                }
+                true
+            }
+            Msg::UserPlotLabels(theme, action) => {
+                let mut user_labels = self
+                    .dbs
+                    .get_user_plot_labels(theme)
+                    .map(|user_labels| RawPlotDb::clone(&user_labels))
+                    .unwrap_or_default();
+
+                // The labels are updated before being saved, so the next edit starts from them
+                match action {
+                    UserPlotLabelsAction::Set(kind, id, label) => {
+                        user_labels.labels_mut(kind).insert(id, label);
+                        self.set_user_plot_labels(theme, user_labels.clone());
+                        Self::save_user_plot_labels(ctx, theme, user_labels);
+                        true
+                    }
+                    UserPlotLabelsAction::Remove(kind, id) => {
+                        user_labels.labels_mut(kind).shift_remove(&id);
+                        self.set_user_plot_labels(theme, user_labels.clone());
+                        Self::save_user_plot_labels(ctx, theme, user_labels);
+                        true
+                    }
+                    UserPlotLabelsAction::Import => {
+                        Self::import_user_plot_labels(ctx, theme);
+                        false
+                    }
+                    UserPlotLabelsAction::Export => {
+                        Self::export_user_plot_labels(ctx, user_labels);
+                        false
                     }
                 }
+            }
+            Msg::UserPlotLabelsImported(theme, imported) => {
+                let mut user_labels = self
+                    .dbs
+                    .get_user_plot_labels(theme)
+                    .map(|user_labels| RawPlotDb::clone(&user_labels))
+                    .unwrap_or_default();
+                user_labels.merge(&imported);
+                self.set_user_plot_labels(theme, user_labels.clone());
+                Self::save_user_plot_labels(ctx, theme, user_labels);
+                true
+            }
+            Msg::SaveHandler(save_handler) => self.update_tlk(ctx, &save_handler),
+            Msg::TlkLoaded(tlk) => {
+                self.dbs.tlk = tlk;
                 true
             }
             Msg::Error(err) => {
                 ctx.props().onerror.emit(err);
                 false
             }
+            Msg::Noop => false,
         }
     }
 
@@ -252,14 +527,41 @@ impl Component for DatabaseProvider {
 }
 
 impl DatabaseProvider {
+    /// Replaces the user labels of `theme` and merges them over its raw plot db
+    fn set_user_plot_labels(&mut self, theme: Theme, user_labels: RawPlotDb) {
+        let (base, raw_plot, user_plot_labels) = match theme {
+            Theme::MassEffect1 => (
+                &self.me1_raw_plot_base,
+                &mut self.dbs.me1_raw_plot,
+                &mut self.dbs.me1_user_plot_labels,
+            ),
+            Theme::MassEffect2 => (
+                &self.me2_raw_plot_base,
+                &mut self.dbs.me2_raw_plot,
+                &mut self.dbs.me2_user_plot_labels,
+            ),
+            Theme::MassEffect3 => (
+                &self.me3_raw_plot_base,
+                &mut self.dbs.me3_raw_plot,
+                &mut self.dbs.me3_user_plot_labels,
+            ),
+        };
+
+        if let Some(base) = base {
+            let mut db = base.clone();
+            db.merge(&user_labels);
+            *raw_plot = Some(db.into());
+        }
+        *user_plot_labels = Some(user_labels.into());
+    }
+
     fn load_db<F>(ctx: &Context<Self>, path: &'static str, deserialize: F)
     where
         F: Fn(String) -> Result<Database> + 'static,
     {
         ctx.link().send_future(async move {
             let handle_db = async {
-                let rpc_file = rpc::load_database(path).await?;
-                let file = String::from_utf8(rpc_file.file.decode()?)?;
+                let file = Self::read_db(path).await?;
                 deserialize(file)
             };
             match handle_db.await.context(format!("Failed to parse `/{}`", path)) {
@@ -268,4 +570,168 @@ impl DatabaseProvider {
             }
         });
     }
+
+    /// Reads the variant of the current language if there is one, like `me2_plot_db.fr.ron`
+    async fn read_db(path: &str) -> Result<String> {
+        let localized = match i18n::language().localized_path(path) {
+            Some(localized) => rpc::load_database(&localized).await.ok(),
+            None => None,
+        };
+        let rpc_file = match localized {
+            Some(rpc_file) => rpc_file,
+            None => rpc::load_database(path).await?,
+        };
+        Ok(String::from_utf8(rpc_file.file.decode()?)?)
+    }
+
+    // Loads the TLK file of the game of the active save when it or its path change
+    fn update_tlk(&mut self, ctx: &Context<Self>, save_handler: &SaveHandler) -> bool {
+        let tlk_source = save_handler.save_game.as_ref().and_then(|save_game| {
+            // The ME1 fields have no localized ids to resolve
+            if matches!(**save_game, SaveGame::MassEffect1 { .. }) {
+                return None;
+            }
+            let game = save_game.game_name();
+            save_handler.settings.tlk_files.get(game).map(|path| (game, path.clone()))
+        });
+        if tlk_source == self.tlk_source {
+            return false;
+        }
+        self.tlk_source = tlk_source;
+
+        match self.tlk_source {
+            Some((game, ref path)) => {
+                let path = path.clone();
+                ctx.link().send_future(async move {
+                    let handle_tlk = async {
+                        let tlk = match rpc::load_tlk(game).await? {
+                            Some(rpc_file) => {
+                                Some(Rc::new(TlkFile::parse(&rpc_file.file.decode()?)?))
+                            }
+                            None => None,
+                        };
+                        Ok::<_, Error>(tlk)
+                    };
+                    match handle_tlk.await.context(format!("Failed to load `{}`", path.display())) {
+                        Ok(tlk) => Msg::TlkLoaded(tlk),
+                        Err(err) => Msg::Error(err),
+                    }
+                });
+                false
+            }
+            None => {
+                let changed = self.dbs.tlk.is_some();
+                self.dbs.tlk = None;
+                changed
+            }
+        }
+    }
+
+    fn raw_plot_paths(theme: Theme) -> (&'static str, &'static str) {
+        match theme {
+            Theme::MassEffect1 => ("databases/me1_raw_plot_db.ron", "me1_plot_labels.ron"),
+            Theme::MassEffect2 => ("databases/me2_raw_plot_db.ron", "me2_plot_labels.ron"),
+            Theme::MassEffect3 => ("databases/me3_raw_plot_db.ron", "me3_plot_labels.ron"),
+        }
+    }
+
+    fn load_raw_plot_db(ctx: &Context<Self>, theme: Theme) {
+        let (path, user_path) = Self::raw_plot_paths(theme);
+        ctx.link().send_future(async move {
+            let handle_db = async {
+                let file = Self::read_db(path).await?;
+                let db: RawPlotDb =
+                    ron::from_str(&file).context(format!("Failed to parse `/{}`", path))?;
+
+                let user_labels: RawPlotDb = match rpc::load_user_plot_labels(user_path).await? {
+                    Some(rpc_file) => {
+                        let file = String::from_utf8(rpc_file.file.decode()?)?;
+                        ron::from_str(&file)
+                            .context(format!("Failed to parse the user labels `{}`", user_path))?
+                    }
+                    None => RawPlotDb::default(),
+                };
+                Ok::<_, Error>((db, user_labels))
+            };
+
+            match handle_db.await {
+                Ok((db, user_labels)) => {
+                    let db = match theme {
+                        Theme::MassEffect1 => Database::Me1RawPlot(db, user_labels),
+                        Theme::MassEffect2 => Database::Me2RawPlot(db, user_labels),
+                        Theme::MassEffect3 => Database::Me3RawPlot(db, user_labels),
+                    };
+                    Msg::DatabaseLoaded(Box::new(db))
+                }
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn serialize_user_plot_labels(path: PathBuf, mut user_labels: RawPlotDb) -> Result<RpcFile> {
+        for kind in [PlotKind::Boolean, PlotKind::Integer, PlotKind::Float] {
+            user_labels.labels_mut(kind).sort_keys();
+        }
+
+        let pretty_config = PrettyConfig::new().new_line(String::from('\n'));
+        let output = ron::ser::to_string_pretty(&user_labels, pretty_config)?;
+        Ok(RpcFile {
+            path,
+            file: Base64File { unencoded_size: output.len(), base64: base64::encode(output) },
+        })
+    }
+
+    fn save_user_plot_labels(ctx: &Context<Self>, theme: Theme, user_labels: RawPlotDb) {
+        let (_, user_path) = Self::raw_plot_paths(theme);
+        ctx.link().send_future(async move {
+            let handle_labels = async {
+                let rpc_file = Self::serialize_user_plot_labels(user_path.into(), user_labels)?;
+                rpc::save_user_plot_labels(rpc_file).await
+            };
+
+            match handle_labels.await.context("Failed to save the user plot labels") {
+                Ok(()) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn import_user_plot_labels(ctx: &Context<Self>, theme: Theme) {
+        ctx.link().send_future(async move {
+            let handle_labels = async {
+                let has_rpc_file = rpc::import_plot_labels().await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => {
+                        let ron = String::from_utf8(rpc_file.file.decode()?)?;
+                        ron::from_str(&ron).map(Some)?
+                    }
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_labels.await.context("Failed to import the plot labels") {
+                Ok(Some(labels)) => Msg::UserPlotLabelsImported(theme, labels),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn export_user_plot_labels(ctx: &Context<Self>, user_labels: RawPlotDb) {
+        ctx.link().send_future(async move {
+            let handle_labels = async {
+                if let Some(path) = rpc::export_plot_labels_dialog().await? {
+                    let rpc_file = Self::serialize_user_plot_labels(path, user_labels)?;
+                    rpc::save_file(rpc_file).await?;
+                }
+                Ok::<_, Error>(())
+            };
+
+            match handle_labels.await.context("Failed to export the plot labels") {
+                Ok(()) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
 }
@@ -1,4 +1,10 @@
+pub mod batch;
+pub mod career;
+pub mod clipboard;
 pub mod database;
 pub mod drop_handler;
 pub mod rpc;
 pub mod save_handler;
+pub mod save_library;
+pub mod script;
+pub mod search;
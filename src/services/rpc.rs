@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use anyhow::{anyhow, Result};
 use serde::{de, Deserialize, Serialize};
@@ -62,8 +62,9 @@ pub async fn save_file(rpc_file: RpcFile) -> Result<()> {
     call_with_params("save_file", rpc_file).await
 }
 
-pub async fn open_save(last_dir: bool) -> Result<Option<RpcFile>> {
-    call_with_params("open_save", last_dir).await
+/// Opens the dialog in the last directory of `game`
+pub async fn open_save(game: Option<&str>) -> Result<Option<RpcFile>> {
+    call_with_params("open_save", game).await
 }
 
 pub async fn open_command_line_save() -> Result<Option<RpcFile>> {
@@ -78,6 +79,10 @@ pub async fn reload_save(path: PathBuf) -> Result<RpcFile> {
     call_with_params("reload_save", path).await
 }
 
+pub async fn watch_file(path: PathBuf) -> Result<()> {
+    call_with_params("watch_file", path).await
+}
+
 pub async fn import_head_morph() -> Result<Option<RpcFile>> {
     call("import_head_morph").await
 }
@@ -86,6 +91,106 @@ pub async fn export_head_morph_dialog() -> Result<Option<PathBuf>> {
     call("export_head_morph_dialog").await
 }
 
+pub async fn import_plot_preset() -> Result<Option<RpcFile>> {
+    call("import_plot_preset").await
+}
+
+pub async fn export_plot_preset_dialog() -> Result<Option<PathBuf>> {
+    call("export_plot_preset_dialog").await
+}
+
+pub async fn import_plot_labels() -> Result<Option<RpcFile>> {
+    call("import_plot_labels").await
+}
+
+pub async fn export_plot_labels_dialog() -> Result<Option<PathBuf>> {
+    call("export_plot_labels_dialog").await
+}
+
+pub async fn load_user_plot_labels(file_name: &str) -> Result<Option<RpcFile>> {
+    call_with_params("load_user_plot_labels", file_name).await
+}
+
+pub async fn save_user_plot_labels(rpc_file: RpcFile) -> Result<()> {
+    call_with_params("save_user_plot_labels", rpc_file).await
+}
+
+pub async fn open_script() -> Result<Option<RpcFile>> {
+    call("open_script").await
+}
+
+pub async fn save_script_dialog() -> Result<Option<PathBuf>> {
+    call("save_script_dialog").await
+}
+
+pub async fn open_command_line_script() -> Result<Option<RpcFile>> {
+    call("open_command_line_script").await
+}
+
+pub async fn print_script_output(lines: Vec<String>) -> Result<()> {
+    call_with_params("print_script_output", lines).await
+}
+
+pub async fn list_saves() -> Result<Vec<LibrarySave>> {
+    call("list_saves").await
+}
+
+pub async fn create_career(career: CareerFiles) -> Result<()> {
+    call_with_params("create_career", career).await
+}
+
+pub async fn rename_career(params: RenameCareerParams) -> Result<PathBuf> {
+    call_with_params("rename_career", params).await
+}
+
+pub async fn delete_career(path: PathBuf) -> Result<PathBuf> {
+    call_with_params("delete_career", path).await
+}
+
+pub async fn list_backups(path: PathBuf) -> Result<Vec<Backup>> {
+    call_with_params("list_backups", path).await
+}
+
+pub async fn restore_backup(params: RestoreBackupParams) -> Result<()> {
+    call_with_params("restore_backup", params).await
+}
+
+pub async fn get_settings() -> Result<Settings> {
+    call("get_settings").await
+}
+
+pub async fn set_settings(settings: Settings) -> Result<()> {
+    call_with_params("set_settings", settings).await
+}
+
+pub async fn add_recent_file(recent_file: RecentFile) -> Result<Settings> {
+    call_with_params("add_recent_file", recent_file).await
+}
+
+pub async fn open_tlk_dialog() -> Result<Option<PathBuf>> {
+    call("open_tlk_dialog").await
+}
+
+pub async fn load_tlk(game: &str) -> Result<Option<RpcFile>> {
+    call_with_params("load_tlk", game).await
+}
+
+pub async fn open_saves_dialog() -> Result<Vec<PathBuf>> {
+    call("open_saves_dialog").await
+}
+
+pub async fn expand_batch_paths(patterns: Vec<String>) -> Result<Vec<PathBuf>> {
+    call_with_params("expand_batch_paths", patterns).await
+}
+
+pub async fn open_command_line_batch() -> Result<Option<CommandLineBatch>> {
+    call("open_command_line_batch").await
+}
+
+pub async fn finish_batch(report: BatchReport) -> Result<()> {
+    call_with_params("finish_batch", report).await
+}
+
 pub async fn load_database(path: &str) -> Result<RpcFile> {
     call_with_params("load_database", path).await
 }
@@ -97,6 +202,114 @@ pub struct DialogParams {
     pub filters: Vec<(&'static str, Vec<&'static str>)>,
 }
 
+#[derive(Serialize)]
+pub struct CareerFiles {
+    pub path: PathBuf,
+    pub saves: Vec<RpcFile>,
+}
+
+#[derive(Serialize)]
+pub struct RenameCareerParams {
+    pub from: PathBuf,
+    pub to: CareerFiles,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct RestoreBackupParams {
+    pub backup: PathBuf,
+    pub path: PathBuf,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct BackupSettings {
+    pub retention: usize,
+    pub max_size_mb: u64,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        BackupSettings { retention: 10, max_size_mb: 100 }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct Settings {
+    /// Newest first
+    pub recent_files: Vec<RecentFile>,
+    pub last_dirs: BTreeMap<String, PathBuf>,
+    pub backup: BackupSettings,
+    pub language: Language,
+    pub default_platform: SavePlatform,
+    /// The TLK file picked for each game, to resolve the localized ids
+    pub tlk_files: BTreeMap<String, PathBuf>,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
+pub struct RecentFile {
+    pub path: PathBuf,
+    pub game: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Language {
+    English,
+    French,
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::English
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum SavePlatform {
+    Pc,
+    Xbox360,
+}
+
+impl Default for SavePlatform {
+    fn default() -> Self {
+        SavePlatform::Pc
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CommandLineBatch {
+    pub paths: Vec<PathBuf>,
+    pub operation: CommandLineOperation,
+}
+
+#[derive(Deserialize)]
+pub enum CommandLineOperation {
+    Preset(RpcFile),
+    Script(RpcFile),
+    Assignments(Vec<String>),
+}
+
+#[derive(Serialize)]
+pub struct BatchReport {
+    pub text: String,
+    pub failed: usize,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct LibrarySave {
+    pub path: PathBuf,
+    pub game: String,
+    pub career: String,
+    /// Seconds since the Unix epoch
+    pub modified: u64,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct RpcFile {
     pub path: PathBuf,
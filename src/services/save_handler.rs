@@ -1,24 +1,36 @@
-use std::{path::PathBuf, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 use anyhow::{bail, Context as ErrorContext, Error, Result};
 use crc::{Crc, CRC_32_BZIP2};
-use gloo::utils;
+use gloo::{events::EventListener, timers::callback::Timeout, utils};
 use ron::ser::PrettyConfig;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use yew::{prelude::*, ContextProvider};
 
 use crate::{
-    gui::Theme,
+    gui::{i18n, Theme},
     save_data::mass_effect_1_le::Me1LeMagicNumber,
     save_data::{
         mass_effect_1::{Me1MagicNumber, Me1SaveGame},
         mass_effect_1_le::{Me1LeSaveData, Me1LeSaveGame, Me1LeVersion},
         mass_effect_2::{Me2LeSaveGame, Me2LeVersion, Me2SaveGame, Me2Version},
         mass_effect_3::{Me3SaveGame, Me3Version},
-        shared::appearance::HeadMorph,
+        shared::{appearance::HeadMorph, plot::PlotPreset},
         RcRef,
     },
-    services::rpc::{self, Base64File, DialogParams, RpcFile},
+    services::{
+        batch,
+        clipboard::Clipboard,
+        rpc::{
+            self, Base64File, DialogParams, Language, RecentFile, RpcFile, SavePlatform, Settings,
+        },
+        script,
+    },
     unreal,
 };
 
@@ -34,21 +46,107 @@ pub enum SaveGame {
     MassEffect3 { file_path: PathBuf, save_game: RcRef<Me3SaveGame> },
 }
 
+impl SaveGame {
+    pub fn file_path(&self) -> &Path {
+        match self {
+            SaveGame::MassEffect1 { file_path, .. }
+            | SaveGame::MassEffect1Le { file_path, .. }
+            | SaveGame::MassEffect1LePs4 { file_path, .. }
+            | SaveGame::MassEffect2 { file_path, .. }
+            | SaveGame::MassEffect2Le { file_path, .. }
+            | SaveGame::MassEffect3 { file_path, .. } => file_path,
+        }
+    }
+
+    /// As named in the save library
+    pub fn game_name(&self) -> &'static str {
+        match self {
+            SaveGame::MassEffect1 { .. } => "Mass Effect",
+            SaveGame::MassEffect1Le { .. } | SaveGame::MassEffect1LePs4 { .. } => {
+                "Mass Effect Legendary"
+            }
+            SaveGame::MassEffect2 { .. } => "Mass Effect 2",
+            SaveGame::MassEffect2Le { .. } => "Mass Effect 2 Legendary",
+            SaveGame::MassEffect3 { .. } => "Mass Effect 3",
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self {
+            SaveGame::MassEffect1 { .. }
+            | SaveGame::MassEffect1Le { .. }
+            | SaveGame::MassEffect1LePs4 { .. } => Theme::MassEffect1,
+            SaveGame::MassEffect2 { .. } | SaveGame::MassEffect2Le { .. } => Theme::MassEffect2,
+            SaveGame::MassEffect3 { .. } => Theme::MassEffect3,
+        }
+    }
+
+    // Compared to know if the save has been edited
+    fn checksum(&self) -> Option<u32> {
+        let output = SaveHandlerProvider::to_bytes(self.file_path(), self).ok()?;
+        Some(Crc::<u32>::new(&CRC_32_BZIP2).checksum(&output))
+    }
+}
+
+/// A save open in a tab
+#[derive(Clone)]
+pub struct Document {
+    pub save_game: Rc<SaveGame>,
+    pub is_dirty: bool,
+    // As opened or last saved
+    checksum: Option<u32>,
+}
+
+impl Document {
+    fn new(save_game: Rc<SaveGame>) -> Self {
+        let checksum = save_game.checksum();
+        Document { save_game, is_dirty: false, checksum }
+    }
+}
+
+impl PartialEq for Document {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.save_game, &other.save_game) && self.is_dirty == other.is_dirty
+    }
+}
+
 pub enum Action {
     OpenSave,
+    OpenSaveAt(PathBuf),
+    LoadSaveGame(SaveGame),
     SaveSave,
     ReloadSave,
     ImportHeadMorph(Callback<HeadMorph>),
     ExportHeadMorph(RcRef<HeadMorph>),
+    ImportPlotPreset(Callback<PlotPreset>),
+    ExportPlotPreset(Rc<PlotPreset>),
+    OpenSaveToCompare(Callback<SaveGame>),
+    OpenScript(Callback<String>),
+    SaveScript(String),
+    SelectDocument(usize),
+    CloseDocument(usize),
+    Copy(Clipboard),
+    SetSettings(Settings),
+    Error(Error),
 }
 
 pub enum Msg {
     Action(Action),
     SaveOpened(SaveGame),
     SaveDropped(Result<(String, Vec<u8>)>),
-    SaveSaved,
+    SaveSaved(Rc<SaveGame>),
     HeadMorphImported(HeadMorph, Callback<HeadMorph>),
     HeadMorphExported,
+    PlotPresetImported(PlotPreset, Callback<PlotPreset>),
+    PlotPresetExported,
+    SaveToCompareOpened(SaveGame, Callback<SaveGame>),
+    ScriptOpened(String, Callback<String>),
+    ScriptSaved,
+    SettingsLoaded(Settings),
+    SettingsSaved(Settings),
+    LanguageLoaded(Language, HashMap<String, String>),
+    UserInput,
+    CheckDirty,
     Error(Error),
     Noop,
 }
@@ -62,7 +160,11 @@ pub struct Props {
 
 #[derive(Clone)]
 pub struct SaveHandler {
+    /// The save of the active tab
     pub save_game: Option<Rc<SaveGame>>,
+    pub documents: Vec<Document>,
+    pub clipboard: Option<Rc<Clipboard>>,
+    pub settings: Rc<Settings>,
     callback: Callback<Action>,
 }
 
@@ -70,20 +172,53 @@ impl SaveHandler {
     pub fn action(&self, action: Action) {
         self.callback.emit(action);
     }
-}
 
-impl PartialEq for SaveHandler {
-    fn eq(&self, other: &Self) -> bool {
+    /// Whether both have the same active save, the tabs and the clipboard aside
+    pub fn is_same_save(&self, other: &SaveHandler) -> bool {
         match (&self.save_game, &other.save_game) {
             (Some(this), Some(other)) => Rc::ptr_eq(this, other),
             (None, None) => true,
             _ => false,
         }
     }
+
+    /// Copies `value` to the clipboard shared by the open saves
+    pub fn copy<T: Serialize>(&self, label: impl Into<String>, value: &T) {
+        match Clipboard::copy(label, value).context("Failed to copy") {
+            Ok(clipboard) => self.action(Action::Copy(clipboard)),
+            Err(err) => self.action(Action::Error(err)),
+        }
+    }
+
+    pub fn can_paste<T>(&self) -> bool {
+        self.clipboard.as_ref().map(|clipboard| clipboard.holds::<T>()).unwrap_or_default()
+    }
+
+    /// `None` if the clipboard is empty or holds another kind of data
+    pub fn paste<T: DeserializeOwned>(&self) -> Option<T> {
+        match self.clipboard.as_ref()?.paste()? {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.action(Action::Error(err.context("Failed to paste")));
+                None
+            }
+        }
+    }
+}
+
+impl PartialEq for SaveHandler {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_same_save(other)
+            && self.documents == other.documents
+            && self.clipboard == other.clipboard
+            && self.settings == other.settings
+    }
 }
 
 pub struct SaveHandlerProvider {
     _drop_handler: DropHandler,
+    _input_listeners: [EventListener; 2],
+    dirty_check: Option<Timeout>,
     save_handler: SaveHandler,
 }
 
@@ -93,11 +228,24 @@ impl Component for SaveHandlerProvider {
 
     fn create(ctx: &Context<Self>) -> Self {
         let _drop_handler = DropHandler::new(ctx.link().callback(Msg::SaveDropped));
-        let save_handler =
-            SaveHandler { save_game: None, callback: ctx.link().callback(Msg::Action) };
+        // Edits come from inputs and clicks, the active save is checked once they stop
+        let _input_listeners = ["input", "click"].map(|event_type| {
+            let link = ctx.link().clone();
+            EventListener::new(&utils::document(), event_type, move |_| {
+                link.send_message(Msg::UserInput)
+            })
+        });
+        let save_handler = SaveHandler {
+            save_game: None,
+            documents: Vec::new(),
+            clipboard: None,
+            settings: Default::default(),
+            callback: ctx.link().callback(Msg::Action),
+        };
+        Self::load_settings(ctx);
         Self::open_command_line_save(ctx);
 
-        SaveHandlerProvider { _drop_handler, save_handler }
+        SaveHandlerProvider { _drop_handler, _input_listeners, dirty_check: None, save_handler }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -105,37 +253,72 @@ impl Component for SaveHandlerProvider {
             // Actions
             Msg::Action(action) => {
                 match action {
-                    Action::OpenSave => {
-                        let last_dir = self.save_handler.save_game.is_some();
-                        Self::open_save(ctx, last_dir);
+                    Action::OpenSave => Self::open_save(ctx, self.game_name()),
+                    Action::OpenSaveAt(path) => Self::reload_save(ctx, path),
+                    Action::LoadSaveGame(save_game) => {
+                        ctx.link().send_message(Msg::SaveOpened(save_game))
                     }
                     Action::SaveSave => {
                         if let Some(ref save_game) = self.save_handler.save_game {
-                            Self::save_save(ctx, save_game);
+                            Self::save_save(
+                                ctx,
+                                save_game,
+                                self.save_handler.settings.default_platform,
+                            );
                         }
                     }
                     Action::ReloadSave => {
                         if let Some(ref save_game) = self.save_handler.save_game {
-                            match save_game.as_ref() {
-                                SaveGame::MassEffect1 { file_path, .. }
-                                | SaveGame::MassEffect1Le { file_path, .. }
-                                | SaveGame::MassEffect1LePs4 { file_path, .. }
-                                | SaveGame::MassEffect2 { file_path, .. }
-                                | SaveGame::MassEffect2Le { file_path, .. }
-                                | SaveGame::MassEffect3 { file_path, .. } => {
-                                    Self::reload_save(ctx, file_path.clone())
-                                }
-                            }
+                            Self::reload_save(ctx, save_game.file_path().to_owned())
                         }
                     }
                     Action::ImportHeadMorph(callback) => Self::import_head_morph(ctx, callback),
                     Action::ExportHeadMorph(head_morph) => Self::export_head_morph(ctx, head_morph),
+                    Action::ImportPlotPreset(callback) => Self::import_plot_preset(ctx, callback),
+                    Action::ExportPlotPreset(preset) => Self::export_plot_preset(ctx, preset),
+                    Action::OpenSaveToCompare(callback) => {
+                        Self::open_save_to_compare(ctx, self.game_name(), callback)
+                    }
+                    Action::OpenScript(callback) => Self::open_script(ctx, callback),
+                    Action::SaveScript(script) => Self::save_script(ctx, script),
+                    Action::SelectDocument(idx) => {
+                        self.check_dirty();
+                        if let Some(document) = self.save_handler.documents.get(idx) {
+                            self.save_handler.save_game = Some(Rc::clone(&document.save_game));
+                            self.change_theme();
+                        }
+                        return true;
+                    }
+                    Action::CloseDocument(idx) => {
+                        self.close_document(idx);
+                        return true;
+                    }
+                    Action::SetSettings(settings) => Self::save_settings(ctx, settings),
+                    Action::Error(err) => ctx.props().onerror.emit(err),
+                    Action::Copy(clipboard) => {
+                        self.save_handler.clipboard = Some(clipboard.into());
+                        ctx.props().onnotification.emit("Copied");
+                        return true;
+                    }
                 }
                 false
             }
             // Messages
             Msg::SaveOpened(save_game) => {
-                self.save_handler.save_game = Some(save_game.into());
+                let save_game = Rc::new(save_game);
+                let document = Document::new(Rc::clone(&save_game));
+
+                // A save opened again replaces its tab
+                let documents = &mut self.save_handler.documents;
+                match documents
+                    .iter()
+                    .position(|document| document.save_game.file_path() == save_game.file_path())
+                {
+                    Some(idx) => documents[idx] = document,
+                    None => documents.push(document),
+                }
+                Self::add_recent_file(ctx, &save_game);
+                self.save_handler.save_game = Some(save_game);
                 self.change_theme();
                 ctx.props().onnotification.emit("Opened");
                 true
@@ -147,9 +330,17 @@ impl Component for SaveHandlerProvider {
                 }
                 false
             }
-            Msg::SaveSaved => {
+            Msg::SaveSaved(save_game) => {
+                if let Some(document) = self
+                    .save_handler
+                    .documents
+                    .iter_mut()
+                    .find(|document| Rc::ptr_eq(&document.save_game, &save_game))
+                {
+                    *document = Document::new(save_game);
+                }
                 ctx.props().onnotification.emit("Saved");
-                false
+                true
             }
             Msg::HeadMorphImported(head_morph, callback) => {
                 callback.emit(head_morph);
@@ -160,6 +351,56 @@ impl Component for SaveHandlerProvider {
                 ctx.props().onnotification.emit("Exported");
                 false
             }
+            Msg::PlotPresetImported(preset, callback) => {
+                callback.emit(preset);
+                ctx.props().onnotification.emit("Imported");
+                false
+            }
+            Msg::PlotPresetExported => {
+                ctx.props().onnotification.emit("Exported");
+                false
+            }
+            Msg::SaveToCompareOpened(save_game, callback) => {
+                callback.emit(save_game);
+                false
+            }
+            Msg::ScriptOpened(script, callback) => {
+                callback.emit(script);
+                false
+            }
+            Msg::ScriptSaved => {
+                ctx.props().onnotification.emit("Saved");
+                false
+            }
+            Msg::SettingsLoaded(settings) => {
+                Self::load_language(ctx, settings.language);
+                self.save_handler.settings = settings.into();
+                true
+            }
+            Msg::SettingsSaved(settings) => {
+                Self::load_language(ctx, settings.language);
+                self.save_handler.settings = settings.into();
+                ctx.props().onnotification.emit("Settings saved");
+                true
+            }
+            Msg::LanguageLoaded(language, messages) => {
+                i18n::set_language(language, messages);
+                true
+            }
+            Msg::UserInput => {
+                // Once edited, the save stays dirty until it is saved, no need to check it again
+                if self.active_document().map(|document| !document.is_dirty).unwrap_or_default() {
+                    let link = ctx.link().clone();
+                    // Replacing the timeout cancels the previous one
+                    self.dirty_check =
+                        Some(Timeout::new(500, move || link.send_message(Msg::CheckDirty)));
+                }
+                false
+            }
+            Msg::CheckDirty => {
+                self.dirty_check = None;
+                self.check_dirty()
+            }
             Msg::Error(err) => {
                 ctx.props().onerror.emit(err);
                 false
@@ -175,17 +416,24 @@ impl Component for SaveHandlerProvider {
     fn view(&self, ctx: &Context<Self>) -> Html {
         html! {
             <ContextProvider<SaveHandler> context={self.save_handler.clone()}>
-                { ctx.props().children.clone() }
+                // Everything is recreated in the new language, the databases included
+                <div key={i18n::language().code()} class="contents">
+                    { ctx.props().children.clone() }
+                </div>
             </ContextProvider<SaveHandler>>
         }
     }
 }
 
 impl SaveHandlerProvider {
-    fn open_save(ctx: &Context<Self>, last_dir: bool) {
+    fn game_name(&self) -> Option<&'static str> {
+        self.save_handler.save_game.as_ref().map(|save_game| save_game.game_name())
+    }
+
+    fn open_save(ctx: &Context<Self>, game: Option<&'static str>) {
         ctx.link().send_future(async move {
             let handle_save = async {
-                let has_rpc_file = rpc::open_save(last_dir).await?;
+                let has_rpc_file = rpc::open_save(game).await?;
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
                         let RpcFile { path, file } = rpc_file;
@@ -204,14 +452,45 @@ impl SaveHandlerProvider {
         });
     }
 
+    fn open_save_to_compare(
+        ctx: &Context<Self>, game: Option<&'static str>, callback: Callback<SaveGame>,
+    ) {
+        ctx.link().send_future(async move {
+            let handle_save = async {
+                let has_rpc_file = rpc::open_save(game).await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => {
+                        let RpcFile { path, file } = rpc_file;
+                        Self::deserialize(path, file.decode()?).map(Some)?
+                    }
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_save.await.context("Failed to open the save to compare") {
+                Ok(Some(save_game)) => Msg::SaveToCompareOpened(save_game, callback),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
     fn open_command_line_save(ctx: &Context<Self>) {
         ctx.link().send_future(async move {
             let handle_save = async {
+                if let Some(batch) = rpc::open_command_line_batch().await? {
+                    batch::run_command_line(batch).await?;
+                    return Ok(None);
+                }
+
                 let has_rpc_file = rpc::open_command_line_save().await?;
                 let result = match has_rpc_file {
                     Some(rpc_file) => {
                         let RpcFile { path, file } = rpc_file;
-                        Self::deserialize(path, file.decode()?).map(Some)?
+                        let save_game = Self::deserialize(path, file.decode()?)?;
+                        Self::run_command_line_script(&save_game).await?;
+                        Some(save_game)
                     }
                     None => None,
                 };
@@ -226,6 +505,24 @@ impl SaveHandlerProvider {
         });
     }
 
+    async fn run_command_line_script(save_game: &SaveGame) -> Result<()> {
+        if let Some(rpc_file) = rpc::open_command_line_script().await? {
+            let script = String::from_utf8(rpc_file.file.decode()?)?;
+            let output = Rc::new(RefCell::new(Vec::new()));
+            let result = {
+                let output = Rc::clone(&output);
+                script::run(save_game, &script, move |line| output.borrow_mut().push(line))
+            };
+
+            let lines = output.take();
+            if !lines.is_empty() {
+                rpc::print_script_output(lines).await?;
+            }
+            result.context("Failed to run the command line script")?;
+        }
+        Ok(())
+    }
+
     fn open_dropped_file(ctx: &Context<Self>, file_name: String, bytes: Vec<u8>) {
         ctx.link().send_message({
             let deserialize = || Self::deserialize(file_name.into(), bytes);
@@ -237,8 +534,8 @@ impl SaveHandlerProvider {
         });
     }
 
-    fn save_save(ctx: &Context<Self>, save_game: &Rc<SaveGame>) {
-        let (path, filters) = match save_game.as_ref() {
+    fn save_save(ctx: &Context<Self>, save_game: &Rc<SaveGame>, platform: SavePlatform) {
+        let (path, mut filters) = match save_game.as_ref() {
             SaveGame::MassEffect1 { ref file_path, .. } => {
                 (file_path.clone(), vec![("Mass Effect 1 save", vec!["MassEffectSave"])])
             }
@@ -266,6 +563,10 @@ impl SaveHandlerProvider {
                 ],
             ),
         };
+        // The PC filter comes first
+        if platform == SavePlatform::Xbox360 {
+            filters.reverse();
+        }
 
         let save_game = Rc::clone(save_game);
         ctx.link().send_future(async move {
@@ -273,7 +574,7 @@ impl SaveHandlerProvider {
                 let has_path = rpc::save_save_dialog(DialogParams { path, filters }).await?;
                 let cancelled = match has_path {
                     Some(path) => {
-                        let rpc_file = Self::serialize(path, save_game)?;
+                        let rpc_file = Self::serialize(path, Rc::clone(&save_game))?;
                         rpc::save_file(rpc_file).await?;
                         false
                     }
@@ -283,7 +584,7 @@ impl SaveHandlerProvider {
             };
 
             match handle_save.await.context("Failed to save the save") {
-                Ok(false) => Msg::SaveSaved,
+                Ok(false) => Msg::SaveSaved(save_game),
                 Ok(true) => Msg::Noop,
                 Err(err) => Msg::Error(err),
             }
@@ -305,7 +606,7 @@ impl SaveHandlerProvider {
         });
     }
 
-    fn deserialize(file_path: PathBuf, input: Vec<u8>) -> Result<SaveGame> {
+    pub fn deserialize(file_path: PathBuf, input: Vec<u8>) -> Result<SaveGame> {
         fn header<'de, T>(header: &'de [u8]) -> Result<T, unreal::Error>
         where
             T: Deserialize<'de>,
@@ -359,8 +660,18 @@ impl SaveHandlerProvider {
         Ok(save_game)
     }
 
-    fn serialize(path: PathBuf, save_game: Rc<SaveGame>) -> Result<RpcFile> {
-        let output = match save_game.as_ref() {
+    pub fn serialize(path: PathBuf, save_game: Rc<SaveGame>) -> Result<RpcFile> {
+        let output = Self::to_bytes(&path, &save_game)?;
+        let rpc_file = RpcFile {
+            path,
+            file: Base64File { unencoded_size: output.len(), base64: base64::encode(output) },
+        };
+
+        Ok(rpc_file)
+    }
+
+    fn to_bytes(path: &Path, save_game: &SaveGame) -> Result<Vec<u8>> {
+        let output = match save_game {
             SaveGame::MassEffect1 { save_game, .. } => unreal::Serializer::to_vec(&save_game)?,
             SaveGame::MassEffect1Le { save_game, .. } => {
                 let mut output = unreal::Serializer::to_vec(&save_game)?;
@@ -431,13 +742,7 @@ impl SaveHandlerProvider {
                 output
             }
         };
-
-        let rpc_file = RpcFile {
-            path,
-            file: Base64File { unencoded_size: output.len(), base64: base64::encode(output) },
-        };
-
-        Ok(rpc_file)
+        Ok(output)
     }
 
     fn import_head_morph(ctx: &Context<Self>, callback: Callback<HeadMorph>) {
@@ -504,15 +809,221 @@ impl SaveHandlerProvider {
         });
     }
 
+    fn import_plot_preset(ctx: &Context<Self>, callback: Callback<PlotPreset>) {
+        ctx.link().send_future(async move {
+            let handle_preset = async {
+                let has_rpc_file = rpc::import_plot_preset().await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => {
+                        let ron = String::from_utf8(rpc_file.file.decode()?)?;
+                        ron::from_str(&ron).map(Some)?
+                    }
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_preset.await.context("Failed to import the plot preset") {
+                Ok(Some(preset)) => Msg::PlotPresetImported(preset, callback),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn export_plot_preset(ctx: &Context<Self>, preset: Rc<PlotPreset>) {
+        ctx.link().send_future(async move {
+            let handle_preset = async {
+                let has_path = rpc::export_plot_preset_dialog().await?;
+                let cancelled = match has_path {
+                    Some(path) => {
+                        let pretty_config = PrettyConfig::new().new_line(String::from('\n'));
+
+                        let output = ron::ser::to_string_pretty(&*preset, pretty_config)?;
+                        let rpc_file = RpcFile {
+                            path,
+                            file: Base64File {
+                                unencoded_size: output.len(),
+                                base64: base64::encode(output),
+                            },
+                        };
+                        rpc::save_file(rpc_file).await?;
+                        false
+                    }
+                    None => true,
+                };
+                Ok::<_, Error>(cancelled)
+            };
+
+            match handle_preset.await.context("Failed to export the plot preset") {
+                Ok(false) => Msg::PlotPresetExported,
+                Ok(true) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn load_settings(ctx: &Context<Self>) {
+        ctx.link().send_future(async {
+            match rpc::get_settings().await.context("Failed to load the settings") {
+                Ok(settings) => Msg::SettingsLoaded(settings),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn load_language(ctx: &Context<Self>, language: Language) {
+        if language == i18n::language() {
+            return;
+        }
+
+        ctx.link().send_future(async move {
+            let handle_catalog = async {
+                let messages = match language.catalog_path() {
+                    Some(path) => {
+                        let rpc_file = rpc::load_database(&path).await?;
+                        let file = String::from_utf8(rpc_file.file.decode()?)?;
+                        ron::from_str(&file)?
+                    }
+                    None => HashMap::new(),
+                };
+                Ok::<_, Error>(messages)
+            };
+
+            match handle_catalog.await.context("Failed to load the translations") {
+                Ok(messages) => Msg::LanguageLoaded(language, messages),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn save_settings(ctx: &Context<Self>, settings: Settings) {
+        ctx.link().send_future(async {
+            match rpc::set_settings(settings.clone()).await.context("Failed to save the settings") {
+                Ok(_) => Msg::SettingsSaved(settings),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn add_recent_file(ctx: &Context<Self>, save_game: &SaveGame) {
+        let path = save_game.file_path().to_owned();
+        // Dropped saves only have a file name
+        if !path.to_string_lossy().contains(['/', '\\']) {
+            return;
+        }
+
+        let recent_file = RecentFile { path, game: save_game.game_name().to_owned() };
+        ctx.link().send_future(async {
+            match rpc::add_recent_file(recent_file).await.context("Failed to save the settings") {
+                Ok(settings) => Msg::SettingsLoaded(settings),
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn open_script(ctx: &Context<Self>, callback: Callback<String>) {
+        ctx.link().send_future(async move {
+            let handle_script = async {
+                let has_rpc_file = rpc::open_script().await?;
+                let result = match has_rpc_file {
+                    Some(rpc_file) => String::from_utf8(rpc_file.file.decode()?).map(Some)?,
+                    None => None,
+                };
+                Ok::<_, Error>(result)
+            };
+
+            match handle_script.await.context("Failed to open the script") {
+                Ok(Some(script)) => Msg::ScriptOpened(script, callback),
+                Ok(None) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn save_script(ctx: &Context<Self>, script: String) {
+        ctx.link().send_future(async move {
+            let handle_script = async {
+                let has_path = rpc::save_script_dialog().await?;
+                let cancelled = match has_path {
+                    Some(path) => {
+                        let rpc_file = RpcFile {
+                            path,
+                            file: Base64File {
+                                unencoded_size: script.len(),
+                                base64: base64::encode(script),
+                            },
+                        };
+                        rpc::save_file(rpc_file).await?;
+                        false
+                    }
+                    None => true,
+                };
+                Ok::<_, Error>(cancelled)
+            };
+
+            match handle_script.await.context("Failed to save the script") {
+                Ok(false) => Msg::ScriptSaved,
+                Ok(true) => Msg::Noop,
+                Err(err) => Msg::Error(err),
+            }
+        });
+    }
+
+    fn close_document(&mut self, idx: usize) {
+        let documents = &mut self.save_handler.documents;
+        if idx >= documents.len() {
+            return;
+        }
+
+        let document = documents.remove(idx);
+        let was_active = self
+            .save_handler
+            .save_game
+            .as_ref()
+            .map(|save_game| Rc::ptr_eq(save_game, &document.save_game))
+            .unwrap_or_default();
+        if was_active {
+            // The next tab, or the last one
+            let next = documents.get(idx).or_else(|| documents.last());
+            self.save_handler.save_game = next.map(|document| Rc::clone(&document.save_game));
+            self.change_theme();
+        }
+    }
+
+    // Returns `true` if the dirty state of the active save has changed
+    fn active_document(&self) -> Option<&Document> {
+        let save_game = self.save_handler.save_game.as_ref()?;
+        self.save_handler
+            .documents
+            .iter()
+            .find(|document| Rc::ptr_eq(&document.save_game, save_game))
+    }
+
+    /// Serializes the active save to compare it with the one opened, only while it is not dirty
+    fn check_dirty(&mut self) -> bool {
+        let save_game = match self.save_handler.save_game {
+            Some(ref save_game) => save_game,
+            None => return false,
+        };
+
+        let document = self
+            .save_handler
+            .documents
+            .iter_mut()
+            .find(|document| Rc::ptr_eq(&document.save_game, save_game));
+        match document {
+            Some(document) if !document.is_dirty => {
+                document.is_dirty = save_game.checksum() != document.checksum;
+                document.is_dirty
+            }
+            _ => false,
+        }
+    }
+
     fn change_theme(&self) {
         if let Some(ref save_game) = self.save_handler.save_game {
-            let theme = match save_game.as_ref() {
-                SaveGame::MassEffect1 { .. }
-                | SaveGame::MassEffect1Le { .. }
-                | SaveGame::MassEffect1LePs4 { .. } => Theme::MassEffect1,
-                SaveGame::MassEffect2 { .. } | SaveGame::MassEffect2Le { .. } => Theme::MassEffect2,
-                SaveGame::MassEffect3 { .. } => Theme::MassEffect3,
-            };
+            let theme = save_game.theme();
 
             let body = utils::document().body().unwrap();
             let classes = body.class_list();
@@ -0,0 +1,186 @@
+use crate::{
+    gui::shared::save_plot_snapshot,
+    save_data::mass_effect_1_le::{player::Me1LeClass, Me1LeSaveData},
+    services::save_handler::SaveGame,
+};
+
+/// What the save library displays of a save, without keeping the whole save in memory
+#[derive(Clone, PartialEq, Default)]
+pub struct SaveSummary {
+    pub name: String,
+    pub class: String,
+    pub level: Option<i32>,
+    pub timestamp: String,
+    pub location: String,
+    /// ME1LE only, the id shared by all the saves of a career
+    pub character_id: Option<String>,
+}
+
+pub fn summarize(save_game: &SaveGame) -> SaveSummary {
+    match save_game {
+        SaveGame::MassEffect1 { save_game, .. } => {
+            // The player is a raw property tree, only the state is easily readable
+            let save_game = save_game.borrow();
+            let state = save_game.state();
+            let location = state.base_level_name().clone();
+            SaveSummary { location, ..Default::default() }
+        }
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let save_data = save_game.save_data.borrow();
+            me1_le_summary(&save_data)
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => me1_le_summary(&save_game.borrow()),
+        SaveGame::MassEffect2 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let player = save_game.player();
+            let summary = SaveSummary {
+                name: player.first_name().clone(),
+                class: class_name(&player.class_name()),
+                level: Some(player.level()),
+                timestamp: save_game.timestamp().to_string(),
+                location: save_game.base_level_name().clone(),
+                character_id: None,
+            };
+            summary
+        }
+        SaveGame::MassEffect2Le { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let player = save_game.player();
+            let summary = SaveSummary {
+                name: player.first_name().clone(),
+                class: class_name(&player.class_name()),
+                level: Some(player.level()),
+                timestamp: save_game.timestamp().to_string(),
+                location: save_game.base_level_name().clone(),
+                character_id: None,
+            };
+            summary
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            let save_game = save_game.borrow();
+            let player = save_game.player();
+            let summary = SaveSummary {
+                name: player.first_name().clone(),
+                class: class_name(&player.class_name()),
+                level: Some(player.level()),
+                timestamp: save_game.timestamp().to_string(),
+                location: save_game.base_level_name().clone(),
+                character_id: None,
+            };
+            summary
+        }
+    }
+}
+
+/// What changed from `other` to `current`, one line per change
+pub fn diff(current: &SaveGame, other: &SaveGame) -> Vec<String> {
+    let (current_summary, other_summary) = (summarize(current), summarize(other));
+    let mut changes = Vec::new();
+    let mut changed = |label: &str, current: String, other: String| {
+        if current != other {
+            changes.push(format!("{}: {} => {}", label, other, current));
+        }
+    };
+    changed("Name", current_summary.name, other_summary.name);
+    changed("Class", current_summary.class, other_summary.class);
+    changed(
+        "Level",
+        current_summary.level.map(|level| level.to_string()).unwrap_or_default(),
+        other_summary.level.map(|level| level.to_string()).unwrap_or_default(),
+    );
+    changed("Location", current_summary.location, other_summary.location);
+
+    let changed_plots = save_plot_snapshot(current).diff(&save_plot_snapshot(other));
+    if !changed_plots.booleans.is_empty() {
+        changes.push(format!("{} plot boolean(s) changed", changed_plots.booleans.len()));
+    }
+    if !changed_plots.integers.is_empty() {
+        changes.push(format!("{} plot integer(s) changed", changed_plots.integers.len()));
+    }
+    if !changed_plots.floats.is_empty() {
+        changes.push(format!("{} plot float(s) changed", changed_plots.floats.len()));
+    }
+    changes
+}
+
+fn me1_le_summary(save_data: &Me1LeSaveData) -> SaveSummary {
+    let player = save_data.player();
+    let class = Me1LeClass::variants()
+        .get(player.player_class().clone() as usize)
+        .map(|class| class.to_string())
+        .unwrap_or_default();
+    let summary = SaveSummary {
+        name: player.first_name().clone(),
+        class,
+        level: Some(player.level()),
+        timestamp: save_data.timestamp().to_string(),
+        location: save_data.base_level_name().clone(),
+        character_id: Some(save_data.character_id().clone()),
+    };
+    summary
+}
+
+// `SFXGame.SFXPawn_PlayerAdept` => `Adept`
+fn class_name(class_name: &str) -> String {
+    let class_name = class_name.rsplit(['.', '_']).next().unwrap_or(class_name);
+    class_name.strip_prefix("Player").unwrap_or(class_name).to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::services::save_handler::SaveHandlerProvider;
+
+    #[test]
+    fn summarize_saves() -> Result<()> {
+        for file in ["test/ME1LeSave.pcsav", "test/ME2Save.pcsav", "test/ME3Save.pcsav"] {
+            let input = fs::read(file)?;
+            let save_game = SaveHandlerProvider::deserialize(PathBuf::from(file), input)?;
+            let summary = summarize(&save_game);
+
+            assert!(!summary.name.is_empty(), "{}", file);
+            assert!(!summary.class.is_empty(), "{}", file);
+            assert!(!summary.location.is_empty(), "{}", file);
+            assert!(summary.level.unwrap_or_default() > 0, "{}", file);
+        }
+
+        let input = fs::read("test/ME1Save.MassEffectSave")?;
+        let save_game = SaveHandlerProvider::deserialize(PathBuf::from("ME1Save"), input)?;
+        assert!(!summarize(&save_game).location.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff_saves() -> Result<()> {
+        let open = || -> Result<SaveGame> {
+            let input = fs::read("test/ME2Save.pcsav")?;
+            SaveHandlerProvider::deserialize(PathBuf::from("ME2Save.pcsav"), input)
+        };
+        let (current, other) = (open()?, open()?);
+        assert!(diff(&current, &other).is_empty());
+
+        if let SaveGame::MassEffect2 { save_game, .. } = &current {
+            let save_game = save_game.borrow();
+            let plot = save_game.plot();
+            let mut booleans = plot.booleans.borrow_mut();
+            let value = booleans[10];
+            booleans.set(10, !value);
+            let floats = plot.floats.borrow();
+            floats[0].set(floats[0].get() + 1.0);
+            save_game.player().level.set(save_game.player().level() + 1);
+        }
+        let changes = diff(&current, &other);
+        assert_eq!(changes.len(), 3);
+        assert!(changes[0].starts_with("Level: "));
+        assert_eq!(changes[1], "1 plot boolean(s) changed");
+        assert_eq!(changes[2], "1 plot float(s) changed");
+
+        Ok(())
+    }
+}
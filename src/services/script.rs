@@ -0,0 +1,638 @@
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use indexmap::map::Entry;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope};
+
+use crate::{
+    gui::shared::{FloatPlotType, IntPlotType},
+    save_data::{
+        mass_effect_1_le::{
+            player::{ComplexTalent, Item, ItemLevel, Player as Me1LePlayer},
+            squad::Henchman as Me1LeHenchman,
+        },
+        mass_effect_2::{
+            player::{Player as Me2Player, Power as Me2Power},
+            squad::Henchman as Me2Henchman,
+        },
+        mass_effect_3::{
+            player::{Player as Me3Player, Power as Me3Power},
+            squad::Henchman as Me3Henchman,
+        },
+        shared::{
+            plot::{check_plot_id, BitVec, PlotTable},
+            Kismet,
+        },
+        RcCell, RcRef,
+    },
+    services::save_handler::SaveGame,
+};
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+// Prevents an infinite loop from freezing the whole app
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+/// Runs `script` against `save_game`, the save is exposed to the script as the `save` variable.
+/// Everything printed by the script with `print` or `debug` is sent to `output`.
+pub fn run(save_game: &SaveGame, script: &str, output: impl Fn(String) + 'static) -> Result<()> {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+
+    let output = Rc::new(output);
+    {
+        let output = Rc::clone(&output);
+        engine.on_print(move |text| output(text.to_owned()));
+    }
+    engine.on_debug(move |text, _, pos| output(format!("[{}] {}", pos, text)));
+
+    register_save(&mut engine);
+    register_plot(&mut engine);
+    register_player(&mut engine);
+    register_squad(&mut engine);
+
+    let mut scope = Scope::new();
+    scope.push("save", Save(save_game.clone()));
+
+    engine.run_with_scope(&mut scope, script).map_err(|err| anyhow!(err.to_string()))
+}
+
+fn not_available<T>(name: &str) -> ScriptResult<T> {
+    Err(format!("`{}` is not available for this game", name).into())
+}
+
+// Save
+#[derive(Clone)]
+struct Save(SaveGame);
+
+fn register_save(engine: &mut Engine) {
+    engine.register_type_with_name::<Save>("Save");
+
+    engine.register_get("game", |save: &mut Save| -> String {
+        match save.0 {
+            SaveGame::MassEffect1 { .. } => "Mass Effect 1",
+            SaveGame::MassEffect1Le { .. } | SaveGame::MassEffect1LePs4 { .. } => {
+                "Mass Effect 1 Legendary"
+            }
+            SaveGame::MassEffect2 { .. } => "Mass Effect 2",
+            SaveGame::MassEffect2Le { .. } => "Mass Effect 2 Legendary",
+            SaveGame::MassEffect3 { .. } => "Mass Effect 3",
+        }
+        .to_owned()
+    });
+
+    engine.register_get("plot", |save: &mut Save| -> Plot {
+        match save.0 {
+            SaveGame::MassEffect1 { ref save_game, .. } => {
+                Plot::from_table(&save_game.borrow().state().plot())
+            }
+            SaveGame::MassEffect1Le { ref save_game, .. } => {
+                Plot::from_table(&save_game.borrow().save_data.borrow().plot())
+            }
+            SaveGame::MassEffect1LePs4 { ref save_game, .. } => {
+                Plot::from_table(&save_game.borrow().plot())
+            }
+            SaveGame::MassEffect2 { ref save_game, .. } => {
+                Plot::from_table(&save_game.borrow().plot())
+            }
+            SaveGame::MassEffect2Le { ref save_game, .. } => {
+                Plot::from_table(&save_game.borrow().plot())
+            }
+            SaveGame::MassEffect3 { ref save_game, .. } => {
+                let me3 = save_game.borrow();
+                let plot = me3.plot();
+                Plot {
+                    booleans: RcRef::clone(&plot.booleans),
+                    integers: IntPlotType::IndexMap(RcRef::clone(&plot.integers)),
+                    floats: FloatPlotType::IndexMap(RcRef::clone(&plot.floats)),
+                }
+            }
+        }
+    });
+
+    engine.register_get("me1_plot", |save: &mut Save| -> ScriptResult<Plot> {
+        match save.0 {
+            SaveGame::MassEffect2 { ref save_game, .. } => {
+                Ok(Plot::from_table(&save_game.borrow().me1_plot()))
+            }
+            SaveGame::MassEffect2Le { ref save_game, .. } => {
+                Ok(Plot::from_table(&save_game.borrow().me1_plot()))
+            }
+            _ => not_available("me1_plot"),
+        }
+    });
+
+    engine.register_get("player", |save: &mut Save| -> ScriptResult<Player> {
+        match save.0 {
+            SaveGame::MassEffect1 { .. } => not_available("player"),
+            SaveGame::MassEffect1Le { ref save_game, .. } => {
+                Ok(Player::Me1Le(RcRef::clone(&save_game.borrow().save_data.borrow().player)))
+            }
+            SaveGame::MassEffect1LePs4 { ref save_game, .. } => {
+                Ok(Player::Me1Le(RcRef::clone(&save_game.borrow().player)))
+            }
+            SaveGame::MassEffect2 { ref save_game, .. } => {
+                Ok(Player::Me2(RcRef::clone(&save_game.borrow().player)))
+            }
+            SaveGame::MassEffect2Le { ref save_game, .. } => {
+                Ok(Player::Me2(RcRef::clone(&save_game.borrow().player)))
+            }
+            SaveGame::MassEffect3 { ref save_game, .. } => {
+                Ok(Player::Me3(RcRef::clone(&save_game.borrow().player)))
+            }
+        }
+    });
+
+    engine.register_get("squad", |save: &mut Save| -> ScriptResult<Array> {
+        fn to_array<T: Clone>(squad: &[RcRef<T>], f: impl Fn(RcRef<T>) -> Henchman) -> Array {
+            squad.iter().map(|henchman| Dynamic::from(f(RcRef::clone(henchman)))).collect()
+        }
+
+        match save.0 {
+            SaveGame::MassEffect1 { .. } => not_available("squad"),
+            SaveGame::MassEffect1Le { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().save_data.borrow().squad(), Henchman::Me1Le))
+            }
+            SaveGame::MassEffect1LePs4 { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().squad(), Henchman::Me1Le))
+            }
+            SaveGame::MassEffect2 { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().squad(), Henchman::Me2))
+            }
+            SaveGame::MassEffect2Le { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().squad(), Henchman::Me2))
+            }
+            SaveGame::MassEffect3 { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().squad(), Henchman::Me3))
+            }
+        }
+    });
+
+    engine.register_get("kismet_records", |save: &mut Save| -> ScriptResult<Array> {
+        fn to_array(kismet_records: &[RcRef<Kismet>]) -> Array {
+            kismet_records.iter().map(|kismet| Dynamic::from(RcRef::clone(kismet))).collect()
+        }
+
+        match save.0 {
+            SaveGame::MassEffect2 { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().kismet_records()))
+            }
+            SaveGame::MassEffect2Le { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().kismet_records()))
+            }
+            SaveGame::MassEffect3 { ref save_game, .. } => {
+                Ok(to_array(&save_game.borrow().kismet_records()))
+            }
+            _ => not_available("kismet_records"),
+        }
+    });
+
+    // Read-only copy of the whole save, for everything that doesn't have a binding
+    engine.register_fn("raw", |save: &mut Save| -> ScriptResult<Dynamic> {
+        match save.0 {
+            SaveGame::MassEffect1 { ref save_game, .. } => {
+                rhai::serde::to_dynamic(&*save_game.borrow().state())
+            }
+            SaveGame::MassEffect1Le { ref save_game, .. } => {
+                rhai::serde::to_dynamic(&save_game.borrow().save_data)
+            }
+            SaveGame::MassEffect1LePs4 { ref save_game, .. } => rhai::serde::to_dynamic(save_game),
+            SaveGame::MassEffect2 { ref save_game, .. } => rhai::serde::to_dynamic(save_game),
+            SaveGame::MassEffect2Le { ref save_game, .. } => rhai::serde::to_dynamic(save_game),
+            SaveGame::MassEffect3 { ref save_game, .. } => rhai::serde::to_dynamic(save_game),
+        }
+    });
+
+    engine.register_type_with_name::<RcRef<Kismet>>("Kismet");
+    engine.register_get("guid", |kismet: &mut RcRef<Kismet>| kismet.borrow().guid().hyphenated());
+    engine.register_get_set(
+        "value",
+        |kismet: &mut RcRef<Kismet>| kismet.borrow().value(),
+        |kismet: &mut RcRef<Kismet>, value: bool| kismet.borrow_mut().set_value(value),
+    );
+}
+
+// Plot
+#[derive(Clone)]
+struct Plot {
+    booleans: RcRef<BitVec>,
+    integers: IntPlotType,
+    floats: FloatPlotType,
+}
+
+impl Plot {
+    fn from_table(plot: &PlotTable) -> Self {
+        Plot {
+            booleans: RcRef::clone(&plot.booleans),
+            integers: IntPlotType::Vec(RcRef::clone(&plot.integers)),
+            floats: FloatPlotType::Vec(RcRef::clone(&plot.floats)),
+        }
+    }
+
+    fn id(id: i32) -> ScriptResult<usize> {
+        usize::try_from(id).map_err(|_| format!("invalid plot id: {}", id).into())
+    }
+
+    /// The id of a plot that will be set in a table of `len` plots
+    fn new_id(id: i32, len: usize) -> ScriptResult<usize> {
+        let id = Self::id(id)?;
+        check_plot_id(id, len).map_err(|err| err.to_string())?;
+        Ok(id)
+    }
+
+    fn get_bool(&mut self, id: i32) -> ScriptResult<bool> {
+        let id = Self::id(id)?;
+        Ok(self.booleans.borrow().get(id).map(|value| *value).unwrap_or_default())
+    }
+
+    fn set_bool(&mut self, id: i32, value: bool) -> ScriptResult<()> {
+        let mut booleans = self.booleans.borrow_mut();
+        let id = Self::new_id(id, booleans.len())?;
+        if id >= booleans.len() {
+            booleans.resize(id + 1, false);
+        }
+        booleans.set(id, value);
+        Ok(())
+    }
+
+    fn get_int(&mut self, id: i32) -> ScriptResult<i32> {
+        let value = match self.integers {
+            IntPlotType::Vec(ref vec) => vec.borrow().get(Self::id(id)?).map(RcCell::get),
+            IntPlotType::IndexMap(ref index_map) => index_map.borrow().get(&id).map(RcCell::get),
+        };
+        Ok(value.unwrap_or_default())
+    }
+
+    fn set_int(&mut self, id: i32, value: i32) -> ScriptResult<()> {
+        match self.integers {
+            IntPlotType::Vec(ref vec) => {
+                let mut vec = vec.borrow_mut();
+                let id = Self::new_id(id, vec.len())?;
+                if id >= vec.len() {
+                    vec.resize_with(id + 1, Default::default);
+                }
+                vec[id].set(value);
+            }
+            IntPlotType::IndexMap(ref index_map) => match index_map.borrow_mut().entry(id) {
+                Entry::Occupied(plot) => plot.get().set(value),
+                Entry::Vacant(plot) => {
+                    plot.insert(RcCell::new(value));
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn get_float(&mut self, id: i32) -> ScriptResult<f32> {
+        let value = match self.floats {
+            FloatPlotType::Vec(ref vec) => vec.borrow().get(Self::id(id)?).map(RcCell::get),
+            FloatPlotType::IndexMap(ref index_map) => index_map.borrow().get(&id).map(RcCell::get),
+        };
+        Ok(value.unwrap_or_default())
+    }
+
+    fn set_float(&mut self, id: i32, value: f32) -> ScriptResult<()> {
+        match self.floats {
+            FloatPlotType::Vec(ref vec) => {
+                let mut vec = vec.borrow_mut();
+                let id = Self::new_id(id, vec.len())?;
+                if id >= vec.len() {
+                    vec.resize_with(id + 1, Default::default);
+                }
+                vec[id].set(value);
+            }
+            FloatPlotType::IndexMap(ref index_map) => match index_map.borrow_mut().entry(id) {
+                Entry::Occupied(plot) => plot.get().set(value),
+                Entry::Vacant(plot) => {
+                    plot.insert(RcCell::new(value));
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+fn register_plot(engine: &mut Engine) {
+    engine.register_type_with_name::<Plot>("Plot");
+    engine.register_fn("get_bool", Plot::get_bool);
+    engine.register_fn("set_bool", Plot::set_bool);
+    engine.register_fn("get_int", Plot::get_int);
+    engine.register_fn("set_int", Plot::set_int);
+    engine.register_fn("get_float", Plot::get_float);
+    engine.register_fn("set_float", Plot::set_float);
+    engine.register_get("bool_count", |plot: &mut Plot| plot.booleans.borrow().len() as i32);
+}
+
+// Player
+#[derive(Clone)]
+enum Player {
+    Me1Le(RcRef<Me1LePlayer>),
+    Me2(RcRef<Me2Player>),
+    Me3(RcRef<Me3Player>),
+}
+
+#[derive(Clone)]
+enum Power {
+    Me2(RcRef<Me2Power>),
+    Me3(RcRef<Me3Power>),
+}
+
+// Registers a getter / setter for each `RcCell` field shared by the listed players
+macro_rules! player_fields {
+    ($engine:ident, $games:tt => { $($field:ident: $ty:ty),+ $(,)? }) => {
+        $(player_fields!(@field $engine, $games, $field, $ty);)+
+    };
+    (@field $engine:ident, [$($game:ident),+], $field:ident, $ty:ty) => {
+        $engine.register_get_set(
+            stringify!($field),
+            |player: &mut Player| -> ScriptResult<$ty> {
+                #[allow(unreachable_patterns)]
+                match player {
+                    $(Player::$game(player) => Ok(player.borrow().$field.get()),)+
+                    _ => not_available(stringify!($field)),
+                }
+            },
+            |player: &mut Player, value: $ty| -> ScriptResult<()> {
+                #[allow(unreachable_patterns)]
+                match player {
+                    $(Player::$game(player) => Ok(player.borrow().$field.set(value)),)+
+                    _ => not_available(stringify!($field)),
+                }
+            },
+        );
+    };
+}
+
+fn register_player(engine: &mut Engine) {
+    engine.register_type_with_name::<Player>("Player");
+
+    player_fields!(engine, [Me1Le, Me2, Me3] => {
+        is_female: bool,
+        level: i32,
+        current_xp: f32,
+        talent_points: i32,
+        credits: i32,
+        medigel: i32,
+    });
+    player_fields!(engine, [Me1Le] => { omnigel: f32 });
+    player_fields!(engine, [Me2] => {
+        eezo: i32,
+        iridium: i32,
+        palladium: i32,
+        platinum: i32,
+        probes: i32,
+    });
+    player_fields!(engine, [Me2, Me3] => { current_fuel: f32 });
+
+    engine.register_get_set(
+        "first_name",
+        |player: &mut Player| match player {
+            Player::Me1Le(player) => player.borrow().first_name().clone(),
+            Player::Me2(player) => player.borrow().first_name().clone(),
+            Player::Me3(player) => player.borrow().first_name().clone(),
+        },
+        |player: &mut Player, value: String| match player {
+            Player::Me1Le(player) => *player.borrow_mut().first_name_mut() = value,
+            Player::Me2(player) => *player.borrow_mut().first_name_mut() = value,
+            Player::Me3(player) => *player.borrow_mut().first_name_mut() = value,
+        },
+    );
+
+    engine.register_get("powers", |player: &mut Player| -> ScriptResult<Array> {
+        match player {
+            Player::Me1Le(_) => not_available("powers"),
+            Player::Me2(player) => Ok(powers(&player.borrow().powers(), Power::Me2)),
+            Player::Me3(player) => Ok(powers(&player.borrow().powers(), Power::Me3)),
+        }
+    });
+
+    engine.register_get("talents", |player: &mut Player| -> ScriptResult<Array> {
+        match player {
+            Player::Me1Le(player) => Ok(talents(&player.borrow().complex_talents())),
+            _ => not_available("talents"),
+        }
+    });
+
+    engine.register_get("inventory", |player: &mut Player| -> ScriptResult<Array> {
+        match player {
+            Player::Me1Le(player) => {
+                let player = player.borrow();
+                let inventory = player.inventory();
+                let items = inventory.inventory();
+                Ok(items.iter().map(|item| Dynamic::from(RcRef::clone(item))).collect())
+            }
+            _ => not_available("inventory"),
+        }
+    });
+
+    engine.register_fn(
+        "add_item",
+        |player: &mut Player,
+         item_id: i32,
+         manufacturer_id: i32,
+         item_level: i32|
+         -> ScriptResult<()> {
+            match player {
+                Player::Me1Le(player) => {
+                    let item = Item::default();
+                    item.item_id.set(item_id);
+                    item.manufacturer_id.set(manufacturer_id);
+                    *item.item_level.borrow_mut() = item_level_from(item_level)?;
+
+                    let player = player.borrow();
+                    let inventory = player.inventory();
+                    inventory.inventory.borrow_mut().push(item.into());
+                    Ok(())
+                }
+                _ => not_available("add_item"),
+            }
+        },
+    );
+
+    register_power(engine);
+    register_talent(engine);
+    register_item(engine);
+}
+
+fn powers<T: Clone>(powers: &[RcRef<T>], f: impl Fn(RcRef<T>) -> Power) -> Array {
+    powers.iter().map(|power| Dynamic::from(f(RcRef::clone(power)))).collect()
+}
+
+fn talents(talents: &[RcRef<ComplexTalent>]) -> Array {
+    talents.iter().map(|talent| Dynamic::from(RcRef::clone(talent))).collect()
+}
+
+fn register_power(engine: &mut Engine) {
+    engine.register_type_with_name::<Power>("Power");
+    engine.register_get("name", |power: &mut Power| match power {
+        Power::Me2(power) => power.borrow().name().clone(),
+        Power::Me3(power) => power.borrow().name().clone(),
+    });
+    engine.register_get_set(
+        "rank",
+        |power: &mut Power| match power {
+            Power::Me2(power) => power.borrow().rank(),
+            Power::Me3(power) => power.borrow().rank(),
+        },
+        |power: &mut Power, value: f32| match power {
+            Power::Me2(power) => power.borrow_mut().set_rank(value),
+            Power::Me3(power) => power.borrow_mut().set_rank(value),
+        },
+    );
+}
+
+fn register_talent(engine: &mut Engine) {
+    engine.register_type_with_name::<RcRef<ComplexTalent>>("Talent");
+    engine
+        .register_get("talent_id", |talent: &mut RcRef<ComplexTalent>| talent.borrow().talent_id());
+    engine.register_get("max_rank", |talent: &mut RcRef<ComplexTalent>| talent.borrow().max_rank());
+    engine.register_get_set(
+        "current_rank",
+        |talent: &mut RcRef<ComplexTalent>| talent.borrow().current_rank(),
+        |talent: &mut RcRef<ComplexTalent>, value: i32| talent.borrow_mut().set_current_rank(value),
+    );
+}
+
+fn item_level_from(item_level: i32) -> ScriptResult<ItemLevel> {
+    match usize::try_from(item_level) {
+        Ok(idx) if idx < ItemLevel::variants().len() => Ok(ItemLevel::from(idx)),
+        _ => Err(format!("invalid item level: {}", item_level).into()),
+    }
+}
+
+fn register_item(engine: &mut Engine) {
+    engine.register_type_with_name::<RcRef<Item>>("Item");
+    engine.register_get_set(
+        "item_id",
+        |item: &mut RcRef<Item>| item.borrow().item_id(),
+        |item: &mut RcRef<Item>, value: i32| item.borrow_mut().set_item_id(value),
+    );
+    engine.register_get_set(
+        "manufacturer_id",
+        |item: &mut RcRef<Item>| item.borrow().manufacturer_id(),
+        |item: &mut RcRef<Item>, value: i32| item.borrow_mut().set_manufacturer_id(value),
+    );
+    engine.register_get_set(
+        "item_level",
+        |item: &mut RcRef<Item>| usize::from(*item.borrow().item_level()) as i32,
+        |item: &mut RcRef<Item>, value: i32| -> ScriptResult<()> {
+            *item.borrow_mut().item_level_mut() = item_level_from(value)?;
+            Ok(())
+        },
+    );
+}
+
+// Squad
+#[derive(Clone)]
+enum Henchman {
+    Me1Le(RcRef<Me1LeHenchman>),
+    Me2(RcRef<Me2Henchman>),
+    Me3(RcRef<Me3Henchman>),
+}
+
+fn register_squad(engine: &mut Engine) {
+    engine.register_type_with_name::<Henchman>("Henchman");
+
+    engine.register_get("tag", |henchman: &mut Henchman| match henchman {
+        Henchman::Me1Le(henchman) => henchman.borrow().tag().clone(),
+        Henchman::Me2(henchman) => henchman.borrow().tag().clone(),
+        Henchman::Me3(henchman) => henchman.borrow().tag().clone(),
+    });
+
+    engine.register_get_set(
+        "level",
+        |henchman: &mut Henchman| match henchman {
+            Henchman::Me1Le(henchman) => henchman.borrow().level(),
+            Henchman::Me2(henchman) => henchman.borrow().character_level(),
+            Henchman::Me3(henchman) => henchman.borrow().character_level(),
+        },
+        |henchman: &mut Henchman, value: i32| match henchman {
+            Henchman::Me1Le(henchman) => henchman.borrow_mut().set_level(value),
+            Henchman::Me2(henchman) => henchman.borrow_mut().set_character_level(value),
+            Henchman::Me3(henchman) => henchman.borrow_mut().set_character_level(value),
+        },
+    );
+
+    engine.register_get_set(
+        "talent_points",
+        |henchman: &mut Henchman| match henchman {
+            Henchman::Me1Le(henchman) => henchman.borrow().talent_points(),
+            Henchman::Me2(henchman) => henchman.borrow().talent_points(),
+            Henchman::Me3(henchman) => henchman.borrow().talent_points(),
+        },
+        |henchman: &mut Henchman, value: i32| match henchman {
+            Henchman::Me1Le(henchman) => henchman.borrow_mut().set_talent_points(value),
+            Henchman::Me2(henchman) => henchman.borrow_mut().set_talent_points(value),
+            Henchman::Me3(henchman) => henchman.borrow_mut().set_talent_points(value),
+        },
+    );
+
+    engine.register_get("powers", |henchman: &mut Henchman| -> ScriptResult<Array> {
+        match henchman {
+            Henchman::Me1Le(_) => not_available("powers"),
+            Henchman::Me2(henchman) => Ok(powers(&henchman.borrow().powers(), Power::Me2)),
+            Henchman::Me3(henchman) => Ok(powers(&henchman.borrow().powers(), Power::Me3)),
+        }
+    });
+
+    engine.register_get("talents", |henchman: &mut Henchman| -> ScriptResult<Array> {
+        match henchman {
+            Henchman::Me1Le(henchman) => Ok(talents(&henchman.borrow().complex_talents())),
+            _ => not_available("talents"),
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, fs, path::PathBuf};
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    #[test]
+    fn run_script() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: RcRef<Me2SaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let save_game = SaveGame::MassEffect2 {
+            file_path: PathBuf::from("test/ME2Save.pcsav"),
+            save_game: RcRef::clone(&me2_save_game),
+        };
+
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let script = r#"
+            save.player.credits = 123456;
+            save.player.first_name = "Jane";
+            save.plot.set_bool(42, true);
+            save.plot.set_int(7, 3);
+            for henchman in save.squad {
+                for power in henchman.powers {
+                    power.rank = 4.0;
+                }
+            }
+            print(save.game);
+            print(save.plot.get_int(7));
+        "#;
+        {
+            let output = Rc::clone(&output);
+            run(&save_game, script, move |line| output.borrow_mut().push(line))?;
+        }
+
+        let me2 = me2_save_game.borrow();
+        assert_eq!(me2.player().credits(), 123456);
+        assert_eq!(*me2.player().first_name(), "Jane");
+        assert!(me2.plot().booleans().get(42).map(|value| *value).unwrap_or_default());
+        assert_eq!(me2.plot().integers()[7].get(), 3);
+        assert!(me2
+            .squad()
+            .iter()
+            .flat_map(|henchman| henchman.borrow().powers().clone())
+            .all(|power| power.borrow().rank() == 4.0));
+        assert_eq!(*output.borrow(), ["Mass Effect 2", "3"]);
+
+        // Errors are reported instead of panicking
+        assert!(run(&save_game, "save.player.omnigel = 1.0;", |_| ()).is_err());
+        assert!(run(&save_game, "save.plot.set_bool(2147483647, true);", |_| ()).is_err());
+        Ok(())
+    }
+}
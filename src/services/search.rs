@@ -0,0 +1,334 @@
+use std::{collections::HashSet, fmt, rc::Rc};
+
+use indexmap::IndexMap;
+use rhai::{Array, Dynamic, Map};
+
+use crate::{
+    save_data::{
+        mass_effect_1::plot_db::Me1PlotDb,
+        mass_effect_1_le::{
+            item_db::{DbItem, Me1ItemDb},
+            player::Item,
+            Me1LeSaveData,
+        },
+        mass_effect_2::plot_db::Me2PlotDb,
+        mass_effect_3::plot_db::Me3PlotDb,
+        shared::plot::{PlotCategory, PlotKind, RawPlotDb},
+        RcRef,
+    },
+    services::{database::Databases, save_handler::SaveGame},
+};
+
+/// Something that can be found with the global search.
+/// `path` goes from the main tab to the label of the control to highlight.
+pub struct SearchEntry {
+    pub path: Vec<String>,
+    pub value: Option<String>,
+    lowercase: String,
+}
+
+impl SearchEntry {
+    fn new(path: Vec<String>, value: Option<String>) -> Self {
+        let mut entry = SearchEntry { path, value, lowercase: String::new() };
+        entry.lowercase = entry.to_string().to_lowercase();
+        entry
+    }
+
+    /// `query` must be lowercase
+    pub fn matches(&self, query: &str) -> bool {
+        self.lowercase.contains(query)
+    }
+}
+
+impl fmt::Display for SearchEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.join(" > "))?;
+        if let Some(ref value) = self.value {
+            write!(f, " = {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Indexes the plot labels, the item names and the raw data of `save_game`.
+/// The databases that are not loaded yet are skipped.
+pub fn build_index(save_game: &SaveGame, dbs: &Databases) -> Vec<SearchEntry> {
+    let mut index = Vec::new();
+
+    match save_game {
+        SaveGame::MassEffect1 { .. } => {
+            if let Some(plot_db) = dbs.clone().get_me1_plot() {
+                me1_plot(&mut index, &["Plot"], &plot_db);
+            }
+            raw_plot(&mut index, dbs.clone().get_me1_raw_plot());
+        }
+        SaveGame::MassEffect1Le { save_game, .. } => {
+            let save_data = &save_game.borrow().save_data;
+            me1_le(&mut index, dbs, save_data);
+        }
+        SaveGame::MassEffect1LePs4 { save_game, .. } => me1_le(&mut index, dbs, save_game),
+        SaveGame::MassEffect2 { save_game, .. } => {
+            me2(&mut index, dbs);
+            raw_data(&mut index, "Mass Effect 2", rhai::serde::to_dynamic(save_game).ok());
+        }
+        SaveGame::MassEffect2Le { save_game, .. } => {
+            me2(&mut index, dbs);
+            raw_data(&mut index, "Mass Effect 2", rhai::serde::to_dynamic(save_game).ok());
+        }
+        SaveGame::MassEffect3 { save_game, .. } => {
+            if let Some(plot_db) = dbs.clone().get_me3_plot() {
+                me3_plot(&mut index, &["Plot"], &plot_db);
+            }
+            if let Some(plot_db) = dbs.clone().get_me2_plot() {
+                me2_plot(&mut index, &["Plot", "Mass Effect 2"], &plot_db);
+            }
+            if let Some(plot_db) = dbs.clone().get_me1_plot() {
+                me1_plot(&mut index, &["Plot", "Mass Effect 1"], &plot_db);
+            }
+            raw_plot(&mut index, dbs.clone().get_me3_raw_plot());
+            raw_data(&mut index, "Mass Effect 3", rhai::serde::to_dynamic(save_game).ok());
+        }
+    }
+
+    index
+}
+
+fn me1_le(index: &mut Vec<SearchEntry>, dbs: &Databases, save_data: &RcRef<Me1LeSaveData>) {
+    if let Some(plot_db) = dbs.clone().get_me1_plot() {
+        me1_plot(index, &["Plot"], &plot_db);
+    }
+    if let Some(item_db) = dbs.clone().get_me1_item_db() {
+        items(index, &item_db, &save_data.borrow());
+    }
+    raw_plot(index, dbs.clone().get_me1_raw_plot());
+    raw_data(index, "Mass Effect 1", rhai::serde::to_dynamic(save_data).ok());
+}
+
+fn me2(index: &mut Vec<SearchEntry>, dbs: &Databases) {
+    if let Some(plot_db) = dbs.clone().get_me2_plot() {
+        me2_plot(index, &["Plot"], &plot_db);
+    }
+    if let Some(plot_db) = dbs.clone().get_me1_plot() {
+        me1_plot(index, &["Plot", "Mass Effect 1"], &plot_db);
+    }
+    raw_plot(index, dbs.clone().get_me2_raw_plot());
+}
+
+// Plot
+fn plot_category(index: &mut Vec<SearchEntry>, path: &[&str], category: &PlotCategory) {
+    for label in category.booleans.values().chain(category.integers.values()) {
+        let mut path: Vec<String> = path.iter().map(|&title| title.to_owned()).collect();
+        path.push(label.clone());
+        index.push(SearchEntry::new(path, None));
+    }
+}
+
+fn plot_categories(
+    index: &mut Vec<SearchEntry>, path: &[&str], categories: &IndexMap<String, PlotCategory>,
+) {
+    for (title, category) in categories {
+        let path: Vec<&str> = path.iter().copied().chain([title.as_str()]).collect();
+        plot_category(index, &path, category);
+    }
+}
+
+fn tab_path<'a>(path: &[&'a str], tab: &'a str) -> Vec<&'a str> {
+    path.iter().copied().chain([tab]).collect()
+}
+
+fn me1_plot(index: &mut Vec<SearchEntry>, path: &[&str], plot_db: &Me1PlotDb) {
+    let Me1PlotDb { player_crew, missions } = plot_db;
+    plot_categories(index, &tab_path(path, "Player / Crew"), player_crew);
+    plot_categories(index, &tab_path(path, "Missions"), missions);
+}
+
+fn me2_plot(index: &mut Vec<SearchEntry>, path: &[&str], plot_db: &Me2PlotDb) {
+    let Me2PlotDb {
+        player,
+        crew,
+        romance,
+        missions,
+        loyalty_missions,
+        research_upgrades,
+        rewards,
+        captains_cabin,
+        imported_me1,
+    } = plot_db;
+
+    plot_category(index, &tab_path(path, "Player"), player);
+    plot_categories(index, &tab_path(path, "Crew"), crew);
+    plot_categories(index, &tab_path(path, "Romance"), romance);
+    plot_categories(index, &tab_path(path, "Missions"), missions);
+    plot_categories(index, &tab_path(path, "Loyalty missions"), loyalty_missions);
+    plot_categories(index, &tab_path(path, "Research / Upgrades"), research_upgrades);
+    plot_category(index, &tab_path(path, "Captain's cabin"), captains_cabin);
+    plot_category(index, &tab_path(path, "Rewards"), rewards);
+    plot_categories(index, &tab_path(path, "Imported ME1"), imported_me1);
+}
+
+fn me3_plot(index: &mut Vec<SearchEntry>, path: &[&str], plot_db: &Me3PlotDb) {
+    let Me3PlotDb {
+        general,
+        crew,
+        romance,
+        missions,
+        citadel_dlc,
+        normandy,
+        appearances,
+        weapons_powers,
+        intel,
+    } = plot_db;
+
+    plot_category(index, &tab_path(path, "General"), general);
+    plot_categories(index, &tab_path(path, "Crew"), crew);
+    plot_categories(index, &tab_path(path, "Romance"), romance);
+    plot_categories(index, &tab_path(path, "Missions"), missions);
+    plot_categories(index, &tab_path(path, "Normandy"), normandy);
+    plot_categories(index, &tab_path(path, "Citadel DLC"), citadel_dlc);
+    plot_categories(index, &tab_path(path, "Appearances"), appearances);
+    for (title, variable) in weapons_powers {
+        let labels = variable.booleans.values().chain(variable.variables.values());
+        for label in labels {
+            let mut path: Vec<String> =
+                tab_path(path, "Weapons / Powers").into_iter().map(str::to_owned).collect();
+            path.extend([title.clone(), label.clone()]);
+            index.push(SearchEntry::new(path, None));
+        }
+    }
+    plot_category(index, &tab_path(path, "Intel"), intel);
+}
+
+fn raw_plot(index: &mut Vec<SearchEntry>, plot_db: Option<Rc<RawPlotDb>>) {
+    if let Some(plot_db) = plot_db {
+        let kinds = [
+            (PlotKind::Boolean, "Booleans"),
+            (PlotKind::Integer, "Integers"),
+            (PlotKind::Float, "Floats"),
+        ];
+        for (kind, tab) in kinds {
+            for (id, label) in plot_db.labels(kind) {
+                // Same label as the `RawPlot` rows
+                let path =
+                    vec!["Raw Plot".to_owned(), tab.to_owned(), format!("{} - {}", id, label)];
+                index.push(SearchEntry::new(path, None));
+            }
+        }
+    }
+}
+
+// Items
+fn items(index: &mut Vec<SearchEntry>, item_db: &Me1ItemDb, save_data: &Me1LeSaveData) {
+    let player = save_data.player();
+    let inventory = player.inventory();
+    let squad = save_data.squad();
+
+    let mut names = HashSet::new();
+    let mut add_items = |items: &[RcRef<Item>]| {
+        for item in items {
+            let item = item.borrow();
+            let db_item =
+                DbItem { item_id: item.item_id(), manufacturer_id: item.manufacturer_id() };
+            if let Some(name) = item_db.get(&db_item) {
+                if names.insert(name.clone()) {
+                    index.push(SearchEntry::new(vec!["Inventory".to_owned(), name.clone()], None));
+                }
+            }
+        }
+    };
+
+    add_items(&inventory.equipment());
+    add_items(&inventory.quick_slots());
+    add_items(&inventory.inventory());
+    for henchman in squad.iter() {
+        let henchman = henchman.borrow();
+        add_items(&henchman.equipment());
+        add_items(&henchman.quick_slots());
+    }
+}
+
+// Raw data
+fn raw_data(index: &mut Vec<SearchEntry>, root: &str, save_data: Option<Dynamic>) {
+    if let Some(save_data) = save_data {
+        let mut path = vec!["Raw Data".to_owned(), root.to_owned()];
+        raw_data_fields(index, &mut path, &save_data);
+    }
+}
+
+fn raw_data_fields(index: &mut Vec<SearchEntry>, path: &mut Vec<String>, value: &Dynamic) {
+    if let Some(map) = value.read_lock::<Map>() {
+        for (field, value) in map.iter() {
+            // Same label as the `RawUi` derive
+            path.push(title_case(field));
+            index.push(SearchEntry::new(path.clone(), primitive(value)));
+            raw_data_fields(index, path, value);
+            path.pop();
+        }
+    } else if let Some(array) = value.read_lock::<Array>() {
+        for (idx, value) in array.iter().enumerate() {
+            // Numbers and booleans in lists are mostly raw bytes and plots, not worth indexing
+            if value.is_map() || value.is_array() {
+                path.push(idx.to_string());
+                raw_data_fields(index, path, value);
+                path.pop();
+            } else if value.is_string() {
+                path.push(idx.to_string());
+                index.push(SearchEntry::new(path.clone(), primitive(value)));
+                path.pop();
+            }
+        }
+    }
+}
+
+fn primitive(value: &Dynamic) -> Option<String> {
+    (!value.is_map() && !value.is_array() && !value.is_unit()).then(|| value.to_string())
+}
+
+fn title_case(field: &str) -> String {
+    field
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| {
+                    first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect()
+                })
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::{save_data::mass_effect_2::Me2SaveGame, unreal};
+
+    #[test]
+    fn search_raw_data() -> Result<()> {
+        let input = fs::read("test/ME2Save.pcsav")?;
+        let me2_save_game: RcRef<Me2SaveGame> = unreal::Deserializer::from_bytes(&input)?;
+        let first_name = me2_save_game.borrow().player().first_name().clone();
+        let save_game = SaveGame::MassEffect2 {
+            file_path: PathBuf::from("test/ME2Save.pcsav"),
+            save_game: me2_save_game,
+        };
+
+        let index = build_index(&save_game, &Databases::default());
+        let query = first_name.to_lowercase();
+        let entry = index
+            .iter()
+            .find(|entry| entry.matches(&query) && entry.path.last().unwrap() == "First Name")
+            .expect("first name not found");
+
+        assert_eq!(entry.path[..3], ["Raw Data", "Mass Effect 2", "Player"]);
+        assert_eq!(entry.value.as_deref(), Some(first_name.as_str()));
+
+        Ok(())
+    }
+}